@@ -0,0 +1,122 @@
+use crate::api::http::HttpClient;
+use crate::models::air_quality::{AirQualityData, OpenMeteoAirQualityResponse};
+use crate::models::Location;
+use anyhow::{Context, Result};
+
+pub const AIR_QUALITY_API_URL: &str = "https://air-quality-api.open-meteo.com/v1/air-quality";
+
+/// Furthest ahead the air-quality API forecasts
+const MAX_FORECAST_DAYS: usize = 7;
+
+/// Fetch current air quality, an hourly AQI forecast and the pollen
+/// outlook. Open-Meteo covers the whole globe here, so this is used
+/// whichever weather provider is selected. The forecast covers up to
+/// `forecast_days` days.
+pub async fn fetch_air_quality(
+    http: &HttpClient,
+    url: &str,
+    location: &Location,
+    forecast_days: usize,
+) -> Result<AirQualityData> {
+    let current_params = [
+        "us_aqi",
+        "european_aqi",
+        "pm2_5",
+        "pm10",
+        "ozone",
+        "nitrogen_dioxide",
+    ]
+    .join(",");
+
+    // Pollen comes back empty outside the pollen model's coverage
+    let hourly_params = [
+        "us_aqi",
+        "european_aqi",
+        "grass_pollen",
+        "birch_pollen",
+        "alder_pollen",
+        "ragweed_pollen",
+        "olive_pollen",
+        "mugwort_pollen",
+    ]
+    .join(",");
+
+    let params = [
+        ("latitude", location.latitude.to_string()),
+        ("longitude", location.longitude.to_string()),
+        ("current", current_params),
+        ("hourly", hourly_params),
+        ("timezone", "auto".to_string()),
+        (
+            "forecast_days",
+            forecast_days.min(MAX_FORECAST_DAYS).to_string(),
+        ),
+    ];
+
+    let response: OpenMeteoAirQualityResponse = http
+        .send(http.get(url).query(&params))
+        .await
+        .context("Failed to fetch air quality data")?
+        .error_for_status()
+        .context("Failed to fetch air quality data")?
+        .json()
+        .await
+        .context("Failed to parse air quality response")?;
+
+    Ok(response.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::test_server::{denver, MockResponse, TestServer};
+
+    const AIR_QUALITY: &str = include_str!("../../tests/fixtures/air_quality/air_quality.json");
+
+    #[tokio::test]
+    async fn test_fetch_air_quality() {
+        let server =
+            TestServer::start(vec![("/air-quality", MockResponse::json(AIR_QUALITY))]).await;
+        let url = format!("{}/air-quality", server.url());
+
+        let data = fetch_air_quality(&HttpClient::new(), &url, &denver(), 16)
+            .await
+            .unwrap();
+
+        assert_eq!(data.current.us_aqi, Some(156.0));
+        assert_eq!(data.current.european_aqi, Some(72.0));
+        assert_eq!(data.current.pm2_5, Some(64.3));
+        assert_eq!(data.current.nitrogen_dioxide, Some(11.2));
+        assert_eq!(data.hourly.len(), 6);
+        assert_eq!(data.us_aqi_at("2024-07-25T10:00"), Some(158.0));
+        // Gaps in the model output come through as missing values
+        assert_eq!(data.us_aqi_at("2024-07-25T13:00"), None);
+        // No pollen model covers Denver
+        assert!(data.pollen.is_empty());
+
+        let request = &server.requests()[0];
+        assert!(request.query.contains("latitude=39.74"));
+        assert!(request.query.contains("timezone=auto"));
+        // Clamped to the air-quality API's horizon
+        assert!(request.query.contains("forecast_days=7"));
+        assert!(request.query.contains("us_aqi%2Ceuropean_aqi"));
+        assert!(request.query.contains("grass_pollen"));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_air_quality_error() {
+        let server = TestServer::start(vec![(
+            "/air-quality",
+            MockResponse::status(
+                400,
+                r#"{"error":true,"reason":"Latitude must be in range"}"#,
+            ),
+        )])
+        .await;
+        let url = format!("{}/air-quality", server.url());
+
+        assert!(fetch_air_quality(&HttpClient::new(), &url, &denver(), 16)
+            .await
+            .is_err());
+    }
+}
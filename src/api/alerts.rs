@@ -0,0 +1,189 @@
+use anyhow::{Context, Result};
+use reqwest::Url;
+
+use crate::api::http::HttpClient;
+use crate::config::AlertsConfig;
+use crate::models::alerts::{Alert, Alerts, NwsAlertsResponse};
+use crate::models::cap::parse_cap;
+use crate::models::Location;
+
+/// Places api.weather.gov issues alerts for, as geolocation and geocoding
+/// services name them
+const NWS_COUNTRIES: &[&str] = &[
+    "United States",
+    "United States of America",
+    "US",
+    "USA",
+    "Puerto Rico",
+    "PR",
+    "Guam",
+    "GU",
+    "U.S. Virgin Islands",
+    "VI",
+    "American Samoa",
+    "AS",
+    "Northern Mariana Islands",
+    "MP",
+];
+
+/// Gather the alerts in force at a location from the NWS (for US places)
+/// and each configured CAP feed, most severe first. A source that can't be
+/// reached is listed in `failed_sources` rather than failing the rest.
+pub async fn fetch_alerts(
+    http: &HttpClient,
+    nws_url: &str,
+    config: &AlertsConfig,
+    location: &Location,
+) -> Alerts {
+    let mut alerts = Alerts::default();
+
+    if config.nws && NWS_COUNTRIES.contains(&location.country.as_str()) {
+        match fetch_nws_alerts(http, nws_url, location).await {
+            Ok(found) => alerts.alerts.extend(found),
+            Err(_) => alerts
+                .failed_sources
+                .push("National Weather Service".to_string()),
+        }
+    }
+
+    for url in &config.cap_feeds {
+        match fetch_cap_feed(http, url).await {
+            // Feeds cover a whole region or country, so keep what's here
+            Ok(found) => alerts.alerts.extend(
+                found
+                    .into_iter()
+                    .filter(|a| a.covers(location.latitude, location.longitude, &config.zones)),
+            ),
+            Err(_) => alerts.failed_sources.push(
+                Url::parse(url)
+                    .ok()
+                    .and_then(|u| u.host_str().map(str::to_string))
+                    .unwrap_or_else(|| url.clone()),
+            ),
+        }
+    }
+
+    // The same alert can come from more than one source
+    let mut seen = std::collections::HashSet::new();
+    alerts
+        .alerts
+        .retain(|a| a.id.is_empty() || seen.insert(a.id.clone()));
+    alerts
+        .alerts
+        .sort_by(|a, b| b.severity.cmp(&a.severity).then(b.urgency.cmp(&a.urgency)));
+    alerts
+}
+
+/// Active alerts for a point from api.weather.gov, as JSON-LD
+pub async fn fetch_nws_alerts(
+    http: &HttpClient,
+    base_url: &str,
+    location: &Location,
+) -> Result<Vec<Alert>> {
+    let url = format!("{}/alerts/active", base_url.trim_end_matches('/'));
+    // NWS rejects coordinates with more than four decimal places
+    let point = format!("{:.4},{:.4}", location.latitude, location.longitude);
+
+    let response: NwsAlertsResponse = http
+        .send(
+            http.get(&url)
+                .query(&[("point", point)])
+                .header("Accept", "application/ld+json"),
+        )
+        .await
+        .context("Failed to fetch NWS alerts")?
+        .error_for_status()
+        .context("Failed to fetch NWS alerts")?
+        .json()
+        .await
+        .context("Failed to parse NWS alerts")?;
+
+    Ok(response.into_alerts())
+}
+
+/// Every alert in a CAP document or Atom feed of CAP alerts
+pub async fn fetch_cap_feed(http: &HttpClient, url: &str) -> Result<Vec<Alert>> {
+    let body = http
+        .send(http.get(url))
+        .await
+        .context("Failed to fetch CAP feed")?
+        .error_for_status()
+        .context("Failed to fetch CAP feed")?
+        .text()
+        .await
+        .context("Failed to read CAP feed")?;
+
+    parse_cap(&body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::test_server::{amsterdam, denver, MockResponse, TestServer};
+    use crate::models::alerts::Severity;
+
+    const NWS_ALERTS: &str = include_str!("../../tests/fixtures/alerts/nws_alerts.json");
+    const WARNING: &str = include_str!("../../tests/fixtures/alerts/cap_warning.xml");
+    const FEED: &str = include_str!("../../tests/fixtures/alerts/meteoalarm_feed.xml");
+
+    #[tokio::test]
+    async fn test_fetch_nws_alerts() {
+        let server =
+            TestServer::start(vec![("/alerts/active", MockResponse::json(NWS_ALERTS))]).await;
+        let config = AlertsConfig::default();
+
+        let alerts = fetch_alerts(&HttpClient::new(), &server.url(), &config, &denver()).await;
+
+        // The test message is dropped and the warning sorts first
+        assert!(alerts.failed_sources.is_empty());
+        assert_eq!(alerts.alerts.len(), 2);
+        let warning = &alerts.alerts[0];
+        assert_eq!(warning.event, "Severe Thunderstorm Warning");
+        assert_eq!(warning.severity, Severity::Severe);
+        assert!(warning.instruction.is_some());
+        // Ends wins over the message's own expiry
+        assert_eq!(
+            alerts.alerts[1].expires.unwrap().to_rfc3339(),
+            "2024-06-01T20:00:00-06:00"
+        );
+
+        let request = &server.requests_to("/alerts/active")[0];
+        assert_eq!(request.query, "point=39.7400%2C-104.9900");
+        assert_eq!(request.header("accept"), Some("application/ld+json"));
+    }
+
+    #[tokio::test]
+    async fn test_cap_feeds_filtered_to_location() {
+        let server = TestServer::start(vec![
+            ("/warning.xml", MockResponse::xml(WARNING)),
+            ("/netherlands", MockResponse::xml(FEED)),
+            ("/broken", MockResponse::status(404, "")),
+        ])
+        .await;
+        let config = AlertsConfig {
+            nws: false,
+            cap_feeds: vec![
+                format!("{}/warning.xml", server.url()),
+                format!("{}/netherlands", server.url()),
+                format!("{}/broken", server.url()),
+            ],
+            zones: vec!["EMMA_ID:NL007".to_string()],
+        };
+
+        let alerts = fetch_alerts(&HttpClient::new(), "", &config, &amsterdam()).await;
+
+        // Only the Noord-Holland wind warning is for Amsterdam
+        assert_eq!(alerts.alerts.len(), 1);
+        assert_eq!(alerts.alerts[0].event, "Moderate wind warning");
+        assert_eq!(alerts.failed_sources, vec!["127.0.0.1".to_string()]);
+
+        // The polygon warning covers Denver, with no zones configured there
+        let config = AlertsConfig {
+            zones: Vec::new(),
+            ..config
+        };
+        let alerts = fetch_alerts(&HttpClient::new(), "", &config, &denver()).await;
+        assert_eq!(alerts.alerts.len(), 1);
+        assert_eq!(alerts.alerts[0].event, "Severe Thunderstorm Warning");
+    }
+}
@@ -0,0 +1,79 @@
+use crate::api::http::HttpClient;
+use crate::models::ensemble::{EnsembleData, OpenMeteoEnsembleResponse};
+use crate::models::Location;
+use anyhow::{Context, Result};
+
+pub const ENSEMBLE_API_URL: &str = "https://ensemble-api.open-meteo.com/v1/ensemble";
+
+/// NCEP GEFS: 31 members, global, and out to 35 days, so it covers every
+/// forecast horizon the config allows
+pub const ENSEMBLE_MODEL: &str = "gfs_seamless";
+
+/// Fetch every ensemble member's hourly temperature and precipitation and
+/// summarise them as percentiles, for up to `forecast_days` days
+pub async fn fetch_ensemble(
+    http: &HttpClient,
+    url: &str,
+    location: &Location,
+    forecast_days: usize,
+) -> Result<EnsembleData> {
+    let params = [
+        ("latitude", location.latitude.to_string()),
+        ("longitude", location.longitude.to_string()),
+        ("hourly", "temperature_2m,precipitation".to_string()),
+        ("models", ENSEMBLE_MODEL.to_string()),
+        ("timezone", "auto".to_string()),
+        ("forecast_days", forecast_days.to_string()),
+    ];
+
+    let response: OpenMeteoEnsembleResponse = http
+        .send(http.get(url).query(&params))
+        .await
+        .context("Failed to fetch ensemble forecast")?
+        .error_for_status()
+        .context("Failed to fetch ensemble forecast")?
+        .json()
+        .await
+        .context("Failed to parse ensemble response")?;
+
+    Ok(response.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::test_server::{newport, MockResponse, TestServer};
+    use crate::models::ensemble::Confidence;
+
+    const ENSEMBLE: &str = include_str!("../../tests/fixtures/ensemble/ensemble.json");
+
+    #[tokio::test]
+    async fn test_fetch_ensemble() {
+        let server = TestServer::start(vec![("/ensemble", MockResponse::json(ENSEMBLE))]).await;
+        let url = format!("{}/ensemble", server.url());
+
+        let data = fetch_ensemble(&HttpClient::new(), &url, &newport(), 16)
+            .await
+            .unwrap();
+
+        assert_eq!(data.members, 4);
+        assert_eq!(data.hourly.len(), 54);
+        // The last day stops at 05:00 and is left out
+        assert_eq!(data.daily.len(), 2);
+
+        let first = data.day("2024-08-10").unwrap();
+        assert_eq!(first.temp_max.median, 24.75);
+        assert_eq!(first.wet_probability, 0.0);
+        assert_eq!(first.confidence, Confidence::High);
+
+        // Members split on rain, and the one with a gap is skipped
+        let second = data.day("2024-08-11").unwrap();
+        assert_eq!(second.temp_max.median, 26.0);
+        assert_eq!(second.wet_probability, 50.0);
+        assert_eq!(second.confidence, Confidence::Low);
+
+        let query = &server.requests()[0].query;
+        assert!(query.contains("forecast_days=16"));
+        assert!(query.contains("models=gfs_seamless"));
+    }
+}
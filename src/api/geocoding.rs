@@ -1,28 +1,212 @@
+use crate::api::http::HttpClient;
 use crate::models::location::{GeocodingResponse, Location};
 use anyhow::{anyhow, Context, Result};
 
-const GEOCODING_API_URL: &str = "https://geocoding-api.open-meteo.com/v1/search";
+pub const GEOCODING_API_URL: &str = "https://geocoding-api.open-meteo.com/v1/search";
 
-pub async fn lookup_zipcode(zipcode: &str) -> Result<Location> {
-    let client = reqwest::Client::new();
+/// Number of candidates offered when searching for a place by name
+pub const SEARCH_RESULTS: usize = 10;
 
-    let url = format!(
-        "{}?name={}&count=1&language=en&format=json",
-        GEOCODING_API_URL, zipcode
-    );
+/// Split a trailing ISO country code off a search, so "10001, US" searches
+/// for "10001" in the United States. Anything else is searched as typed.
+pub fn parse_query(input: &str) -> (&str, Option<&str>) {
+    if let Some((name, code)) = input.rsplit_once(',') {
+        let (name, code) = (name.trim(), code.trim());
+        if !name.is_empty() && code.len() == 2 && code.chars().all(|c| c.is_ascii_alphabetic()) {
+            return (name, Some(code));
+        }
+    }
+    (input.trim(), None)
+}
+
+/// Search for places matching a name or postal code, best match first,
+/// optionally limited to one country (ISO 3166-1 alpha-2)
+pub async fn search_locations(
+    http: &HttpClient,
+    url: &str,
+    query: &str,
+    country_code: Option<&str>,
+    count: usize,
+) -> Result<Vec<Location>> {
+    let country_code = country_code.map(str::to_ascii_uppercase);
 
-    let response: GeocodingResponse = client
-        .get(&url)
-        .send()
+    let mut params = vec![
+        ("name", query.to_string()),
+        ("count", count.to_string()),
+        ("language", "en".to_string()),
+        ("format", "json".to_string()),
+    ];
+    if let Some(code) = &country_code {
+        params.push(("countryCode", code.clone()));
+    }
+
+    let response: GeocodingResponse = http
+        .send(http.get(url).query(&params))
         .await
         .context("Failed to fetch geocoding data")?
+        .error_for_status()
+        .context("Failed to fetch geocoding data")?
         .json()
         .await
         .context("Failed to parse geocoding response")?;
 
-    response
+    // The API filters by country already; check again in case a mirror doesn't
+    let results: Vec<Location> = response
         .results
-        .and_then(|mut results| results.pop())
-        .map(|r| r.into())
-        .ok_or_else(|| anyhow!("No location found for zipcode: {}", zipcode))
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|r| match (&country_code, &r.country_code) {
+            (Some(wanted), Some(actual)) => wanted.eq_ignore_ascii_case(actual),
+            _ => true,
+        })
+        .map(Location::from)
+        .collect();
+
+    if results.is_empty() {
+        return Err(match country_code {
+            Some(code) => anyhow!("No location found for: {} in {}", query, code),
+            None => anyhow!("No location found for: {}", query),
+        });
+    }
+
+    Ok(results)
+}
+
+/// Look up a saved zipcode, taking the best match. A "zip, CC" zipcode names
+/// its own country, which wins over `country_code`.
+pub async fn lookup_zipcode(
+    http: &HttpClient,
+    url: &str,
+    zipcode: &str,
+    country_code: Option<&str>,
+) -> Result<Location> {
+    let (query, country) = parse_query(zipcode);
+    let country = country.or(country_code);
+    // Ask for the usual number of candidates so a mirror that ignores the
+    // country still leaves one that passes the filter
+    let mut results = search_locations(http, url, query, country, SEARCH_RESULTS).await?;
+    Ok(results.remove(0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::test_server::{MockResponse, TestServer};
+
+    const SPRINGFIELD: &str = include_str!("../../tests/fixtures/geocoding/springfield.json");
+    const ZIP_10001: &str = include_str!("../../tests/fixtures/geocoding/10001.json");
+
+    #[tokio::test]
+    async fn test_search_returns_all_matches() {
+        let server = TestServer::start(vec![("/search", MockResponse::json(SPRINGFIELD))]).await;
+        let url = format!("{}/search", server.url());
+
+        let results = search_locations(
+            &HttpClient::new(),
+            &url,
+            "Springfield",
+            None,
+            SEARCH_RESULTS,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].display_name(), "Springfield, Illinois");
+        assert_eq!(results[1].region.as_deref(), Some("Missouri"));
+        assert_eq!(results[2].timezone, "America/New_York");
+        assert!(server.requests()[0].query.contains("count=10"));
+    }
+
+    #[tokio::test]
+    async fn test_no_results() {
+        let server = TestServer::start(vec![(
+            "/search",
+            MockResponse::json(r#"{"generationtime_ms": 0.5}"#),
+        )])
+        .await;
+        let url = format!("{}/search", server.url());
+
+        let err = lookup_zipcode(&HttpClient::new(), &url, "zzzz", None)
+            .await
+            .unwrap_err();
+        assert_eq!(err.to_string(), "No location found for: zzzz");
+    }
+
+    #[tokio::test]
+    async fn test_query_is_encoded() {
+        let server = TestServer::start(vec![("/search", MockResponse::json(SPRINGFIELD))]).await;
+        let url = format!("{}/search", server.url());
+
+        search_locations(&HttpClient::new(), &url, "São Paulo & Co #1", None, 1)
+            .await
+            .unwrap();
+
+        let query = &server.requests()[0].query;
+        assert!(query.starts_with("name=S%C3%A3o+Paulo+%26+Co+%231&"));
+        assert!(query.contains("count=1"));
+    }
+
+    #[tokio::test]
+    async fn test_country_code_filters_results() {
+        let server = TestServer::start(vec![("/search", MockResponse::json(ZIP_10001))]).await;
+        let url = format!("{}/search", server.url());
+
+        let location = lookup_zipcode(&HttpClient::new(), &url, "10001", Some("us"))
+            .await
+            .unwrap();
+
+        assert_eq!(location.display_name(), "New York, New York");
+        assert!(server.requests()[0].query.contains("countryCode=US"));
+
+        let err = lookup_zipcode(&HttpClient::new(), &url, "10001", Some("CA"))
+            .await
+            .unwrap_err();
+        assert_eq!(err.to_string(), "No location found for: 10001 in CA");
+    }
+
+    #[tokio::test]
+    async fn test_lookup_zipcode_with_country() {
+        let server = TestServer::start(vec![("/search", MockResponse::json(ZIP_10001))]).await;
+        let url = format!("{}/search", server.url());
+
+        // The server ignores the country, like some mirrors, and puts a
+        // French match first
+        let location = lookup_zipcode(&HttpClient::new(), &url, "10001, US", Some("FR"))
+            .await
+            .unwrap();
+
+        assert_eq!(location.display_name(), "New York, New York");
+        let query = &server.requests()[0].query;
+        assert!(query.starts_with("name=10001&"));
+        assert!(query.contains("countryCode=US"));
+        assert!(query.contains("count=10"));
+    }
+
+    #[tokio::test]
+    async fn test_geocoder_error_status() {
+        let server = TestServer::start(vec![(
+            "/search",
+            MockResponse::status(
+                400,
+                r#"{"error":true,"reason":"Parameter count must be between 1 and 100"}"#,
+            ),
+        )])
+        .await;
+        let url = format!("{}/search", server.url());
+
+        let err = lookup_zipcode(&HttpClient::new(), &url, "10001", None)
+            .await
+            .unwrap_err();
+        assert_eq!(err.to_string(), "Failed to fetch geocoding data");
+    }
+
+    #[test]
+    fn test_parse_query() {
+        assert_eq!(parse_query("10001, US"), ("10001", Some("US")));
+        assert_eq!(parse_query(" SW1A 1AA,gb "), ("SW1A 1AA", Some("gb")));
+        assert_eq!(parse_query("Portland, Oregon"), ("Portland, Oregon", None));
+        assert_eq!(parse_query("New York"), ("New York", None));
+        assert_eq!(parse_query(", US"), (", US", None));
+    }
 }
@@ -1,20 +1,200 @@
-use crate::models::location::{IpApiResponse, Location};
-use anyhow::{Context, Result};
+use crate::api::http::HttpClient;
+use crate::config::{EndpointsConfig, IpProviderKind};
+use crate::models::location::{IpApiResponse, IpInfoResponse, IpWhoisResponse, Location};
+use anyhow::{anyhow, bail, Context, Result};
 
-const IP_API_URL: &str = "https://ipapi.co/json/";
+pub const IP_API_URL: &str = "https://ipapi.co/json/";
+pub const IPWHOIS_API_URL: &str = "https://ipwho.is/";
+pub const IPINFO_API_URL: &str = "https://ipinfo.io/json";
 
-pub async fn get_location_from_ip() -> Result<Location> {
-    let client = reqwest::Client::new();
+/// Locate this machine by its public IP, asking each provider in turn until
+/// one answers
+pub async fn get_location_from_ip(
+    http: &HttpClient,
+    endpoints: &EndpointsConfig,
+    providers: &[IpProviderKind],
+) -> Result<Location> {
+    let mut errors = Vec::new();
+    for &provider in providers {
+        match fetch_location(http, endpoints, provider).await {
+            Ok(location) => return Ok(location),
+            Err(e) => errors.push(format!("{}: {:#}", provider_name(provider), e)),
+        }
+    }
 
-    let response: IpApiResponse = client
-        .get(IP_API_URL)
-        .header("User-Agent", "wxman/0.1.0")
-        .send()
+    if errors.is_empty() {
+        bail!("No IP geolocation providers configured");
+    }
+    Err(anyhow!("IP geolocation failed ({})", errors.join("; ")))
+}
+
+fn provider_name(provider: IpProviderKind) -> &'static str {
+    match provider {
+        IpProviderKind::Ipapi => "ipapi.co",
+        IpProviderKind::Ipwhois => "ipwho.is",
+        IpProviderKind::Ipinfo => "ipinfo.io",
+    }
+}
+
+async fn fetch_location(
+    http: &HttpClient,
+    endpoints: &EndpointsConfig,
+    provider: IpProviderKind,
+) -> Result<Location> {
+    match provider {
+        IpProviderKind::Ipapi => {
+            let response: IpApiResponse = get_json(http, endpoints.ip_geolocation()).await?;
+            Ok(response.into())
+        }
+        IpProviderKind::Ipwhois => {
+            let response: IpWhoisResponse = get_json(http, endpoints.ipwhois()).await?;
+            if !response.success {
+                bail!(response
+                    .message
+                    .unwrap_or_else(|| "lookup failed".to_string()));
+            }
+            let (Some(latitude), Some(longitude)) = (response.latitude, response.longitude) else {
+                bail!("response has no coordinates");
+            };
+            Ok(Location {
+                latitude,
+                longitude,
+                city: response.city.unwrap_or_else(|| "Unknown".to_string()),
+                region: response.region,
+                country: response.country.unwrap_or_default(),
+                timezone: response
+                    .timezone
+                    .map(|tz| tz.id)
+                    .unwrap_or_else(|| "auto".to_string()),
+            })
+        }
+        IpProviderKind::Ipinfo => {
+            let response: IpInfoResponse = get_json(http, endpoints.ipinfo()).await?;
+            let (latitude, longitude) = response
+                .loc
+                .split_once(',')
+                .and_then(|(lat, lon)| Some((lat.trim().parse().ok()?, lon.trim().parse().ok()?)))
+                .ok_or_else(|| anyhow!("invalid coordinates: {}", response.loc))?;
+            Ok(Location {
+                latitude,
+                longitude,
+                city: response.city,
+                region: response.region,
+                country: response.country,
+                timezone: response.timezone,
+            })
+        }
+    }
+}
+
+async fn get_json<T: serde::de::DeserializeOwned>(http: &HttpClient, url: &str) -> Result<T> {
+    http.send(http.get(url))
         .await
         .context("Failed to fetch IP geolocation")?
+        .error_for_status()
+        .context("Failed to fetch IP geolocation")?
         .json()
         .await
-        .context("Failed to parse IP geolocation response")?;
+        .context("Failed to parse IP geolocation response")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::test_server::{MockResponse, TestServer};
+
+    const IPAPI: &str = include_str!("../../tests/fixtures/geolocation/ipapi.json");
+    const IPWHOIS: &str = include_str!("../../tests/fixtures/geolocation/ipwhois.json");
+    const IPINFO: &str = include_str!("../../tests/fixtures/geolocation/ipinfo.json");
+
+    fn endpoints(server: &TestServer) -> EndpointsConfig {
+        EndpointsConfig {
+            ip_geolocation: Some(format!("{}/ipapi", server.url())),
+            ipwhois: Some(format!("{}/ipwhois", server.url())),
+            ipinfo: Some(format!("{}/ipinfo", server.url())),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_each_provider() {
+        let server = TestServer::start(vec![
+            ("/ipapi", MockResponse::json(IPAPI)),
+            ("/ipwhois", MockResponse::json(IPWHOIS)),
+            ("/ipinfo", MockResponse::json(IPINFO)),
+        ])
+        .await;
+        let http = HttpClient::new();
+        let endpoints = endpoints(&server);
+
+        for provider in [
+            IpProviderKind::Ipapi,
+            IpProviderKind::Ipwhois,
+            IpProviderKind::Ipinfo,
+        ] {
+            let location = get_location_from_ip(&http, &endpoints, &[provider])
+                .await
+                .unwrap();
+            assert_eq!(location.city, "Mountain View", "{:?}", provider);
+            assert_eq!(location.region.as_deref(), Some("California"));
+            assert_eq!(location.timezone, "America/Los_Angeles");
+            assert!((location.latitude - 37.4).abs() < 0.1);
+            assert!((location.longitude + 122.08).abs() < 0.1);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_falls_through_providers_in_order() {
+        let server = TestServer::start(vec![
+            (
+                "/ipapi",
+                MockResponse::status(429, r#"{"error": true, "reason": "RateLimited"}"#),
+            ),
+            (
+                "/ipwhois",
+                MockResponse::json(r#"{"success": false, "message": "Reserved range"}"#),
+            ),
+            ("/ipinfo", MockResponse::json(IPINFO)),
+        ])
+        .await;
+        let http = HttpClient::new().with_base_delay(std::time::Duration::from_millis(1));
+        let providers = [
+            IpProviderKind::Ipapi,
+            IpProviderKind::Ipwhois,
+            IpProviderKind::Ipinfo,
+        ];
+
+        let location = get_location_from_ip(&http, &endpoints(&server), &providers)
+            .await
+            .unwrap();
+        assert_eq!(location.country, "US");
+        assert_eq!(server.requests_to("/ipwhois").len(), 1);
+        assert_eq!(server.requests_to("/ipinfo").len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_reports_every_failure() {
+        let server = TestServer::start(vec![(
+            "/ipwhois",
+            MockResponse::json(r#"{"success": false, "message": "Reserved range"}"#),
+        )])
+        .await;
+
+        let err = get_location_from_ip(
+            &HttpClient::new(),
+            &endpoints(&server),
+            &[IpProviderKind::Ipwhois],
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "IP geolocation failed (ipwho.is: Reserved range)"
+        );
 
-    Ok(response.into())
+        let err = get_location_from_ip(&HttpClient::new(), &endpoints(&server), &[])
+            .await
+            .unwrap_err();
+        assert_eq!(err.to_string(), "No IP geolocation providers configured");
+    }
 }
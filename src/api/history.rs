@@ -0,0 +1,144 @@
+use crate::api::http::HttpClient;
+use crate::models::history::{DayHistory, OpenMeteoArchiveResponse};
+use crate::models::normals::ClimateNormals;
+use crate::models::Location;
+use anyhow::{anyhow, Context, Result};
+use chrono::{Months, NaiveDate};
+
+pub const ARCHIVE_API_URL: &str = "https://archive-api.open-meteo.com/v1/archive";
+
+/// How many past years a day is compared against
+pub const HISTORY_YEARS: u32 = 10;
+
+/// The current WMO climatological standard normal period
+const NORMALS_PERIOD: (&str, &str) = ("1991-01-01", "2020-12-31");
+
+const DAILY_PARAMS: &str = "temperature_2m_max,temperature_2m_min,precipitation_sum";
+
+/// Average the weather on the same calendar date over the past
+/// [`HISTORY_YEARS`] years. The whole span comes back in one request and is
+/// narrowed down to the matching days locally.
+pub async fn fetch_day_history(
+    http: &HttpClient,
+    url: &str,
+    location: &Location,
+    date: NaiveDate,
+) -> Result<DayHistory> {
+    let start = date - Months::new(12 * HISTORY_YEARS);
+    let end = date - Months::new(12);
+
+    let params = [
+        ("latitude", location.latitude.to_string()),
+        ("longitude", location.longitude.to_string()),
+        ("start_date", start.format("%Y-%m-%d").to_string()),
+        ("end_date", end.format("%Y-%m-%d").to_string()),
+        ("daily", DAILY_PARAMS.to_string()),
+        ("timezone", "auto".to_string()),
+    ];
+
+    let response = fetch_archive(http, url, &params).await?;
+    DayHistory::from_archive(&response, date)
+        .ok_or_else(|| anyhow!("No historical weather for {}", date.format("%b %-d")))
+}
+
+/// Compute 1991-2020 normals for the grid point around a location. That's
+/// thirty years of daily data, so callers should keep the result.
+pub async fn fetch_normals(
+    http: &HttpClient,
+    url: &str,
+    location: &Location,
+) -> Result<ClimateNormals> {
+    let (latitude, longitude) = ClimateNormals::grid(location.latitude, location.longitude);
+    let params = [
+        ("latitude", latitude.to_string()),
+        ("longitude", longitude.to_string()),
+        ("start_date", NORMALS_PERIOD.0.to_string()),
+        ("end_date", NORMALS_PERIOD.1.to_string()),
+        ("daily", DAILY_PARAMS.to_string()),
+        ("timezone", "auto".to_string()),
+    ];
+
+    let response = fetch_archive(http, url, &params).await?;
+    ClimateNormals::from_archive(&response, latitude, longitude)
+        .ok_or_else(|| anyhow!("No climate data for this location"))
+}
+
+async fn fetch_archive(
+    http: &HttpClient,
+    url: &str,
+    params: &[(&str, String)],
+) -> Result<OpenMeteoArchiveResponse> {
+    http.send(http.get(url).query(params))
+        .await
+        .context("Failed to fetch historical weather")?
+        .error_for_status()
+        .context("Failed to fetch historical weather")?
+        .json()
+        .await
+        .context("Failed to parse historical weather response")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::test_server::{denver, MockResponse, TestServer};
+
+    const ARCHIVE: &str = include_str!("../../tests/fixtures/history/archive.json");
+
+    #[tokio::test]
+    async fn test_fetch_day_history() {
+        let server = TestServer::start(vec![("/archive", MockResponse::json(ARCHIVE))]).await;
+        let url = format!("{}/archive", server.url());
+        let date = NaiveDate::from_ymd_opt(2025, 10, 16).unwrap();
+
+        let history = fetch_day_history(&HttpClient::new(), &url, &denver(), date)
+            .await
+            .unwrap();
+
+        assert_eq!(history.years, 3);
+        assert!((history.temp_max - 18.666).abs() < 0.01);
+        assert!((history.temp_min - 4.333).abs() < 0.01);
+        assert!((history.precipitation - 1.0).abs() < 0.01);
+
+        let query = &server.requests()[0].query;
+        assert!(query.contains("start_date=2015-10-16"));
+        assert!(query.contains("end_date=2024-10-16"));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_day_history_without_data() {
+        let server = TestServer::start(vec![("/archive", MockResponse::json(ARCHIVE))]).await;
+        let url = format!("{}/archive", server.url());
+        let date = NaiveDate::from_ymd_opt(2025, 3, 1).unwrap();
+
+        let err = fetch_day_history(&HttpClient::new(), &url, &denver(), date)
+            .await
+            .unwrap_err();
+        assert_eq!(err.to_string(), "No historical weather for Mar 1");
+    }
+
+    #[tokio::test]
+    async fn test_fetch_normals() {
+        let server = TestServer::start(vec![("/archive", MockResponse::json(ARCHIVE))]).await;
+        let url = format!("{}/archive", server.url());
+
+        let normals = fetch_normals(&HttpClient::new(), &url, &denver())
+            .await
+            .unwrap();
+
+        assert_eq!((normals.latitude, normals.longitude), (39.7, -105.0));
+        let normal = normals
+            .for_date(NaiveDate::from_ymd_opt(2025, 10, 16).unwrap())
+            .unwrap();
+        assert!((normal.temp_max - 18.011).abs() < 0.01);
+        assert!((normal.precipitation_probability - 33.33).abs() < 0.01);
+        // The fixture only covers mid-October
+        assert!(normals
+            .for_date(NaiveDate::from_ymd_opt(2025, 4, 1).unwrap())
+            .is_none());
+
+        let query = &server.requests()[0].query;
+        assert!(query.contains("latitude=39.7&longitude=-105&"));
+        assert!(query.contains("start_date=1991-01-01&end_date=2020-12-31"));
+    }
+}
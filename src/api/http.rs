@@ -0,0 +1,209 @@
+//! Shared HTTP client used for every outbound request.
+//!
+//! Applies connect/read timeouts so a hung endpoint can't freeze the UI, sends
+//! a consistent User-Agent, and retries throttled or failing requests (429 and
+//! 5xx) a bounded number of times with exponential backoff and jitter.
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use reqwest::header::RETRY_AFTER;
+use reqwest::{RequestBuilder, Response, StatusCode};
+
+pub const USER_AGENT: &str = concat!("wxman/", env!("CARGO_PKG_VERSION"));
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+const MAX_RETRIES: u32 = 2;
+const BASE_DELAY: Duration = Duration::from_millis(500);
+/// Longer `Retry-After` waits are not honored; the response is returned as-is
+/// so the caller can report it rather than stalling the refresh
+const MAX_DELAY: Duration = Duration::from_secs(10);
+
+/// Cheap to clone; clones share the underlying connection pool
+#[derive(Clone)]
+pub struct HttpClient {
+    client: reqwest::Client,
+    max_retries: u32,
+    base_delay: Duration,
+}
+
+impl HttpClient {
+    pub fn new() -> Self {
+        let client = reqwest::Client::builder()
+            .user_agent(USER_AGENT)
+            .connect_timeout(CONNECT_TIMEOUT)
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .expect("HTTP client configuration is valid");
+
+        Self {
+            client,
+            max_retries: MAX_RETRIES,
+            base_delay: BASE_DELAY,
+        }
+    }
+
+    /// Shorten the backoff so retry paths can be exercised quickly
+    #[cfg(test)]
+    pub fn with_base_delay(mut self, base_delay: Duration) -> Self {
+        self.base_delay = base_delay;
+        self
+    }
+
+    pub fn get(&self, url: &str) -> RequestBuilder {
+        self.client.get(url)
+    }
+
+    /// Send a request, retrying 429/5xx responses and timeouts/connection
+    /// failures. The final response is returned whatever its status, so
+    /// callers still decide how to report errors.
+    pub async fn send(&self, request: RequestBuilder) -> reqwest::Result<Response> {
+        let mut attempt = 0;
+        loop {
+            // The last attempt (or a request whose body can't be cloned) sends
+            // the original and returns whatever comes back
+            let Some(retry) = (attempt < self.max_retries)
+                .then(|| request.try_clone())
+                .flatten()
+            else {
+                return request.send().await;
+            };
+
+            let delay = match retry.send().await {
+                Ok(response) if is_retryable(response.status()) => match retry_after(&response) {
+                    Some(wait) if wait > MAX_DELAY => return Ok(response),
+                    Some(wait) => wait,
+                    None => self.backoff(attempt),
+                },
+                Ok(response) => return Ok(response),
+                Err(e) if e.is_timeout() || e.is_connect() => self.backoff(attempt),
+                Err(e) => return Err(e),
+            };
+
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
+    /// Exponential backoff with up to 50% random jitter
+    fn backoff(&self, attempt: u32) -> Duration {
+        let delay = self.base_delay * 2u32.pow(attempt);
+        let jitter = RandomState::new().build_hasher().finish() % 1000;
+        (delay + delay.mul_f64(jitter as f64 / 2000.0)).min(MAX_DELAY)
+    }
+}
+
+impl Default for HttpClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn is_retryable(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+/// `Retry-After` is either a number of seconds or an HTTP date
+fn retry_after(response: &Response) -> Option<Duration> {
+    let value = response.headers().get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let at = DateTime::parse_from_rfc2822(value)
+        .ok()?
+        .with_timezone(&Utc);
+    Some((at - Utc::now()).to_std().unwrap_or(Duration::ZERO))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::test_server::{MockResponse, TestServer};
+
+    fn client() -> HttpClient {
+        HttpClient::new().with_base_delay(Duration::from_millis(1))
+    }
+
+    #[tokio::test]
+    async fn test_sends_user_agent() {
+        let server = TestServer::start(vec![("/", MockResponse::json("{}"))]).await;
+        let http = client();
+        http.send(http.get(&server.url())).await.unwrap();
+
+        let request = &server.requests()[0];
+        assert_eq!(request.header("user-agent"), Some(USER_AGENT));
+        assert!(USER_AGENT.ends_with(env!("CARGO_PKG_VERSION")));
+    }
+
+    #[tokio::test]
+    async fn test_retries_server_errors() {
+        let server = TestServer::start(vec![
+            ("/", MockResponse::status(503, "")),
+            ("/", MockResponse::status(500, "")),
+            ("/", MockResponse::json("{}")),
+        ])
+        .await;
+        let http = client();
+        let response = http.send(http.get(&server.url())).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(server.requests().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_after_max_retries() {
+        let server = TestServer::start(vec![("/", MockResponse::status(502, ""))]).await;
+        let http = client();
+        let response = http.send(http.get(&server.url())).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::BAD_GATEWAY);
+        assert_eq!(server.requests().len(), MAX_RETRIES as usize + 1);
+    }
+
+    #[tokio::test]
+    async fn test_does_not_retry_client_errors() {
+        let server = TestServer::start(vec![("/", MockResponse::status(404, ""))]).await;
+        let http = client();
+        let response = http.send(http.get(&server.url())).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_honors_retry_after() {
+        let server = TestServer::start(vec![
+            (
+                "/",
+                MockResponse::status(429, "").header("Retry-After", "0"),
+            ),
+            ("/", MockResponse::json("{}")),
+        ])
+        .await;
+        let http = client();
+        let response = http.send(http.get(&server.url())).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        // A wait beyond the cap is handed back instead of stalling
+        let server = TestServer::start(vec![(
+            "/",
+            MockResponse::status(429, "").header("Retry-After", "3600"),
+        )])
+        .await;
+        let response = http.send(http.get(&server.url())).await.unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[test]
+    fn test_backoff_grows() {
+        let http = HttpClient::new();
+        let first = http.backoff(0);
+        let second = http.backoff(1);
+        assert!(first >= BASE_DELAY && first <= BASE_DELAY.mul_f64(1.5));
+        assert!(second >= BASE_DELAY * 2 && second <= (BASE_DELAY * 2).mul_f64(1.5));
+    }
+}
@@ -0,0 +1,96 @@
+use crate::api::http::HttpClient;
+use crate::models::marine::{MarineData, OpenMeteoMarineResponse};
+use crate::models::Location;
+use anyhow::{Context, Result};
+
+pub const MARINE_API_URL: &str = "https://marine-api.open-meteo.com/v1/marine";
+
+/// Fetch current sea conditions and an hourly wave forecast. Like air
+/// quality this always comes from Open-Meteo, whichever weather provider is
+/// selected. The marine API reaches as far ahead as the weather forecast.
+pub async fn fetch_marine(
+    http: &HttpClient,
+    url: &str,
+    location: &Location,
+    forecast_days: usize,
+) -> Result<MarineData> {
+    let current_params = [
+        "wave_height",
+        "wave_direction",
+        "wave_period",
+        "swell_wave_height",
+        "swell_wave_direction",
+        "swell_wave_period",
+        "sea_surface_temperature",
+    ]
+    .join(",");
+
+    let params = [
+        ("latitude", location.latitude.to_string()),
+        ("longitude", location.longitude.to_string()),
+        ("current", current_params),
+        (
+            "hourly",
+            "wave_height,wave_period,swell_wave_height".to_string(),
+        ),
+        ("timezone", "auto".to_string()),
+        ("forecast_days", forecast_days.to_string()),
+    ];
+
+    let response: OpenMeteoMarineResponse = http
+        .send(http.get(url).query(&params))
+        .await
+        .context("Failed to fetch marine data")?
+        .error_for_status()
+        .context("Failed to fetch marine data")?
+        .json()
+        .await
+        .context("Failed to parse marine response")?;
+
+    Ok(response.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::test_server::{newport, MockResponse, TestServer};
+
+    const MARINE: &str = include_str!("../../tests/fixtures/marine/marine.json");
+    const INLAND: &str = include_str!("../../tests/fixtures/marine/inland.json");
+
+    #[tokio::test]
+    async fn test_fetch_marine() {
+        let server = TestServer::start(vec![("/marine", MockResponse::json(MARINE))]).await;
+        let url = format!("{}/marine", server.url());
+
+        let data = fetch_marine(&HttpClient::new(), &url, &newport(), 5)
+            .await
+            .unwrap();
+
+        assert!(data.has_data());
+        assert_eq!(data.current.wave_height, Some(1.24));
+        assert_eq!(data.current.wave_direction, Some(196.0));
+        assert_eq!(data.current.swell_wave_period, Some(9.45));
+        assert_eq!(data.current.sea_surface_temperature, Some(18.6));
+        assert_eq!(data.hourly.len(), 4);
+        let hour = data.at("2024-08-10T11:00").unwrap();
+        assert_eq!(hour.wave_height, Some(1.3));
+        assert_eq!(hour.wave_period, Some(6.2));
+
+        let request = &server.requests()[0];
+        assert!(request.query.contains("latitude=41.49"));
+        assert!(request.query.contains("forecast_days=5"));
+        assert!(request.query.contains("sea_surface_temperature"));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_marine_inland() {
+        let server = TestServer::start(vec![("/marine", MockResponse::json(INLAND))]).await;
+        let url = format!("{}/marine", server.url());
+
+        let data = fetch_marine(&HttpClient::new(), &url, &newport(), 5)
+            .await
+            .unwrap();
+        assert!(!data.has_data());
+    }
+}
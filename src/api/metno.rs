@@ -0,0 +1,250 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use anyhow::{anyhow, Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use reqwest::header::{EXPIRES, IF_MODIFIED_SINCE, LAST_MODIFIED, USER_AGENT};
+use reqwest::StatusCode;
+
+use crate::api::http::{HttpClient, USER_AGENT as BASE_USER_AGENT};
+use crate::api::provider::WeatherProvider;
+use crate::models::metno::{MetNoForecast, MetNoResponse};
+use crate::models::{Location, WeatherData};
+
+pub const METNO_API_URL: &str = "https://api.met.no/weatherapi/locationforecast/2.0";
+
+/// Weather provider backed by MET Norway's Locationforecast 2.0 API.
+///
+/// The terms of service require an identifying User-Agent and that clients
+/// respect `Expires` and revalidate with `If-Modified-Since`, so responses are
+/// kept in memory per location and only re-requested once they expire.
+pub struct MetNoProvider {
+    http: HttpClient,
+    base_url: String,
+    user_agent: String,
+    cache: Mutex<HashMap<String, CachedForecast>>,
+}
+
+#[derive(Clone)]
+struct CachedForecast {
+    response: MetNoResponse,
+    last_modified: Option<String>,
+    expires: Option<DateTime<Utc>>,
+}
+
+impl MetNoProvider {
+    pub fn new(http: HttpClient, base_url: impl Into<String>, contact: Option<&str>) -> Self {
+        let mut user_agent = format!("{} github.com/benwyrosdick/wxman", BASE_USER_AGENT);
+        if let Some(contact) = contact {
+            user_agent.push_str(&format!(" {}", contact));
+        }
+
+        Self {
+            http,
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            user_agent,
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    async fn fetch_response(&self, location: &Location) -> Result<MetNoResponse> {
+        // MET Norway asks for at most four decimals to improve cache hits
+        let lat = format!("{:.4}", location.latitude);
+        let lon = format!("{:.4}", location.longitude);
+        let key = format!("{},{}", lat, lon);
+
+        let cached = self.cache.lock().unwrap().get(&key).cloned();
+        if let Some(entry) = &cached {
+            if entry.expires.is_some_and(|expires| expires > Utc::now()) {
+                return Ok(entry.response.clone());
+            }
+        }
+
+        let mut request = self
+            .http
+            .get(&format!("{}/compact", self.base_url))
+            .query(&[("lat", &lat), ("lon", &lon)])
+            .header(USER_AGENT, &self.user_agent);
+        if let Some(last_modified) = cached.as_ref().and_then(|c| c.last_modified.as_ref()) {
+            request = request.header(IF_MODIFIED_SINCE, last_modified);
+        }
+
+        let response = self
+            .http
+            .send(request)
+            .await
+            .context("Failed to fetch MET Norway forecast")?;
+
+        let header = |name| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(str::to_string)
+        };
+        let expires = header(EXPIRES).and_then(|v| parse_http_date(&v));
+        let last_modified = header(LAST_MODIFIED);
+
+        let status = response.status();
+        let entry = match (status, cached) {
+            (StatusCode::NOT_MODIFIED, Some(mut entry)) => {
+                entry.expires = expires;
+                entry
+            }
+            (status, _) if status.is_success() => CachedForecast {
+                response: response
+                    .json()
+                    .await
+                    .context("Failed to parse MET Norway forecast")?,
+                last_modified,
+                expires,
+            },
+            (StatusCode::TOO_MANY_REQUESTS, _) => {
+                return Err(anyhow!(
+                    "MET Norway is throttling requests, try again later"
+                ))
+            }
+            (status, _) => return Err(anyhow!("MET Norway request failed ({})", status.as_u16())),
+        };
+
+        let result = entry.response.clone();
+        self.cache.lock().unwrap().insert(key, entry);
+        Ok(result)
+    }
+}
+
+#[async_trait]
+impl WeatherProvider for MetNoProvider {
+    async fn fetch_weather(&self, location: &Location) -> Result<WeatherData> {
+        let response = self.fetch_response(location).await?;
+        Ok(MetNoForecast {
+            location: location.clone(),
+            response,
+        }
+        .into())
+    }
+}
+
+/// Parse an HTTP date such as `Sat, 01 Jun 2024 12:30:00 GMT`
+fn parse_http_date(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc2822(value)
+        .ok()
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::test_server::{oslo, MockResponse, TestServer};
+
+    const COMPACT: &str = include_str!("../../tests/fixtures/metno/compact.json");
+    const PATH: &str = "/compact";
+    const LAST_MODIFIED_AT: &str = "Sat, 01 Jun 2024 19:42:00 GMT";
+
+    fn fresh() -> MockResponse {
+        MockResponse::json(COMPACT)
+            .header("Last-Modified", LAST_MODIFIED_AT)
+            .header("Expires", "Fri, 01 Jan 2100 00:00:00 GMT")
+    }
+
+    fn expired() -> MockResponse {
+        MockResponse::json(COMPACT)
+            .header("Last-Modified", LAST_MODIFIED_AT)
+            .header("Expires", "Sat, 01 Jun 2024 20:00:00 GMT")
+    }
+
+    #[tokio::test]
+    async fn test_fetch_from_fixture() {
+        let server = TestServer::start(vec![(PATH, fresh())]).await;
+        let provider = MetNoProvider::new(HttpClient::new(), server.url(), None);
+
+        let weather = provider.fetch_weather(&oslo()).await.unwrap();
+
+        assert_eq!(weather.current.temperature, 14.0);
+        assert_eq!(weather.current.weather_code, 2);
+        assert_eq!(weather.current.humidity, Some(72));
+        assert_eq!(weather.current.cloud_cover, Some(40));
+        assert_eq!(weather.current.wind_gusts, None);
+        assert!((weather.current.wind_speed - 15.12).abs() < 0.001);
+        assert!(weather.current.is_day);
+
+        // Timeseries is UTC; Oslo is UTC+2 in summer
+        // Only the hourly steps; the six-hourly tail goes into the daily view
+        assert_eq!(weather.hourly.len(), 6);
+        assert_eq!(weather.hourly[0].time, "2024-06-01T22:00");
+        assert_eq!(weather.hourly[0].humidity, Some(72));
+        assert_eq!(weather.hourly[0].wind_direction, Some(210));
+        assert_eq!(weather.hourly[3].weather_code, 63);
+
+        assert_eq!(weather.daily.len(), 2);
+        let first = &weather.daily[0];
+        assert_eq!(first.date, "2024-06-01");
+        assert_eq!(first.temp_max, 14.0);
+        assert_eq!(first.temp_min, 13.0);
+        assert_eq!(first.weather_code, 3);
+
+        let second = &weather.daily[1];
+        assert_eq!(second.date, "2024-06-02");
+        assert_eq!(second.temp_max, 19.0);
+        assert_eq!(second.temp_min, 11.0);
+        assert!((second.precipitation_sum - 4.0).abs() < 0.001);
+        assert_eq!(second.weather_code, 2);
+        assert!(second.sunrise.starts_with("2024-06-02T04:0"));
+    }
+
+    #[tokio::test]
+    async fn test_identifying_user_agent() {
+        let server = TestServer::start(vec![(PATH, fresh())]).await;
+        let provider = MetNoProvider::new(HttpClient::new(), server.url(), Some("ops@example.com"));
+        provider.fetch_weather(&oslo()).await.unwrap();
+
+        let request = &server.requests_to(PATH)[0];
+        let agent = request.header("user-agent").unwrap();
+        assert!(agent.starts_with("wxman/"));
+        assert!(agent.ends_with("ops@example.com"));
+        assert!(request.query.contains("lat=59.9139"));
+        assert!(request.query.contains("lon=10.7522"));
+    }
+
+    #[tokio::test]
+    async fn test_serves_from_cache_until_expiry() {
+        let server = TestServer::start(vec![(PATH, fresh())]).await;
+        let provider = MetNoProvider::new(HttpClient::new(), server.url(), None);
+
+        provider.fetch_weather(&oslo()).await.unwrap();
+        provider.fetch_weather(&oslo()).await.unwrap();
+
+        assert_eq!(server.requests_to(PATH).len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_revalidates_with_if_modified_since() {
+        let server = TestServer::start(vec![
+            (PATH, expired()),
+            (PATH, MockResponse::status(304, "")),
+        ])
+        .await;
+        let provider = MetNoProvider::new(HttpClient::new(), server.url(), None);
+
+        provider.fetch_weather(&oslo()).await.unwrap();
+        let weather = provider.fetch_weather(&oslo()).await.unwrap();
+
+        let requests = server.requests_to(PATH);
+        assert_eq!(requests.len(), 2);
+        assert!(requests[0].header("if-modified-since").is_none());
+        assert_eq!(
+            requests[1].header("if-modified-since"),
+            Some(LAST_MODIFIED_AT)
+        );
+        // The 304 reuses the cached body
+        assert_eq!(weather.current.temperature, 14.0);
+    }
+
+    #[test]
+    fn test_parse_http_date() {
+        let parsed = parse_http_date("Sat, 01 Jun 2024 20:00:00 GMT").unwrap();
+        assert_eq!(parsed.to_rfc3339(), "2024-06-01T20:00:00+00:00");
+        assert!(parse_http_date("not a date").is_none());
+    }
+}
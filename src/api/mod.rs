@@ -1,7 +1,22 @@
+pub mod air_quality;
+pub mod alerts;
+pub mod ensemble;
 pub mod geocoding;
 pub mod geolocation;
+pub mod history;
+pub mod http;
+pub mod marine;
+pub mod metno;
+pub mod nws;
+pub mod openweathermap;
+pub mod provider;
+pub mod reverse_geocoding;
 pub mod weather;
 
+#[cfg(test)]
+pub(crate) mod test_server;
+
 pub use geocoding::lookup_zipcode;
 pub use geolocation::get_location_from_ip;
-pub use weather::fetch_weather;
+pub use http::HttpClient;
+pub use provider::WeatherProvider;
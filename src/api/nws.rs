@@ -0,0 +1,227 @@
+use anyhow::{anyhow, Context, Result};
+use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+
+use crate::api::http::HttpClient;
+use crate::api::provider::WeatherProvider;
+use crate::models::nws::{
+    NwsForecastBundle, NwsForecastResponse, NwsObservationResponse, NwsPointsResponse,
+    NwsStationsResponse,
+};
+use crate::models::{Location, WeatherData};
+
+pub const NWS_API_URL: &str = "https://api.weather.gov";
+
+/// Weather provider backed by the US National Weather Service API.
+/// Only covers the United States and its territories.
+pub struct NwsProvider {
+    http: HttpClient,
+    base_url: String,
+}
+
+/// RFC 7807 problem document returned by api.weather.gov on errors
+#[derive(Debug, Deserialize)]
+struct NwsProblem {
+    detail: Option<String>,
+    title: Option<String>,
+}
+
+impl NwsProvider {
+    pub fn new(http: HttpClient, base_url: impl Into<String>) -> Self {
+        Self {
+            http,
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+        }
+    }
+
+    async fn latest_observation(&self, stations_url: &str) -> Result<NwsObservationResponse> {
+        let stations: NwsStationsResponse = get_json(&self.http, stations_url).await?;
+        let station = stations
+            .features
+            .first()
+            .ok_or_else(|| anyhow!("No observation stations near this location"))?;
+
+        get_json(&self.http, &format!("{}/observations/latest", station.id)).await
+    }
+}
+
+#[async_trait]
+impl WeatherProvider for NwsProvider {
+    async fn fetch_weather(&self, location: &Location) -> Result<WeatherData> {
+        // NWS rejects coordinates with more than four decimal places
+        let points_url = format!(
+            "{}/points/{:.4},{:.4}",
+            self.base_url, location.latitude, location.longitude
+        );
+        let points: NwsPointsResponse = get_json(&self.http, &points_url)
+            .await
+            .context("Failed to resolve location with the National Weather Service")?;
+
+        let (hourly, daily) = tokio::try_join!(
+            get_json::<NwsForecastResponse>(&self.http, &points.properties.forecast_hourly),
+            get_json::<NwsForecastResponse>(&self.http, &points.properties.forecast),
+        )
+        .context("Failed to fetch NWS forecast")?;
+
+        // Observations are a nice-to-have; fall back to the first forecast hour
+        let observation = match &points.properties.observation_stations {
+            Some(url) => self.latest_observation(url).await.ok(),
+            None => None,
+        };
+
+        Ok(NwsForecastBundle {
+            latitude: location.latitude,
+            longitude: location.longitude,
+            hourly,
+            daily,
+            observation,
+        }
+        .into())
+    }
+}
+
+/// api.weather.gov requires an identifying User-Agent, which the shared
+/// client always sends
+async fn get_json<T: DeserializeOwned>(http: &HttpClient, url: &str) -> Result<T> {
+    let response = http
+        .send(http.get(url).header("Accept", "application/geo+json"))
+        .await
+        .context("Failed to reach api.weather.gov")?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let problem = response.json::<NwsProblem>().await.ok();
+        let message = problem
+            .and_then(|p| p.detail.or(p.title))
+            .unwrap_or_else(|| status.to_string());
+        return Err(anyhow!(
+            "NWS request failed ({}): {}",
+            status.as_u16(),
+            message
+        ));
+    }
+
+    response
+        .json()
+        .await
+        .context("Failed to parse NWS response")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::test_server::{new_york, MockResponse, TestServer};
+
+    const POINTS: &str = include_str!("../../tests/fixtures/nws/points.json");
+    const FORECAST: &str = include_str!("../../tests/fixtures/nws/forecast.json");
+    const FORECAST_HOURLY: &str = include_str!("../../tests/fixtures/nws/forecast_hourly.json");
+    const STATIONS: &str = include_str!("../../tests/fixtures/nws/stations.json");
+    const OBSERVATION: &str = include_str!("../../tests/fixtures/nws/observation.json");
+
+    fn routes() -> Vec<(&'static str, MockResponse)> {
+        vec![
+            ("/points/40.7128,-74.0060", MockResponse::json(POINTS)),
+            (
+                "/gridpoints/OKX/33,35/forecast",
+                MockResponse::json(FORECAST),
+            ),
+            (
+                "/gridpoints/OKX/33,35/forecast/hourly",
+                MockResponse::json(FORECAST_HOURLY),
+            ),
+            (
+                "/gridpoints/OKX/33,35/stations",
+                MockResponse::json(STATIONS),
+            ),
+            (
+                "/stations/KNYC/observations/latest",
+                MockResponse::json(OBSERVATION),
+            ),
+        ]
+    }
+
+    #[tokio::test]
+    async fn test_fetch_from_fixtures() {
+        let server = TestServer::start(routes()).await;
+        let provider = NwsProvider::new(HttpClient::new(), server.url());
+
+        let weather = provider.fetch_weather(&new_york()).await.unwrap();
+
+        // Current conditions come from the station observation
+        assert_eq!(weather.current.temperature, 22.2);
+        assert_eq!(weather.current.humidity, Some(65));
+        assert_eq!(weather.current.wind_direction, 200);
+        assert!((weather.current.pressure.unwrap() - 1013.2).abs() < 0.001);
+        assert_eq!(weather.current.uv_index, None);
+        assert_eq!(weather.current.weather_code, 2);
+
+        assert_eq!(weather.hourly.len(), 4);
+        let first = &weather.hourly[0];
+        assert_eq!(first.time, "2024-06-01T14:00");
+        assert!((first.temperature - 25.0).abs() < 0.01);
+        assert_eq!(first.precipitation_probability, Some(10));
+        assert_eq!(first.weather_code, 1);
+        assert_eq!(first.dew_point, Some(15.0));
+        assert_eq!(first.humidity, Some(55));
+        assert_eq!(first.wind_direction, Some(225));
+        assert_eq!(weather.hourly[3].weather_code, 95);
+
+        assert_eq!(weather.daily.len(), 2);
+        let today = &weather.daily[0];
+        assert_eq!(today.date, "2024-06-01");
+        assert!((today.temp_max - 26.667).abs() < 0.01);
+        assert!((today.temp_min - 17.222).abs() < 0.01);
+        assert_eq!(today.precipitation_probability, Some(40));
+        assert_eq!(today.weather_code, 2);
+        assert!(today.sunrise.starts_with("2024-06-01T05:2"));
+        assert!(today.sunset.starts_with("2024-06-01T20:2"));
+
+        let tomorrow = &weather.daily[1];
+        assert_eq!(tomorrow.weather_code, 80);
+        assert_eq!(tomorrow.precipitation_probability, Some(60));
+    }
+
+    #[tokio::test]
+    async fn test_sends_user_agent() {
+        let server = TestServer::start(routes()).await;
+        let provider = NwsProvider::new(HttpClient::new(), server.url());
+        provider.fetch_weather(&new_york()).await.unwrap();
+
+        let request = &server.requests_to("/points/40.7128,-74.0060")[0];
+        assert!(request.header("user-agent").unwrap().starts_with("wxman/"));
+    }
+
+    #[tokio::test]
+    async fn test_missing_observation_falls_back_to_forecast() {
+        let mut routes = routes();
+        routes.retain(|(path, _)| !path.starts_with("/stations"));
+        let server = TestServer::start(routes).await;
+        let provider = NwsProvider::new(HttpClient::new(), server.url());
+
+        let weather = provider.fetch_weather(&new_york()).await.unwrap();
+        assert!((weather.current.temperature - 25.0).abs() < 0.01);
+        assert!(weather.current.is_day);
+    }
+
+    #[tokio::test]
+    async fn test_location_outside_coverage() {
+        let server = TestServer::start(vec![(
+            "/points/51.5074,-0.1278",
+            MockResponse::status(
+                404,
+                r#"{"title": "Data Unavailable For Requested Point", "detail": "Unable to provide data for requested point 51.5074,-0.1278"}"#,
+            ),
+        )])
+        .await;
+        let provider = NwsProvider::new(HttpClient::new(), server.url());
+        let london = Location {
+            latitude: 51.5074,
+            longitude: -0.1278,
+            ..new_york()
+        };
+
+        let err = provider.fetch_weather(&london).await.unwrap_err();
+        assert!(format!("{:#}", err).contains("Unable to provide data"));
+    }
+}
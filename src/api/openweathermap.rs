@@ -0,0 +1,169 @@
+use anyhow::{anyhow, Context, Result};
+use async_trait::async_trait;
+use reqwest::StatusCode;
+
+use crate::api::http::HttpClient;
+use crate::api::provider::WeatherProvider;
+use crate::models::openweathermap::{OwmError, OwmOneCallResponse};
+use crate::models::{Location, WeatherData};
+
+pub const OWM_API_URL: &str = "https://api.openweathermap.org/data/3.0";
+
+/// Environment variable consulted when no key is set in config
+pub const OWM_API_KEY_ENV: &str = "OPENWEATHERMAP_API_KEY";
+
+/// Weather provider backed by the OpenWeatherMap One Call 3.0 API.
+/// Requires an API key with a One Call subscription.
+pub struct OpenWeatherMapProvider {
+    http: HttpClient,
+    base_url: String,
+    api_key: Option<String>,
+}
+
+impl OpenWeatherMapProvider {
+    pub fn new(http: HttpClient, base_url: impl Into<String>, api_key: Option<String>) -> Self {
+        Self {
+            http,
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            api_key: api_key
+                .map(|k| k.trim().to_string())
+                .filter(|k| !k.is_empty()),
+        }
+    }
+}
+
+#[async_trait]
+impl WeatherProvider for OpenWeatherMapProvider {
+    async fn fetch_weather(&self, location: &Location) -> Result<WeatherData> {
+        let api_key = self.api_key.as_deref().ok_or_else(|| {
+            anyhow!(
+                "OpenWeatherMap needs an API key. Set api_key under [provider] in config.toml or the {} environment variable",
+                OWM_API_KEY_ENV
+            )
+        })?;
+
+        // Standard units (Kelvin, m/s) are converted to the crate's metric base units
+        let request = self
+            .http
+            .get(&format!("{}/onecall", self.base_url))
+            .query(&[
+                ("lat", location.latitude.to_string()),
+                ("lon", location.longitude.to_string()),
+                ("appid", api_key.to_string()),
+                ("units", "standard".to_string()),
+                ("exclude", "minutely,alerts".to_string()),
+            ]);
+        let response = self
+            .http
+            .send(request)
+            .await
+            .context("Failed to fetch OpenWeatherMap forecast")?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let message = response
+                .json::<OwmError>()
+                .await
+                .map(|e| e.message)
+                .unwrap_or_else(|_| status.to_string());
+            return Err(match status {
+                StatusCode::UNAUTHORIZED => {
+                    anyhow!("OpenWeatherMap rejected the API key: {}", message)
+                }
+                _ => anyhow!(
+                    "OpenWeatherMap request failed ({}): {}",
+                    status.as_u16(),
+                    message
+                ),
+            });
+        }
+
+        let response: OwmOneCallResponse = response
+            .json()
+            .await
+            .context("Failed to parse OpenWeatherMap response")?;
+
+        Ok(response.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::test_server::{chicago, MockResponse, TestServer};
+
+    const ONECALL: &str = include_str!("../../tests/fixtures/openweathermap/onecall.json");
+
+    #[tokio::test]
+    async fn test_fetch_from_fixture() {
+        let server = TestServer::start(vec![("/onecall", MockResponse::json(ONECALL))]).await;
+        let provider = OpenWeatherMapProvider::new(
+            HttpClient::new(),
+            server.url(),
+            Some("test-key".to_string()),
+        );
+
+        let weather = provider.fetch_weather(&chicago()).await.unwrap();
+
+        assert!((weather.current.temperature - 20.0).abs() < 0.001);
+        assert!((weather.current.apparent_temperature - 19.5).abs() < 0.001);
+        assert!((weather.current.wind_speed - 18.0).abs() < 0.001);
+        assert!((weather.current.wind_gusts.unwrap() - 28.8).abs() < 0.001);
+        assert_eq!(weather.current.pressure, Some(1014.0));
+        assert_eq!(weather.current.weather_code, 2);
+        assert_eq!(weather.current.precipitation, 0.2);
+        assert!(weather.current.is_day);
+
+        assert_eq!(weather.hourly.len(), 2);
+        assert_eq!(weather.hourly[0].time, "2024-06-01T07:00");
+        assert_eq!(weather.hourly[0].precipitation_probability, Some(15));
+        assert!((weather.hourly[0].dew_point.unwrap() - 12.95).abs() < 0.001);
+        assert_eq!(weather.hourly[0].visibility, Some(10000.0));
+        assert!((weather.hourly[0].wind_gusts.unwrap() - 28.8).abs() < 0.001);
+        assert_eq!(weather.hourly[1].weather_code, 63);
+        assert_eq!(weather.hourly[1].precipitation, 1.5);
+
+        assert_eq!(weather.daily.len(), 1);
+        let day = &weather.daily[0];
+        assert_eq!(day.date, "2024-06-01");
+        assert!((day.temp_max - 25.0).abs() < 0.001);
+        assert!((day.temp_min - 15.0).abs() < 0.001);
+        assert_eq!(day.sunrise, "2024-06-01T05:16");
+        assert_eq!(day.precipitation_sum, 3.5);
+        assert_eq!(day.precipitation_probability, Some(60));
+
+        let request = &server.requests_to("/onecall")[0];
+        assert!(request.query.contains("appid=test-key"));
+        assert!(request.query.contains("units=standard"));
+    }
+
+    #[tokio::test]
+    async fn test_missing_key_is_reported_without_request() {
+        let server = TestServer::start(vec![("/onecall", MockResponse::json(ONECALL))]).await;
+        let provider =
+            OpenWeatherMapProvider::new(HttpClient::new(), server.url(), Some("  ".to_string()));
+
+        let err = provider.fetch_weather(&chicago()).await.unwrap_err();
+        assert!(err.to_string().contains("needs an API key"));
+        assert!(server.requests().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_invalid_key() {
+        let server = TestServer::start(vec![(
+            "/onecall",
+            MockResponse::status(
+                401,
+                r#"{"cod":401, "message": "Invalid API key. Please see https://openweathermap.org/faq#error401 for more info."}"#,
+            ),
+        )])
+        .await;
+        let provider =
+            OpenWeatherMapProvider::new(HttpClient::new(), server.url(), Some("bad".to_string()));
+
+        let err = provider.fetch_weather(&chicago()).await.unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("OpenWeatherMap rejected the API key: Invalid API key"));
+    }
+}
@@ -0,0 +1,59 @@
+use std::env;
+use std::sync::Arc;
+
+use anyhow::Result;
+use async_trait::async_trait;
+
+use crate::api::http::HttpClient;
+use crate::api::metno::MetNoProvider;
+use crate::api::nws::NwsProvider;
+use crate::api::openweathermap::{OpenWeatherMapProvider, OWM_API_KEY_ENV};
+use crate::api::weather::OpenMeteoProvider;
+use crate::config::{EndpointsConfig, ProviderConfig, ProviderKind};
+use crate::models::{Location, WeatherData};
+
+/// A backend that can produce forecasts for a location.
+///
+/// Implementations must return data in the crate's metric base units
+/// (Celsius, km/h, mm, hPa) with WMO weather codes, so conversion and
+/// rendering work the same regardless of where the data came from.
+#[async_trait]
+pub trait WeatherProvider: Send + Sync {
+    /// Fetch current conditions plus hourly and daily forecasts
+    async fn fetch_weather(&self, location: &Location) -> Result<WeatherData>;
+}
+
+/// Build the provider selected in the `[provider]` config section,
+/// pointed at the resolved endpoint for that service
+pub fn from_config(
+    http: &HttpClient,
+    config: &ProviderConfig,
+    endpoints: &EndpointsConfig,
+) -> Arc<dyn WeatherProvider> {
+    match config.name {
+        ProviderKind::OpenMeteo => Arc::new(OpenMeteoProvider::new(
+            http.clone(),
+            endpoints.weather(),
+            config.model.clone(),
+            config.forecast_days(),
+        )),
+        ProviderKind::Nws => Arc::new(NwsProvider::new(http.clone(), endpoints.nws())),
+        ProviderKind::MetNorway => Arc::new(MetNoProvider::new(
+            http.clone(),
+            endpoints.met_norway(),
+            config.contact.as_deref(),
+        )),
+        ProviderKind::OpenWeatherMap => {
+            // The configured key wins over the environment
+            let api_key = config
+                .api_key
+                .clone()
+                .or_else(|| env::var(OWM_API_KEY_ENV).ok());
+            Arc::new(OpenWeatherMapProvider::new(
+                http.clone(),
+                endpoints.openweathermap(),
+                api_key,
+            ))
+        }
+    }
+}
@@ -0,0 +1,226 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use anyhow::{anyhow, Context, Result};
+use async_trait::async_trait;
+
+use crate::api::http::HttpClient;
+use crate::config::{EndpointsConfig, ReverseGeocoderKind};
+use crate::models::cities::nearest_city;
+use crate::models::location::NominatimResponse;
+use crate::models::Location;
+
+pub const NOMINATIM_API_URL: &str = "https://nominatim.openstreetmap.org";
+
+/// Beyond this distance the offline lookup names the place "Near <city>"
+const NEARBY_KM: f64 = 50.0;
+
+/// Names the place at a pair of coordinates, for locations configured by
+/// latitude/longitude alone
+#[async_trait]
+pub trait ReverseGeocoder: Send + Sync {
+    /// Resolve city, region, country and IANA timezone for the coordinates.
+    /// The timezone is the nearest major city's, so it can be wrong near a
+    /// zone boundary; prefer `weather::lookup_timezone` where it's reachable.
+    async fn reverse(&self, latitude: f64, longitude: f64) -> Result<Location>;
+}
+
+/// Build the reverse geocoder selected by `reverse_geocoder` in the
+/// `[location]` config section
+pub fn from_config(
+    http: &HttpClient,
+    kind: ReverseGeocoderKind,
+    endpoints: &EndpointsConfig,
+) -> Arc<dyn ReverseGeocoder> {
+    match kind {
+        ReverseGeocoderKind::Nominatim => Arc::new(WithFallback {
+            primary: Box::new(NominatimGeocoder::new(http.clone(), endpoints.nominatim())),
+            fallback: Box::new(OfflineGeocoder),
+        }),
+        ReverseGeocoderKind::Offline => Arc::new(OfflineGeocoder),
+    }
+}
+
+/// Reverse geocoding through OpenStreetMap Nominatim.
+///
+/// Nominatim doesn't report timezones, so that comes from the nearest city
+/// in the built-in table as it does offline. Its usage policy asks clients
+/// to cache results, so each place is only requested once per run.
+pub struct NominatimGeocoder {
+    http: HttpClient,
+    base_url: String,
+    cache: Mutex<HashMap<String, Location>>,
+}
+
+impl NominatimGeocoder {
+    pub fn new(http: HttpClient, base_url: impl Into<String>) -> Self {
+        Self {
+            http,
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+#[async_trait]
+impl ReverseGeocoder for NominatimGeocoder {
+    async fn reverse(&self, latitude: f64, longitude: f64) -> Result<Location> {
+        let lat = format!("{:.4}", latitude);
+        let lon = format!("{:.4}", longitude);
+        let key = format!("{},{}", lat, lon);
+        if let Some(location) = self.cache.lock().unwrap().get(&key) {
+            return Ok(location.clone());
+        }
+
+        // Zoom 10 asks for city-level detail rather than the nearest building
+        let request = self
+            .http
+            .get(&format!("{}/reverse", self.base_url))
+            .query(&[
+                ("lat", lat.as_str()),
+                ("lon", lon.as_str()),
+                ("format", "jsonv2"),
+                ("zoom", "10"),
+                ("accept-language", "en"),
+            ]);
+        let response: NominatimResponse = self
+            .http
+            .send(request)
+            .await
+            .context("Failed to reverse geocode location")?
+            .error_for_status()
+            .context("Failed to reverse geocode location")?
+            .json()
+            .await
+            .context("Failed to parse reverse geocoding response")?;
+
+        let address = response.address.ok_or_else(|| {
+            anyhow!(
+                "No place found at {}, {}: {}",
+                lat,
+                lon,
+                response.error.as_deref().unwrap_or("empty response")
+            )
+        })?;
+
+        let (nearest, _) = nearest_city(latitude, longitude);
+        let location = Location {
+            latitude,
+            longitude,
+            city: address.place_name().unwrap_or(nearest.name).to_string(),
+            region: address.state.clone(),
+            country: address
+                .country
+                .clone()
+                .unwrap_or_else(|| nearest.country.to_string()),
+            timezone: nearest.timezone.to_string(),
+        };
+
+        self.cache.lock().unwrap().insert(key, location.clone());
+        Ok(location)
+    }
+}
+
+/// Reverse geocoding against the built-in table of major cities. Works
+/// offline, but only knows the nearest large city and its timezone.
+pub struct OfflineGeocoder;
+
+#[async_trait]
+impl ReverseGeocoder for OfflineGeocoder {
+    async fn reverse(&self, latitude: f64, longitude: f64) -> Result<Location> {
+        let (city, distance) = nearest_city(latitude, longitude);
+        let name = if distance <= NEARBY_KM {
+            city.name.to_string()
+        } else {
+            format!("Near {}", city.name)
+        };
+
+        Ok(Location {
+            latitude,
+            longitude,
+            city: name,
+            region: city.region.map(str::to_string),
+            country: city.country.to_string(),
+            timezone: city.timezone.to_string(),
+        })
+    }
+}
+
+/// Tries `primary` and answers from `fallback` when it fails
+pub struct WithFallback {
+    primary: Box<dyn ReverseGeocoder>,
+    fallback: Box<dyn ReverseGeocoder>,
+}
+
+#[async_trait]
+impl ReverseGeocoder for WithFallback {
+    async fn reverse(&self, latitude: f64, longitude: f64) -> Result<Location> {
+        match self.primary.reverse(latitude, longitude).await {
+            Ok(location) => Ok(location),
+            Err(_) => self.fallback.reverse(latitude, longitude).await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::test_server::{MockResponse, TestServer};
+
+    const BOULDER: &str = include_str!("../../tests/fixtures/nominatim/reverse.json");
+    const NOT_FOUND: &str = r#"{"error":"Unable to geocode"}"#;
+
+    #[tokio::test]
+    async fn test_nominatim_reverse() {
+        let server = TestServer::start(vec![("/reverse", MockResponse::json(BOULDER))]).await;
+        let geocoder = NominatimGeocoder::new(HttpClient::new(), server.url());
+
+        let location = geocoder.reverse(40.01499, -105.27055).await.unwrap();
+        assert_eq!(location.city, "Boulder");
+        assert_eq!(location.region.as_deref(), Some("Colorado"));
+        assert_eq!(location.country, "United States");
+        assert_eq!(location.timezone, "America/Denver");
+        assert_eq!(location.latitude, 40.01499);
+
+        let request = &server.requests_to("/reverse")[0];
+        assert!(request.query.contains("lat=40.0150"));
+        assert!(request.query.contains("lon=-105.2706"));
+        assert!(request.query.contains("format=jsonv2"));
+
+        // Repeat lookups are answered from memory
+        geocoder.reverse(40.01499, -105.27055).await.unwrap();
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_nominatim_not_found() {
+        let server = TestServer::start(vec![("/reverse", MockResponse::json(NOT_FOUND))]).await;
+        let geocoder = NominatimGeocoder::new(HttpClient::new(), server.url());
+
+        let err = geocoder.reverse(0.0, -140.0).await.unwrap_err();
+        assert!(err.to_string().contains("Unable to geocode"));
+    }
+
+    #[tokio::test]
+    async fn test_falls_back_to_offline() {
+        let server = TestServer::start(vec![("/reverse", MockResponse::status(503, ""))]).await;
+        let endpoints = EndpointsConfig {
+            nominatim: Some(server.url()),
+            ..Default::default()
+        };
+        let http = HttpClient::new().with_base_delay(std::time::Duration::from_millis(1));
+        let geocoder = from_config(&http, ReverseGeocoderKind::Nominatim, &endpoints);
+
+        let location = geocoder.reverse(47.61, -122.33).await.unwrap();
+        assert_eq!(location.city, "Seattle");
+        assert_eq!(location.timezone, "America/Los_Angeles");
+    }
+
+    #[tokio::test]
+    async fn test_offline_reverse() {
+        let location = OfflineGeocoder.reverse(51.45, -2.59).await.unwrap();
+        assert_eq!(location.city, "Near London");
+        assert_eq!(location.country, "United Kingdom");
+        assert_eq!(location.timezone, "Europe/London");
+    }
+}
@@ -0,0 +1,237 @@
+//! Minimal HTTP stand-in for exercising API clients against recorded fixtures.
+//!
+//! Routes are matched on the request path (query string ignored). Registering
+//! the same path more than once queues responses in order; the last one keeps
+//! being served after the queue is drained. Any `{base}` placeholder in a body
+//! is replaced with the server's own URL so fixtures can link to each other.
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+use crate::models::Location;
+
+/// Places the API client tests fetch data for
+fn location(city: &str, country: &str, latitude: f64, longitude: f64, timezone: &str) -> Location {
+    Location {
+        latitude,
+        longitude,
+        city: city.to_string(),
+        region: None,
+        country: country.to_string(),
+        timezone: timezone.to_string(),
+    }
+}
+
+pub fn denver() -> Location {
+    location("Denver", "United States", 39.74, -104.99, "America/Denver")
+}
+
+pub fn new_york() -> Location {
+    location(
+        "New York",
+        "United States",
+        40.7128,
+        -74.006,
+        "America/New_York",
+    )
+}
+
+pub fn chicago() -> Location {
+    location(
+        "Chicago",
+        "United States",
+        41.8781,
+        -87.6298,
+        "America/Chicago",
+    )
+}
+
+/// On the coast, for marine and ensemble data
+pub fn newport() -> Location {
+    location(
+        "Newport",
+        "United States",
+        41.49,
+        -71.31,
+        "America/New_York",
+    )
+}
+
+pub fn oslo() -> Location {
+    location("Oslo", "Norway", 59.91387, 10.7522, "Europe/Oslo")
+}
+
+pub fn amsterdam() -> Location {
+    location("Amsterdam", "Netherlands", 52.37, 4.9, "Europe/Amsterdam")
+}
+
+#[derive(Debug, Clone)]
+pub struct MockResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl MockResponse {
+    pub fn json(body: &str) -> Self {
+        Self {
+            status: 200,
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: body.to_string(),
+        }
+    }
+
+    pub fn xml(body: &str) -> Self {
+        Self {
+            status: 200,
+            headers: vec![("Content-Type".to_string(), "application/xml".to_string())],
+            body: body.to_string(),
+        }
+    }
+
+    pub fn status(status: u16, body: &str) -> Self {
+        Self {
+            status,
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: body.to_string(),
+        }
+    }
+
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RecordedRequest {
+    pub path: String,
+    pub query: String,
+    pub headers: HashMap<String, String>,
+}
+
+impl RecordedRequest {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .get(&name.to_ascii_lowercase())
+            .map(String::as_str)
+    }
+}
+
+type Routes = HashMap<String, Vec<MockResponse>>;
+
+pub struct TestServer {
+    addr: SocketAddr,
+    requests: Arc<Mutex<Vec<RecordedRequest>>>,
+}
+
+impl TestServer {
+    pub async fn start(routes: Vec<(&str, MockResponse)>) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let base = format!("http://{}", addr);
+
+        let mut table: Routes = HashMap::new();
+        for (path, mut response) in routes {
+            response.body = response.body.replace("{base}", &base);
+            table.entry(path.to_string()).or_default().push(response);
+        }
+
+        let routes = Arc::new(Mutex::new(table));
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let recorded = requests.clone();
+
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let routes = routes.clone();
+                let recorded = recorded.clone();
+                tokio::spawn(async move {
+                    let _ = handle(stream, routes, recorded).await;
+                });
+            }
+        });
+
+        Self { addr, requests }
+    }
+
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    pub fn requests_to(&self, path: &str) -> Vec<RecordedRequest> {
+        self.requests()
+            .into_iter()
+            .filter(|r| r.path == path)
+            .collect()
+    }
+}
+
+async fn handle(
+    mut stream: TcpStream,
+    routes: Arc<Mutex<Routes>>,
+    recorded: Arc<Mutex<Vec<RecordedRequest>>>,
+) -> std::io::Result<()> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 4096];
+    while !buf.windows(4).any(|w| w == b"\r\n\r\n") {
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            return Ok(());
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    }
+
+    let text = String::from_utf8_lossy(&buf);
+    let mut lines = text.split("\r\n");
+    let target = lines
+        .next()
+        .and_then(|l| l.split_whitespace().nth(1))
+        .unwrap_or("/")
+        .to_string();
+    let (path, query) = match target.split_once('?') {
+        Some((p, q)) => (p.to_string(), q.to_string()),
+        None => (target, String::new()),
+    };
+    let headers = lines
+        .take_while(|l| !l.is_empty())
+        .filter_map(|l| l.split_once(':'))
+        .map(|(k, v)| (k.trim().to_ascii_lowercase(), v.trim().to_string()))
+        .collect();
+
+    recorded.lock().unwrap().push(RecordedRequest {
+        path: path.clone(),
+        query,
+        headers,
+    });
+
+    let response = {
+        let mut routes = routes.lock().unwrap();
+        match routes.get_mut(&path) {
+            Some(queue) if queue.len() > 1 => queue.remove(0),
+            Some(queue) => queue[0].clone(),
+            None => MockResponse::status(404, r#"{"error":"not found"}"#),
+        }
+    };
+
+    let mut out = format!(
+        "HTTP/1.1 {} Mock\r\nContent-Length: {}\r\nConnection: close\r\n",
+        response.status,
+        response.body.len()
+    );
+    for (name, value) in &response.headers {
+        out.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    out.push_str("\r\n");
+    out.push_str(&response.body);
+
+    stream.write_all(out.as_bytes()).await?;
+    stream.shutdown().await
+}
@@ -1,75 +1,359 @@
-use crate::models::weather::{OpenMeteoResponse, WeatherData};
-use anyhow::{Context, Result};
-
-const WEATHER_API_URL: &str = "https://api.open-meteo.com/v1/forecast";
-
-/// Fetches weather data from Open-Meteo API.
-/// Always requests metric units (Celsius, km/h, mm) so conversions can be done
-/// client-side for live unit switching without re-fetching.
-pub async fn fetch_weather(latitude: f64, longitude: f64) -> Result<WeatherData> {
-    let client = reqwest::Client::new();
-
-    let current_params = [
-        "temperature_2m",
-        "relative_humidity_2m",
-        "apparent_temperature",
-        "precipitation",
-        "weather_code",
-        "wind_speed_10m",
-        "wind_direction_10m",
-        "wind_gusts_10m",
-        "cloud_cover",
-        "pressure_msl",
-        "uv_index",
-        "is_day",
-    ]
-    .join(",");
-
-    let hourly_params = [
-        "temperature_2m",
-        "apparent_temperature",
-        "precipitation_probability",
-        "precipitation",
-        "weather_code",
-        "wind_speed_10m",
-    ]
-    .join(",");
-
-    let daily_params = [
-        "weather_code",
-        "temperature_2m_max",
-        "temperature_2m_min",
-        "apparent_temperature_max",
-        "apparent_temperature_min",
-        "sunrise",
-        "sunset",
-        "precipitation_sum",
-        "precipitation_probability_max",
-        "wind_speed_10m_max",
-        "uv_index_max",
-    ]
-    .join(",");
-
-    // Always request metric units: Celsius, km/h, mm
-    // Conversion to user's preferred units is done at display time
-    let url = format!(
-        "{}?latitude={}&longitude={}&current={}&hourly={}&daily={}&temperature_unit=celsius&wind_speed_unit=kmh&precipitation_unit=mm&timezone=auto&forecast_days=5",
-        WEATHER_API_URL,
-        latitude,
-        longitude,
-        current_params,
-        hourly_params,
-        daily_params,
-    );
-
-    let response: OpenMeteoResponse = client
-        .get(&url)
-        .send()
+use crate::api::http::HttpClient;
+use crate::api::provider::WeatherProvider;
+use crate::models::comparison::{ModelComparison, OpenMeteoComparisonResponse};
+use crate::models::weather::{OpenMeteoResponse, OpenMeteoTimezone, WeatherData};
+use crate::models::Location;
+use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
+
+pub const WEATHER_API_URL: &str = "https://api.open-meteo.com/v1/forecast";
+
+/// Days of past hours to request, enough for the 72-hour precipitation total
+const PAST_DAYS: usize = 3;
+
+/// Weather provider backed by the Open-Meteo forecast API
+pub struct OpenMeteoProvider {
+    http: HttpClient,
+    url: String,
+    /// A specific weather model, e.g. `ecmwf_ifs025`. Open-Meteo blends the
+    /// best models for the location when unset.
+    model: Option<String>,
+    forecast_days: usize,
+}
+
+impl OpenMeteoProvider {
+    pub fn new(
+        http: HttpClient,
+        url: impl Into<String>,
+        model: Option<String>,
+        forecast_days: usize,
+    ) -> Self {
+        Self {
+            http,
+            url: url.into(),
+            model,
+            forecast_days,
+        }
+    }
+}
+
+#[async_trait]
+impl WeatherProvider for OpenMeteoProvider {
+    /// Fetches weather data from Open-Meteo API.
+    /// Always requests metric units (Celsius, km/h, mm) so conversions can be done
+    /// client-side for live unit switching without re-fetching.
+    async fn fetch_weather(&self, location: &Location) -> Result<WeatherData> {
+        let current_params = [
+            "temperature_2m",
+            "relative_humidity_2m",
+            "apparent_temperature",
+            "precipitation",
+            "weather_code",
+            "wind_speed_10m",
+            "wind_direction_10m",
+            "wind_gusts_10m",
+            "cloud_cover",
+            "pressure_msl",
+            "uv_index",
+            "is_day",
+            "dew_point_2m",
+            "visibility",
+        ]
+        .join(",");
+
+        let hourly_params = [
+            "temperature_2m",
+            "apparent_temperature",
+            "precipitation_probability",
+            "precipitation",
+            "weather_code",
+            "wind_speed_10m",
+            "relative_humidity_2m",
+            "dew_point_2m",
+            "wind_direction_10m",
+            "wind_gusts_10m",
+            "cloud_cover",
+            "visibility",
+            "uv_index",
+            "snowfall",
+        ]
+        .join(",");
+
+        let daily_params = [
+            "weather_code",
+            "temperature_2m_max",
+            "temperature_2m_min",
+            "apparent_temperature_max",
+            "apparent_temperature_min",
+            "sunrise",
+            "sunset",
+            "precipitation_sum",
+            "precipitation_probability_max",
+            "wind_speed_10m_max",
+            "uv_index_max",
+        ]
+        .join(",");
+
+        // 15-minute precipitation for the nowcast; a few hours is plenty and
+        // still covers the window if a refresh is missed
+        let minutely_params = ["precipitation", "snowfall"].join(",");
+
+        // Always request metric units: Celsius, km/h, mm
+        // Conversion to user's preferred units is done at display time
+        let mut params = vec![
+            ("latitude", location.latitude.to_string()),
+            ("longitude", location.longitude.to_string()),
+            ("current", current_params),
+            ("hourly", hourly_params),
+            ("daily", daily_params),
+            ("minutely_15", minutely_params),
+            ("forecast_minutely_15", "16".to_string()),
+            ("temperature_unit", "celsius".to_string()),
+            ("wind_speed_unit", "kmh".to_string()),
+            ("precipitation_unit", "mm".to_string()),
+            ("timezone", "auto".to_string()),
+            ("forecast_days", self.forecast_days.to_string()),
+            ("past_days", PAST_DAYS.to_string()),
+        ];
+        if let Some(model) = &self.model {
+            params.push(("models", model.clone()));
+        }
+
+        let response: OpenMeteoResponse = self
+            .http
+            .send(self.http.get(&self.url).query(&params))
+            .await
+            .context("Failed to fetch weather data")?
+            .error_for_status()
+            .context("Failed to fetch weather data")?
+            .json()
+            .await
+            .context("Failed to parse weather response")?;
+
+        // A model that doesn't cover the location comes back all null
+        let current = &response.current;
+        if current.temperature_2m.is_none()
+            && response.hourly.temperature_2m.iter().all(Option::is_none)
+        {
+            bail!(
+                "{} has no forecast for this location",
+                self.model.as_deref().unwrap_or("Open-Meteo")
+            );
+        }
+
+        // Past days come back in the daily series too, which should start
+        // today. Days a model has no data for are dropped when converting,
+        // so they're told apart by date rather than position.
+        let today = response.daily.time.get(PAST_DAYS).cloned();
+        let mut weather: WeatherData = response.into();
+        weather
+            .daily
+            .retain(|day| today.as_ref().is_some_and(|today| &day.date >= today));
+        Ok(weather)
+    }
+}
+
+/// Look up the IANA timezone at a pair of coordinates. Open-Meteo resolves
+/// it from timezone boundaries, and already gets the coordinates for the
+/// forecast.
+pub async fn lookup_timezone(
+    http: &HttpClient,
+    url: &str,
+    latitude: f64,
+    longitude: f64,
+) -> Result<String> {
+    let params = [
+        ("latitude", latitude.to_string()),
+        ("longitude", longitude.to_string()),
+        ("timezone", "auto".to_string()),
+        ("forecast_days", "1".to_string()),
+    ];
+
+    let response: OpenMeteoTimezone = http
+        .send(http.get(url).query(&params))
         .await
-        .context("Failed to fetch weather data")?
+        .context("Failed to look up timezone")?
+        .error_for_status()
+        .context("Failed to look up timezone")?
         .json()
         .await
-        .context("Failed to parse weather response")?;
+        .context("Failed to parse timezone response")?;
+
+    Ok(response.timezone)
+}
+
+/// Fetch hourly temperature and precipitation from several models in one
+/// request, for comparing them side by side over `forecast_days` days
+pub async fn fetch_model_comparison(
+    http: &HttpClient,
+    url: &str,
+    location: &Location,
+    models: &[String],
+    forecast_days: usize,
+) -> Result<ModelComparison> {
+    let params = [
+        ("latitude", location.latitude.to_string()),
+        ("longitude", location.longitude.to_string()),
+        ("hourly", "temperature_2m,precipitation".to_string()),
+        ("models", models.join(",")),
+        ("timezone", "auto".to_string()),
+        ("forecast_days", forecast_days.to_string()),
+    ];
+
+    let response: OpenMeteoComparisonResponse = http
+        .send(http.get(url).query(&params))
+        .await
+        .context("Failed to fetch model comparison")?
+        .error_for_status()
+        .context("Failed to fetch model comparison")?
+        .json()
+        .await
+        .context("Failed to parse model comparison response")?;
+
+    Ok(ModelComparison::from_response(response, models))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::test_server::{denver, MockResponse, TestServer};
+
+    const MODELS: &str = include_str!("../../tests/fixtures/comparison/models.json");
+    const SINGLE_MODEL: &str = include_str!("../../tests/fixtures/weather/single_model.json");
+    const NULL_CURRENT: &str = include_str!("../../tests/fixtures/weather/null_current.json");
+    const TIMEZONE: &str = r#"{"latitude":38.9,"longitude":-104.5,"utc_offset_seconds":-21600,"timezone":"America/Denver","timezone_abbreviation":"MDT","elevation":1900.0}"#;
+
+    #[tokio::test]
+    async fn test_fetch_single_model_with_nulls() {
+        let server = TestServer::start(vec![("/forecast", MockResponse::json(SINGLE_MODEL))]).await;
+        let provider = OpenMeteoProvider::new(
+            HttpClient::new(),
+            format!("{}/forecast", server.url()),
+            Some("gfs_seamless".to_string()),
+            5,
+        );
+
+        let weather = provider.fetch_weather(&denver()).await.unwrap();
+
+        // Hours before the model's run starts have no data at all
+        assert_eq!(weather.hourly.len(), 4);
+        let first = &weather.hourly[0];
+        assert_eq!(first.time, "2024-06-01T12:00");
+        assert_eq!(first.precipitation_probability, None);
+        assert_eq!(first.uv_index, None);
+        // Gaps in a covered hour are filled in rather than rejected
+        assert_eq!(weather.hourly[2].apparent_temperature, 25.6);
+        assert_eq!(weather.hourly[3].weather_code, -1);
+        assert_eq!(weather.hourly[3].precipitation, 1.2);
+
+        // The daily series still starts today, even with a past day dropped
+        assert_eq!(weather.daily.len(), 2);
+        let tomorrow = &weather.daily[1];
+        assert_eq!(tomorrow.date, "2024-06-02");
+        assert_eq!(tomorrow.temp_max, 27.3);
+        assert_eq!(tomorrow.apparent_temp_max, 27.3);
+        assert_eq!(tomorrow.precipitation_sum, 0.0);
+        assert_eq!(tomorrow.uv_index_max, 0.0);
+        assert_eq!(tomorrow.weather_code, -1);
+        assert_eq!(tomorrow.precipitation_probability, None);
+
+        let query = &server.requests()[0].query;
+        assert!(query.contains("models=gfs_seamless"));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_single_model_with_null_current() {
+        let server = TestServer::start(vec![("/forecast", MockResponse::json(NULL_CURRENT))]).await;
+        let provider = OpenMeteoProvider::new(
+            HttpClient::new(),
+            format!("{}/forecast", server.url()),
+            Some("gfs_seamless".to_string()),
+            5,
+        );
+
+        let weather = provider.fetch_weather(&denver()).await.unwrap();
+
+        // Gaps are filled from the forecast for the current hour
+        let current = &weather.current;
+        assert_eq!(current.temperature, 24.1);
+        assert_eq!(current.apparent_temperature, 22.8);
+        assert_eq!(current.weather_code, 2);
+        assert_eq!(current.humidity, Some(31));
+        assert_eq!(current.wind_gusts, Some(24.8));
+        // and left unknown where the hourly series has nothing either
+        assert_eq!(current.pressure, None);
+        assert_eq!(current.uv_index, None);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_invalid_model() {
+        let server = TestServer::start(vec![(
+            "/forecast",
+            MockResponse::status(
+                400,
+                r#"{"error":true,"reason":"Cannot initialize WeatherModel from invalid String value not_a_model for key models"}"#,
+            ),
+        )])
+        .await;
+        let provider = OpenMeteoProvider::new(
+            HttpClient::new(),
+            format!("{}/forecast", server.url()),
+            Some("not_a_model".to_string()),
+            5,
+        );
+
+        let err = provider.fetch_weather(&denver()).await.unwrap_err();
+        assert_eq!(err.to_string(), "Failed to fetch weather data");
+        assert!(format!("{:#}", err).contains("400"));
+    }
+
+    #[tokio::test]
+    async fn test_lookup_timezone() {
+        let server = TestServer::start(vec![("/forecast", MockResponse::json(TIMEZONE))]).await;
+        let url = format!("{}/forecast", server.url());
+
+        let timezone = lookup_timezone(&HttpClient::new(), &url, 38.9, -104.5)
+            .await
+            .unwrap();
+        assert_eq!(timezone, "America/Denver");
+
+        let query = &server.requests()[0].query;
+        assert!(query.contains("timezone=auto"));
+        assert!(query.contains("latitude=38.9"));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_model_comparison() {
+        let server = TestServer::start(vec![("/forecast", MockResponse::json(MODELS))]).await;
+        let url = format!("{}/forecast", server.url());
+        let models = [
+            "ecmwf_ifs025",
+            "gfs_seamless",
+            "icon_seamless",
+            "gem_seamless",
+        ]
+        .map(String::from);
+
+        let comparison = fetch_model_comparison(&HttpClient::new(), &url, &denver(), &models, 16)
+            .await
+            .unwrap();
+
+        assert_eq!(comparison.models.len(), 4);
+        assert_eq!(comparison.hourly.len(), 4);
+        let first = &comparison.hourly[0];
+        assert_eq!(
+            first.temperature,
+            vec![Some(27.1), Some(27.8), Some(26.9), None]
+        );
+        assert!(!first.precipitation_disagrees());
+
+        // GFS runs warm and dry while the others bring afternoon showers
+        let last = &comparison.hourly[3];
+        assert!((last.temperature_spread().unwrap() - 3.7).abs() < 1e-9);
+        assert!(last.precipitation_disagrees());
 
-    Ok(response.into())
+        let query = &server.requests()[0].query;
+        assert!(query.contains("models=ecmwf_ifs025%2Cgfs_seamless%2Cicon_seamless%2Cgem_seamless"));
+        assert!(query.contains("forecast_days=16"));
+    }
 }
@@ -1,10 +1,33 @@
-use anyhow::Result;
-use chrono::{DateTime, Local};
+use std::cell::Cell;
+use std::future::Future;
+use std::sync::Arc;
 
-use crate::api;
-use crate::config::{Config, PrecipitationUnit, PressureUnit, TemperatureUnit, WindSpeedUnit};
+use anyhow::{bail, Result};
+use chrono::{DateTime, Local, NaiveDateTime, TimeDelta, Utc};
+use ratatui::layout::Rect;
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use tokio::task::JoinHandle;
+
+use crate::api::geocoding::{self, SEARCH_RESULTS};
+use crate::api::reverse_geocoding::{self, ReverseGeocoder};
+use crate::api::{self, HttpClient, WeatherProvider};
+use crate::api::{air_quality, alerts, ensemble, history, marine};
+use crate::cache::{self, CachedForecast, CachedIpLocation, ForecastCache};
+use crate::config::{
+    Config, EndpointsConfig, GeolocationConfig, LocationConfig, PrecipitationUnit, PressureUnit,
+    TemperatureUnit, WindSpeedUnit,
+};
+use crate::models::air_quality::AirQualityData;
+use crate::models::alerts::Alerts;
+use crate::models::comparison::ModelComparison;
+use crate::models::ensemble::EnsembleData;
+use crate::models::history::DayHistory;
+use crate::models::marine::MarineData;
+use crate::models::normals::ClimateNormals;
 use crate::models::{Location, WeatherData};
-use crate::ui::hourly::get_max_hourly_scroll;
+use crate::ui::alerts::max_alerts_scroll;
+use crate::ui::daily::visible_days;
+use crate::ui::hourly::{get_max_hourly_scroll, get_min_hourly_scroll};
 
 pub enum AppState {
     Loading,
@@ -12,6 +35,65 @@ pub enum AppState {
     Error(String),
 }
 
+/// Result of a forecast fetch, tagged with the cache key of the location it
+/// was requested for so a late result for a previous location can be dropped
+pub struct Forecast {
+    pub key: String,
+    pub location: Location,
+    pub weather: WeatherData,
+}
+
+/// Optional data fetched alongside the forecast. Each arrives on its own as
+/// its service answers, so a slow one never holds back the forecast.
+enum Extra {
+    /// Missing when the air-quality service couldn't be reached; the weather
+    /// is still shown
+    AirQuality(Option<AirQualityData>),
+    /// Only fetched while marine mode is on
+    Marine(Option<MarineData>),
+    /// Past years' averages for today's date at the location
+    History(Option<DayHistory>),
+    Normals(Option<ClimateNormals>),
+    Ensemble(Option<EnsembleData>),
+    /// Never cached, since an old warning could be over or superseded
+    Alerts(Alerts),
+}
+
+/// Results reported back by background tasks, tagged with the id of the task
+/// that produced them so results from superseded tasks can be ignored
+enum TaskResult {
+    Forecast {
+        id: u64,
+        result: Result<Box<Forecast>>,
+    },
+    Extra {
+        id: u64,
+        key: String,
+        extra: Extra,
+    },
+    LocationLookup {
+        id: u64,
+        input: String,
+        result: Result<Vec<Location>>,
+    },
+    Comparison {
+        id: u64,
+        result: Result<ModelComparison>,
+    },
+}
+
+struct Task {
+    id: u64,
+    handle: JoinHandle<()>,
+}
+
+const SPINNER_FRAMES: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+/// How long an IP geolocation result is reused before asking again
+const IP_LOCATION_MAX_AGE: TimeDelta = TimeDelta::hours(1);
+
+const IP_GEOLOCATION_DISABLED: &str = "IP geolocation is disabled. Press l to set a location.";
+
 #[derive(Clone, Copy, PartialEq)]
 pub enum UnitMenuField {
     Temperature,
@@ -22,11 +104,62 @@ pub enum UnitMenuField {
 
 pub struct App {
     pub config: Config,
+    /// Config endpoints with command-line overrides applied
+    endpoints: EndpointsConfig,
+    /// Shared by every request so timeouts, retries and the User-Agent are
+    /// applied consistently
+    http: HttpClient,
+    provider: Arc<dyn WeatherProvider>,
+    reverse_geocoder: Arc<dyn ReverseGeocoder>,
+    cache: Option<ForecastCache>,
+    task_tx: UnboundedSender<TaskResult>,
+    task_rx: UnboundedReceiver<TaskResult>,
+    next_task_id: u64,
+    refresh_task: Option<Task>,
+    /// The refresh whose forecast is shown, while its extras are still
+    /// coming in
+    extras_task: Option<Task>,
+    lookup_task: Option<Task>,
+    comparison_task: Option<Task>,
+    spinner_frame: usize,
     pub state: AppState,
     pub location: Option<Location>,
     pub weather: Option<WeatherData>,
+    pub air_quality: Option<AirQualityData>,
+    pub marine: Option<MarineData>,
+    pub history: Option<DayHistory>,
+    /// 1991-2020 normals for the location, kept in their own cache file
+    pub normals: Option<ClimateNormals>,
+    /// Spread of the ensemble members, behind the daily confidence badges
+    /// and the plume view
+    pub ensemble: Option<EnsembleData>,
+    pub show_plume: bool,
+    /// Warnings, watches and advisories in force at the location
+    pub alerts: Alerts,
+    pub show_alerts: bool,
+    /// Lines scrolled in the alert detail overlay
+    pub alerts_scroll: usize,
+    /// Where the alert details were last drawn, to stop scrolling at the end
+    pub alerts_area: Cell<Rect>,
+    /// Several models side by side, fetched each time the view is opened
+    pub comparison: Option<ModelComparison>,
+    pub comparison_error: Option<String>,
+    pub comparison_scroll: usize,
+    pub show_comparison: bool,
     pub last_updated: Option<DateTime<Local>>,
-    pub hourly_scroll: usize,
+    /// True while showing a forecast restored from the cache
+    pub is_stale: bool,
+    /// Error from the last refresh when older data is still on screen
+    pub refresh_warning: Option<String>,
+    /// Consecutive failed refreshes, used to schedule retries
+    pub refresh_failures: u32,
+    /// Hours scrolled from the current hour; negative goes back into past
+    /// hours
+    pub hourly_scroll: isize,
+    /// First day shown in the daily strip
+    pub daily_scroll: usize,
+    /// Where the daily strip was last drawn, for paging and mouse scrolling
+    pub daily_area: Cell<Rect>,
     pub show_help: bool,
     pub show_units_menu: bool,
     pub units_menu_selection: UnitMenuField,
@@ -34,18 +167,60 @@ pub struct App {
     pub show_location_input: bool,
     pub location_input: String,
     pub location_error: Option<String>,
+    /// Matches for the submitted search, shown as a list to pick from
+    pub location_results: Vec<Location>,
+    pub location_selection: usize,
     pub should_quit: bool,
 }
 
 impl App {
-    pub fn new(config: Config) -> Self {
+    pub fn new(config: Config, endpoint_overrides: &EndpointsConfig) -> Self {
+        let endpoints = config.endpoints.overridden_by(endpoint_overrides);
+        let http = HttpClient::new();
+        let provider = api::provider::from_config(&http, &config.provider, &endpoints);
+        let reverse_geocoder =
+            reverse_geocoding::from_config(&http, config.location.reverse_geocoder, &endpoints);
+        let (task_tx, task_rx) = mpsc::unbounded_channel();
+
         Self {
             config,
+            endpoints,
+            http,
+            provider,
+            reverse_geocoder,
+            cache: ForecastCache::new(),
+            task_tx,
+            task_rx,
+            next_task_id: 0,
+            refresh_task: None,
+            extras_task: None,
+            lookup_task: None,
+            comparison_task: None,
+            spinner_frame: 0,
             state: AppState::Loading,
             location: None,
             weather: None,
+            air_quality: None,
+            marine: None,
+            history: None,
+            normals: None,
+            ensemble: None,
+            show_plume: false,
+            alerts: Alerts::default(),
+            show_alerts: false,
+            alerts_scroll: 0,
+            alerts_area: Cell::new(Rect::default()),
+            comparison: None,
+            comparison_error: None,
+            comparison_scroll: 0,
+            show_comparison: false,
             last_updated: None,
+            is_stale: false,
+            refresh_warning: None,
+            refresh_failures: 0,
             hourly_scroll: 0,
+            daily_scroll: 0,
+            daily_area: Cell::new(Rect::default()),
             show_help: false,
             show_units_menu: false,
             units_menu_selection: UnitMenuField::Temperature,
@@ -53,56 +228,402 @@ impl App {
             show_location_input: false,
             location_input: String::new(),
             location_error: None,
+            location_results: Vec::new(),
+            location_selection: 0,
             should_quit: false,
         }
     }
 
-    pub async fn load_weather(&mut self) -> Result<()> {
-        self.state = AppState::Loading;
+    /// Fetch the forecast in the background, replacing any refresh that is
+    /// already in flight. The result is applied by `poll_tasks`.
+    pub fn start_refresh(&mut self) {
+        if self.needs_location() {
+            self.set_error(IP_GEOLOCATION_DISABLED.to_string());
+            return;
+        }
+
+        // Keep showing what we have while the new forecast loads
+        if self.weather.is_none() {
+            self.state = AppState::Loading;
+        }
 
-        // Get location
-        let location = self.get_location().await?;
-        self.location = Some(location.clone());
+        for task in [self.refresh_task.take(), self.extras_task.take()]
+            .into_iter()
+            .flatten()
+        {
+            task.handle.abort();
+        }
 
-        // Fetch weather (always in metric units, conversion done at display time)
-        let weather = api::fetch_weather(location.latitude, location.longitude).await?;
+        let id = self.next_task_id();
+        let handle = tokio::spawn(self.fetch_forecast(id));
+        self.refresh_task = Some(Task { id, handle });
+    }
+
+    /// True when there is no place configured and IP geolocation is off, so
+    /// the user has to pick a location before anything can load
+    pub fn needs_location(&self) -> bool {
+        !self.config.geolocation.enabled && !self.config.location.has_place()
+    }
+
+    /// Wall-clock time at the location shown, which forecast times are given
+    /// in; the system's local time until a location is known
+    pub fn local_now(&self) -> NaiveDateTime {
+        match &self.location {
+            Some(location) => location.local_time(Utc::now()),
+            None => Local::now().naive_local(),
+        }
+    }
+
+    pub fn is_refreshing(&self) -> bool {
+        self.refresh_task.is_some()
+    }
+
+    pub fn is_looking_up_location(&self) -> bool {
+        self.lookup_task.is_some()
+    }
+
+    pub fn is_comparing(&self) -> bool {
+        self.comparison_task.is_some()
+    }
+
+    pub fn cancel_refresh(&mut self) {
+        if let Some(task) = self.refresh_task.take() {
+            task.handle.abort();
+            if self.weather.is_none() {
+                self.set_error("Refresh cancelled".to_string());
+            }
+        }
+    }
+
+    pub fn cancel_location_lookup(&mut self) {
+        if let Some(task) = self.lookup_task.take() {
+            task.handle.abort();
+            self.location_error = Some("Lookup cancelled".to_string());
+        }
+    }
+
+    /// Apply the results of any background tasks that have finished
+    pub fn poll_tasks(&mut self) {
+        while let Ok(message) = self.task_rx.try_recv() {
+            match message {
+                TaskResult::Forecast { id, result } => {
+                    if !is_current(&self.refresh_task, id) {
+                        continue;
+                    }
+                    let task = self.refresh_task.take();
+                    match result {
+                        Ok(forecast) => {
+                            // Keep the task running for the extras still on their way
+                            self.extras_task = task;
+                            self.apply_forecast(*forecast);
+                        }
+                        Err(e) => {
+                            if let Some(task) = task {
+                                task.handle.abort();
+                            }
+                            self.refresh_failed(e.to_string());
+                        }
+                    }
+                }
+                TaskResult::Extra { id, key, extra } => {
+                    if !is_current(&self.refresh_task, id) && !is_current(&self.extras_task, id) {
+                        continue;
+                    }
+                    self.apply_extra(&key, extra);
+                }
+                TaskResult::LocationLookup { id, input, result } => {
+                    if !is_current(&self.lookup_task, id) {
+                        continue;
+                    }
+                    self.lookup_task = None;
+                    self.finish_location_lookup(input, result);
+                }
+                TaskResult::Comparison { id, result } => {
+                    if !is_current(&self.comparison_task, id) {
+                        continue;
+                    }
+                    self.comparison_task = None;
+                    match result {
+                        Ok(comparison) => {
+                            self.comparison = Some(comparison);
+                            self.comparison_error = None;
+                        }
+                        Err(e) => self.comparison_error = Some(e.to_string()),
+                    }
+                }
+            }
+        }
+    }
+
+    /// Advance the loading spinner; called once per UI tick
+    pub fn on_tick(&mut self) {
+        if self.is_refreshing() || self.is_looking_up_location() || self.is_comparing() {
+            self.spinner_frame = (self.spinner_frame + 1) % SPINNER_FRAMES.len();
+        }
+    }
+
+    pub fn spinner(&self) -> &'static str {
+        SPINNER_FRAMES[self.spinner_frame]
+    }
 
-        self.weather = Some(weather);
+    fn next_task_id(&mut self) -> u64 {
+        self.next_task_id += 1;
+        self.next_task_id
+    }
+
+    /// Restore the last forecast saved for the configured location, if any
+    pub fn load_cached(&mut self) -> bool {
+        if self.needs_location() {
+            return false;
+        }
+
+        let key = cache::location_key(&self.config.location);
+        let Some(cached) = self.cache.as_ref().and_then(|c| c.load(&key)) else {
+            return false;
+        };
+
+        self.normals = self
+            .cache
+            .as_ref()
+            .and_then(|c| c.load_normals(cached.location.latitude, cached.location.longitude));
+        self.location = Some(cached.location);
+        self.weather = Some(cached.weather);
+        self.air_quality = cached.air_quality;
+        self.marine = cached.marine;
+        self.history = cached.history;
+        self.ensemble = cached.ensemble;
+        self.last_updated = Some(cached.last_updated);
+        self.is_stale = true;
+        self.state = AppState::Ready;
+        true
+    }
+
+    /// A self-contained fetch for the configured location that can be
+    /// spawned onto the runtime while the UI keeps drawing. The forecast is
+    /// reported as soon as it arrives and each extra as its service answers.
+    fn fetch_forecast(&self, id: u64) -> impl Future<Output = ()> + Send + 'static {
+        let tx = self.task_tx.clone();
+        let http = self.http.clone();
+        let endpoints = self.endpoints.clone();
+        let location_config = self.config.location.clone();
+        let geolocation = self.config.geolocation.clone();
+        let provider = self.provider.clone();
+        let reverse_geocoder = self.reverse_geocoder.clone();
+        let cache = self.cache.clone();
+        let with_marine = self.config.display.marine;
+        let previous_history = self.history.clone();
+        let previous_normals = self.normals.clone();
+        let forecast_days = self.config.provider.forecast_days();
+        let alerts_config = self.config.alerts.clone();
+
+        async move {
+            let key = cache::location_key(&location_config);
+            let location = match resolve_location(
+                &http,
+                &endpoints,
+                reverse_geocoder.as_ref(),
+                &geolocation,
+                cache.as_ref(),
+                &location_config,
+            )
+            .await
+            {
+                Ok(location) => location,
+                Err(e) => {
+                    let _ = tx.send(TaskResult::Forecast { id, result: Err(e) });
+                    return;
+                }
+            };
+            let send = |extra| {
+                let _ = tx.send(TaskResult::Extra {
+                    id,
+                    key: key.clone(),
+                    extra,
+                });
+            };
+
+            // Fetch weather (always in metric units, conversion done at display time)
+            // side by side with the extras
+            let fetch_weather = async {
+                // Providers with a fixed horizon can return more days than asked
+                let result = provider.fetch_weather(&location).await.map(|mut weather| {
+                    weather.daily.truncate(forecast_days);
+                    Box::new(Forecast {
+                        key: key.clone(),
+                        location: location.clone(),
+                        weather,
+                    })
+                });
+                let _ = tx.send(TaskResult::Forecast { id, result });
+            };
+            let fetch_air_quality = async {
+                let air_quality = air_quality::fetch_air_quality(
+                    &http,
+                    endpoints.air_quality(),
+                    &location,
+                    forecast_days,
+                )
+                .await;
+                send(Extra::AirQuality(air_quality.ok()));
+            };
+            let fetch_marine = async {
+                let marine = if with_marine {
+                    marine::fetch_marine(&http, endpoints.marine(), &location, forecast_days)
+                        .await
+                        .ok()
+                } else {
+                    None
+                };
+                send(Extra::Marine(marine));
+            };
+            // Past years don't change, so history is only fetched once a day
+            let today = location.local_time(Utc::now()).date();
+            let fetch_history = async {
+                let history = match previous_history {
+                    Some(h) if h.date == today.format("%Y-%m-%d").to_string() => Some(h),
+                    _ => history::fetch_day_history(&http, endpoints.archive(), &location, today)
+                        .await
+                        .ok(),
+                };
+                send(Extra::History(history));
+            };
+            let fetch_normals = async {
+                let normals = match previous_normals {
+                    Some(n) if n.covers(location.latitude, location.longitude) => Some(n),
+                    _ => load_normals(&http, &endpoints, cache.as_ref(), &location).await,
+                };
+                send(Extra::Normals(normals));
+            };
+            let fetch_ensemble = async {
+                let ensemble =
+                    ensemble::fetch_ensemble(&http, endpoints.ensemble(), &location, forecast_days)
+                        .await;
+                send(Extra::Ensemble(ensemble.ok()));
+            };
+            let fetch_alerts = async {
+                let alerts =
+                    alerts::fetch_alerts(&http, endpoints.nws(), &alerts_config, &location).await;
+                send(Extra::Alerts(alerts));
+            };
+            tokio::join!(
+                fetch_weather,
+                fetch_air_quality,
+                fetch_marine,
+                fetch_history,
+                fetch_normals,
+                fetch_ensemble,
+                fetch_alerts,
+            );
+        }
+    }
+
+    /// Show a freshly fetched forecast and save it to the cache
+    fn apply_forecast(&mut self, forecast: Forecast) {
+        if forecast.key != cache::location_key(&self.config.location) {
+            return;
+        }
+
+        // Complete an older config's place once, so later starts use it as-is.
+        // The coordinates are kept so the cache key doesn't change.
+        if self.config.location.is_incomplete_zipcode() {
+            let config = &mut self.config.location;
+            config.city = Some(forecast.location.city.clone());
+            config.region = forecast.location.region.clone();
+            config.country = Some(forecast.location.country.clone());
+            config.timezone = Some(forecast.location.timezone.clone());
+            let _ = self.config.save();
+        }
+
+        self.location = Some(forecast.location);
+        self.weather = Some(forecast.weather);
         self.last_updated = Some(Local::now());
+        self.is_stale = false;
+        self.refresh_warning = None;
+        self.refresh_failures = 0;
         self.hourly_scroll = 0;
+        self.daily_scroll = 0;
         self.state = AppState::Ready;
+        self.store_cache();
+    }
 
-        Ok(())
+    /// Show one of the extras fetched alongside the forecast. Until the
+    /// fresh forecast replaces it, the extras shown may be newer.
+    fn apply_extra(&mut self, key: &str, extra: Extra) {
+        if key != cache::location_key(&self.config.location) {
+            return;
+        }
+
+        match extra {
+            Extra::AirQuality(air_quality) => self.air_quality = air_quality,
+            Extra::Marine(marine) => self.marine = marine,
+            Extra::History(history) => self.history = history,
+            Extra::Ensemble(ensemble) => self.ensemble = ensemble,
+            // Normals have their own cache file and alerts are never cached
+            Extra::Normals(normals) => {
+                self.normals = normals;
+                return;
+            }
+            Extra::Alerts(alerts) => {
+                self.alerts = alerts;
+                return;
+            }
+        }
+        self.store_cache();
     }
 
-    async fn get_location(&self) -> Result<Location> {
-        // Check if zipcode is configured
-        if let Some(zipcode) = &self.config.location.zipcode {
-            return api::lookup_zipcode(zipcode).await;
+    /// Save the fresh forecast on screen, with whatever extras have arrived
+    fn store_cache(&self) {
+        if self.is_stale {
+            return;
         }
+        let (Some(cache), Some(location), Some(weather), Some(last_updated)) = (
+            &self.cache,
+            &self.location,
+            &self.weather,
+            self.last_updated,
+        ) else {
+            return;
+        };
 
-        // Check if coordinates are configured
-        if let (Some(lat), Some(lon)) = (
-            self.config.location.latitude,
-            self.config.location.longitude,
-        ) {
-            return Ok(Location {
-                latitude: lat,
-                longitude: lon,
-                city: self
-                    .config
-                    .location
-                    .city
-                    .clone()
-                    .unwrap_or_else(|| "Unknown".to_string()),
-                region: None,
-                country: "".to_string(),
-                timezone: "auto".to_string(),
-            });
+        let _ = cache.store(
+            &cache::location_key(&self.config.location),
+            &CachedForecast {
+                location: location.clone(),
+                weather: weather.clone(),
+                air_quality: self.air_quality.clone(),
+                marine: self.marine.clone(),
+                history: self.history.clone(),
+                ensemble: self.ensemble.clone(),
+                last_updated,
+            },
+        );
+    }
+
+    /// Record a failed refresh. Existing data stays on screen with a warning;
+    /// the error only takes over the dashboard when there is nothing to show.
+    fn refresh_failed(&mut self, message: String) {
+        self.refresh_failures += 1;
+        if self.weather.is_some() {
+            self.refresh_warning = Some(message);
+        } else {
+            self.set_error(message);
         }
+    }
 
-        // Fall back to IP geolocation
-        api::get_location_from_ip().await
+    /// Drop data for the previous location once a new one is chosen
+    fn clear_weather(&mut self) {
+        self.weather = None;
+        self.air_quality = None;
+        self.marine = None;
+        self.history = None;
+        self.normals = None;
+        self.ensemble = None;
+        self.alerts = Alerts::default();
+        self.show_alerts = false;
+        self.comparison = None;
+        self.is_stale = false;
+        self.refresh_warning = None;
+        self.refresh_failures = 0;
     }
 
     pub fn toggle_units_menu(&mut self) {
@@ -175,21 +696,116 @@ impl App {
     }
 
     pub fn scroll_hourly_up(&mut self) {
-        if self.hourly_scroll > 0 {
-            self.hourly_scroll -= 1;
+        if let Some(weather) = &self.weather {
+            if self.hourly_scroll > get_min_hourly_scroll(&weather.hourly, self.local_now()) {
+                self.hourly_scroll -= 1;
+            }
         }
     }
 
     pub fn scroll_hourly_down(&mut self) {
         if let Some(weather) = &self.weather {
             // Approximate visible height (will be adjusted by actual render area)
-            let max_scroll = get_max_hourly_scroll(&weather.hourly, 12);
+            let max_scroll = get_max_hourly_scroll(&weather.hourly, 12, self.local_now());
             if self.hourly_scroll < max_scroll {
                 self.hourly_scroll += 1;
             }
         }
     }
 
+    /// Move the daily strip a day later or earlier, stopping once the last
+    /// day is in view
+    pub fn scroll_daily(&mut self, forward: bool) {
+        let Some(weather) = &self.weather else {
+            return;
+        };
+        let days = weather.daily.len();
+        let max_scroll = days.saturating_sub(visible_days(self.daily_area.get().width, days));
+        self.daily_scroll = if forward {
+            (self.daily_scroll + 1).min(max_scroll)
+        } else {
+            self.daily_scroll.saturating_sub(1).min(max_scroll)
+        };
+    }
+
+    /// Switch marine mode on or off and remember the choice. Sea conditions
+    /// are fetched straight away the first time it's turned on.
+    pub fn toggle_marine(&mut self) {
+        self.config.display.marine = !self.config.display.marine;
+        let _ = self.config.save();
+        if self.config.display.marine && self.marine.is_none() {
+            self.start_refresh();
+        }
+    }
+
+    pub fn toggle_plume(&mut self) {
+        self.show_plume = !self.show_plume;
+    }
+
+    pub fn toggle_alerts(&mut self) {
+        self.show_alerts = !self.show_alerts;
+        self.alerts_scroll = 0;
+    }
+
+    pub fn scroll_alerts(&mut self, down: bool) {
+        let max_scroll = max_alerts_scroll(&self.alerts, self.alerts_area.get());
+        self.alerts_scroll = if down {
+            (self.alerts_scroll + 1).min(max_scroll)
+        } else {
+            self.alerts_scroll.saturating_sub(1)
+        };
+    }
+
+    /// Open the model comparison, fetching the configured models for the
+    /// current location, or close it
+    pub fn toggle_comparison(&mut self) {
+        self.show_comparison = !self.show_comparison;
+        if self.show_comparison {
+            self.comparison_scroll = 0;
+            self.start_comparison();
+        } else if let Some(task) = self.comparison_task.take() {
+            task.handle.abort();
+        }
+    }
+
+    pub fn start_comparison(&mut self) {
+        let Some(location) = self.location.clone() else {
+            self.comparison_error = Some("No location yet".to_string());
+            return;
+        };
+        if let Some(task) = self.comparison_task.take() {
+            task.handle.abort();
+        }
+
+        let id = self.next_task_id();
+        let http = self.http.clone();
+        let url = self.endpoints.weather().to_string();
+        let models = self.config.provider.compare_models.clone();
+        let forecast_days = self.config.provider.forecast_days();
+        let tx = self.task_tx.clone();
+        let handle = tokio::spawn(async move {
+            let result = api::weather::fetch_model_comparison(
+                &http,
+                &url,
+                &location,
+                &models,
+                forecast_days,
+            )
+            .await;
+            let _ = tx.send(TaskResult::Comparison { id, result });
+        });
+        self.comparison_task = Some(Task { id, handle });
+    }
+
+    pub fn scroll_comparison(&mut self, down: bool) {
+        let rows = self.comparison.as_ref().map_or(0, |c| c.hourly.len());
+        self.comparison_scroll = if down {
+            (self.comparison_scroll + 1).min(rows.saturating_sub(1))
+        } else {
+            self.comparison_scroll.saturating_sub(1)
+        };
+    }
+
     pub fn toggle_help(&mut self) {
         self.show_help = !self.show_help;
     }
@@ -208,48 +824,320 @@ impl App {
         self.show_location_input = false;
         self.location_input.clear();
         self.location_error = None;
+        self.clear_location_results();
     }
 
     pub fn location_input_char(&mut self, c: char) {
         self.location_input.push(c);
         self.location_error = None;
+        self.clear_location_results();
     }
 
     pub fn location_input_backspace(&mut self) {
         self.location_input.pop();
         self.location_error = None;
+        self.clear_location_results();
     }
 
-    pub async fn submit_location(&mut self) -> Result<bool> {
+    pub fn has_location_results(&self) -> bool {
+        !self.location_results.is_empty()
+    }
+
+    /// Go back to editing the search text
+    pub fn clear_location_results(&mut self) {
+        self.location_results.clear();
+        self.location_selection = 0;
+    }
+
+    pub fn location_selection_up(&mut self) {
+        self.location_selection = self.location_selection.saturating_sub(1);
+    }
+
+    pub fn location_selection_down(&mut self) {
+        if self.location_selection + 1 < self.location_results.len() {
+            self.location_selection += 1;
+        }
+    }
+
+    /// Look up the entered location in the background, or save the
+    /// highlighted match when results are already listed. An empty input
+    /// switches back to IP geolocation straight away.
+    pub fn submit_location(&mut self) -> Result<()> {
         let input = self.location_input.trim().to_string();
 
+        if self.has_location_results() {
+            let location = self.location_results.swap_remove(self.location_selection);
+            self.save_location(input, location);
+            return Ok(());
+        }
+
+        if input.is_empty() && !self.config.geolocation.enabled {
+            self.location_error = Some("IP geolocation is disabled; enter a place".to_string());
+            return Ok(());
+        }
+
         if input.is_empty() {
             // Clear zipcode, use IP geolocation
-            self.config.location.zipcode = None;
-            self.config.location.latitude = None;
-            self.config.location.longitude = None;
-            self.config.location.city = None;
+            self.config.location.clear_place();
             self.config.save()?;
+            self.clear_weather();
             self.close_location_input();
-            return Ok(true); // Reload weather
+            self.start_refresh();
+            return Ok(());
+        }
+
+        if let Some(task) = self.lookup_task.take() {
+            task.handle.abort();
         }
+        self.location_error = None;
+
+        let id = self.next_task_id();
+        let http = self.http.clone();
+        let url = self.endpoints.geocoding().to_string();
+        let default_country = self.config.location.country_code.clone();
+        let tx = self.task_tx.clone();
+        let handle = tokio::spawn(async move {
+            // "zip, CC" overrides the configured default country
+            let (query, country) = geocoding::parse_query(&input);
+            let country = country.map(str::to_string).or(default_country);
+            let result =
+                geocoding::search_locations(&http, &url, query, country.as_deref(), SEARCH_RESULTS)
+                    .await;
+            let _ = tx.send(TaskResult::LocationLookup { id, input, result });
+        });
+        self.lookup_task = Some(Task { id, handle });
+
+        Ok(())
+    }
 
-        // Try to look up the location
-        match api::lookup_zipcode(&input).await {
-            Ok(location) => {
-                // Save to config
-                self.config.location.zipcode = Some(input);
-                self.config.location.latitude = Some(location.latitude);
-                self.config.location.longitude = Some(location.longitude);
-                self.config.location.city = Some(location.city);
-                self.config.save()?;
-                self.close_location_input();
-                Ok(true) // Reload weather
+    /// A single match is taken as-is; several are listed for the user to pick
+    fn finish_location_lookup(&mut self, input: String, result: Result<Vec<Location>>) {
+        match result {
+            Ok(mut results) if results.len() == 1 => {
+                self.save_location(input, results.remove(0));
+            }
+            Ok(results) => {
+                self.location_results = results;
+                self.location_selection = 0;
             }
             Err(e) => {
                 self.location_error = Some(format!("Not found: {}", e));
-                Ok(false) // Don't reload
             }
         }
     }
+
+    fn save_location(&mut self, input: String, location: Location) {
+        let config = &mut self.config.location;
+        config.clear_place();
+        config.zipcode = Some(input);
+        config.latitude = Some(location.latitude);
+        config.longitude = Some(location.longitude);
+        config.city = Some(location.city);
+        config.region = location.region;
+        config.country = Some(location.country);
+        config.timezone = Some(location.timezone);
+        self.close_location_input();
+        if let Err(e) = self.config.save() {
+            self.set_error(e.to_string());
+            return;
+        }
+        self.clear_weather();
+        self.start_refresh();
+    }
+}
+
+fn is_current(task: &Option<Task>, id: u64) -> bool {
+    task.as_ref().is_some_and(|t| t.id == id)
+}
+
+async fn resolve_location(
+    http: &HttpClient,
+    endpoints: &EndpointsConfig,
+    reverse_geocoder: &dyn ReverseGeocoder,
+    geolocation: &GeolocationConfig,
+    cache: Option<&ForecastCache>,
+    config: &LocationConfig,
+) -> Result<Location> {
+    // Older configs saved only part of the place picked by zipcode; look
+    // the zipcode up again rather than sending the coordinates elsewhere
+    if config.is_incomplete_zipcode() {
+        if let Some(zipcode) = &config.zipcode {
+            return api::lookup_zipcode(
+                http,
+                endpoints.geocoding(),
+                zipcode,
+                config.country_code.as_deref(),
+            )
+            .await;
+        }
+    }
+
+    // Otherwise coordinates win over the zipcode: they pin down the place
+    // the user picked, while the search text may match several
+    if let (Some(lat), Some(lon)) = (config.latitude, config.longitude) {
+        if let (Some(city), Some(country), Some(timezone)) =
+            (&config.city, &config.country, &config.timezone)
+        {
+            return Ok(Location {
+                latitude: lat,
+                longitude: lon,
+                city: city.clone(),
+                region: config.region.clone(),
+                country: country.clone(),
+                timezone: timezone.clone(),
+            });
+        }
+
+        // Fill in whatever the config leaves out; configured values win.
+        // The geocoder only guesses the timezone from the nearest big city,
+        // so ask Open-Meteo, which knows the zone boundaries.
+        let place = reverse_geocoder.reverse(lat, lon).await?;
+        let timezone = match &config.timezone {
+            Some(timezone) => timezone.clone(),
+            None => api::weather::lookup_timezone(http, endpoints.weather(), lat, lon)
+                .await
+                .unwrap_or(place.timezone),
+        };
+        return Ok(Location {
+            latitude: lat,
+            longitude: lon,
+            city: config.city.clone().unwrap_or(place.city),
+            region: config.region.clone().or(place.region),
+            country: config.country.clone().unwrap_or(place.country),
+            timezone,
+        });
+    }
+
+    // Check if zipcode is configured
+    if let Some(zipcode) = &config.zipcode {
+        return api::lookup_zipcode(
+            http,
+            endpoints.geocoding(),
+            zipcode,
+            config.country_code.as_deref(),
+        )
+        .await;
+    }
+
+    // Fall back to IP geolocation
+    locate_by_ip(http, endpoints, geolocation, cache).await
+}
+
+/// IP geolocation through the configured providers. A recent result is
+/// reused rather than asking again, and an older one stands in when every
+/// provider fails.
+async fn locate_by_ip(
+    http: &HttpClient,
+    endpoints: &EndpointsConfig,
+    geolocation: &GeolocationConfig,
+    cache: Option<&ForecastCache>,
+) -> Result<Location> {
+    if !geolocation.enabled {
+        bail!(IP_GEOLOCATION_DISABLED);
+    }
+
+    let cached = cache.and_then(|c| c.load_ip_location());
+    if let Some(entry) = &cached {
+        if Local::now() - entry.fetched_at < IP_LOCATION_MAX_AGE {
+            return Ok(entry.location.clone());
+        }
+    }
+
+    match api::get_location_from_ip(http, endpoints, &geolocation.providers).await {
+        Ok(location) => {
+            if let Some(cache) = cache {
+                let _ = cache.store_ip_location(&CachedIpLocation {
+                    location: location.clone(),
+                    fetched_at: Local::now(),
+                });
+            }
+            Ok(location)
+        }
+        Err(e) => cached.map(|entry| entry.location).ok_or(e),
+    }
+}
+
+/// Climate normals from the disk cache, fetching and saving them the first
+/// time a location is seen
+async fn load_normals(
+    http: &HttpClient,
+    endpoints: &EndpointsConfig,
+    cache: Option<&ForecastCache>,
+    location: &Location,
+) -> Option<ClimateNormals> {
+    if let Some(normals) = cache.and_then(|c| c.load_normals(location.latitude, location.longitude))
+    {
+        return Some(normals);
+    }
+
+    let normals = history::fetch_normals(http, endpoints.archive(), location)
+        .await
+        .ok()?;
+    if let Some(cache) = cache {
+        let _ = cache.store_normals(&normals);
+    }
+    Some(normals)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::reverse_geocoding::OfflineGeocoder;
+    use crate::api::test_server::{MockResponse, TestServer};
+
+    const ZIP_10001: &str = include_str!("../tests/fixtures/geocoding/10001.json");
+
+    #[tokio::test]
+    async fn test_resolve_saved_zipcode_with_country() {
+        let server = TestServer::start(vec![
+            ("/search", MockResponse::json(ZIP_10001)),
+            ("/search", MockResponse::json(ZIP_10001)),
+        ])
+        .await;
+        let endpoints = EndpointsConfig {
+            geocoding: Some(format!("{}/search", server.url())),
+            ..Default::default()
+        };
+        let resolve = |config: LocationConfig| {
+            let endpoints = endpoints.clone();
+            async move {
+                resolve_location(
+                    &HttpClient::new(),
+                    &endpoints,
+                    &OfflineGeocoder,
+                    &GeolocationConfig::default(),
+                    None,
+                    &config,
+                )
+                .await
+                .unwrap()
+            }
+        };
+
+        // Saved from the location prompt as typed
+        let location = resolve(LocationConfig {
+            zipcode: Some("10001, US".to_string()),
+            ..Default::default()
+        })
+        .await;
+        assert_eq!(location.display_name(), "New York, New York");
+
+        // An older save without the country or timezone is looked up again
+        let location = resolve(LocationConfig {
+            zipcode: Some("10001, US".to_string()),
+            latitude: Some(40.75),
+            longitude: Some(-73.99),
+            country_code: Some("FR".to_string()),
+            ..Default::default()
+        })
+        .await;
+        assert_eq!(location.display_name(), "New York, New York");
+
+        let requests = server.requests_to("/search");
+        assert_eq!(requests.len(), 2);
+        for request in &requests {
+            assert!(request.query.starts_with("name=10001&"));
+            assert!(request.query.contains("countryCode=US"));
+        }
+    }
 }
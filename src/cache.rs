@@ -0,0 +1,322 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::process;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::config::LocationConfig;
+use crate::models::air_quality::AirQualityData;
+use crate::models::ensemble::EnsembleData;
+use crate::models::history::DayHistory;
+use crate::models::marine::MarineData;
+use crate::models::normals::ClimateNormals;
+use crate::models::{Location, WeatherData};
+
+const IP_LOCATION: &str = "ip-location";
+
+/// The last successful fetch for one location, shown on startup while a
+/// fresh forecast loads
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedForecast {
+    pub location: Location,
+    pub weather: WeatherData,
+    #[serde(default)]
+    pub air_quality: Option<AirQualityData>,
+    #[serde(default)]
+    pub marine: Option<MarineData>,
+    #[serde(default)]
+    pub history: Option<DayHistory>,
+    #[serde(default)]
+    pub ensemble: Option<EnsembleData>,
+    pub last_updated: DateTime<Local>,
+}
+
+/// The last place IP geolocation reported, reused for a while to spare the
+/// rate-limited services and as a fallback when none of them answer
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedIpLocation {
+    pub location: Location,
+    pub fetched_at: DateTime<Local>,
+}
+
+/// Forecasts stored as one JSON file per location. Writes go to a temporary
+/// file that is renamed into place, so several wxman instances can share the
+/// directory without ever reading a half-written file.
+#[derive(Clone)]
+pub struct ForecastCache {
+    dir: PathBuf,
+}
+
+impl ForecastCache {
+    /// Cache under the platform cache directory, e.g. `~/.cache/wxman`
+    pub fn new() -> Option<Self> {
+        dirs::cache_dir().map(|dir| Self::at(dir.join("wxman")))
+    }
+
+    pub fn at(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("forecast-{}.json", key))
+    }
+
+    /// Missing, unreadable or outdated-format entries are treated as a miss
+    pub fn load(&self, key: &str) -> Option<CachedForecast> {
+        let content = fs::read_to_string(self.path(key)).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    pub fn store(&self, key: &str, forecast: &CachedForecast) -> Result<()> {
+        let content = serde_json::to_string(forecast).context("Failed to serialize forecast")?;
+        self.write(&format!("forecast-{}", key), &content)
+    }
+
+    pub fn load_ip_location(&self) -> Option<CachedIpLocation> {
+        let content = fs::read_to_string(self.dir.join(format!("{}.json", IP_LOCATION))).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    pub fn store_ip_location(&self, entry: &CachedIpLocation) -> Result<()> {
+        let content = serde_json::to_string(entry).context("Failed to serialize IP location")?;
+        self.write(IP_LOCATION, &content)
+    }
+
+    /// Normals for the grid point around a location. They're computed
+    /// from decades of data, so entries never expire.
+    pub fn load_normals(&self, latitude: f64, longitude: f64) -> Option<ClimateNormals> {
+        let name = normals_name(latitude, longitude);
+        let content = fs::read_to_string(self.dir.join(format!("{}.json", name))).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    pub fn store_normals(&self, normals: &ClimateNormals) -> Result<()> {
+        let content = serde_json::to_string(normals).context("Failed to serialize normals")?;
+        self.write(&normals_name(normals.latitude, normals.longitude), &content)
+    }
+
+    /// Atomically replace `{name}.json` in the cache directory
+    fn write(&self, name: &str, content: &str) -> Result<()> {
+        fs::create_dir_all(&self.dir)
+            .with_context(|| format!("Failed to create cache dir: {}", self.dir.display()))?;
+
+        // Unique per process and write so concurrent instances never share a temp file
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.subsec_nanos())
+            .unwrap_or_default();
+        let tmp = self
+            .dir
+            .join(format!(".{}.{}.{}.tmp", name, process::id(), nanos));
+
+        fs::write(&tmp, content)
+            .with_context(|| format!("Failed to write cache file: {}", tmp.display()))?;
+        fs::rename(&tmp, self.dir.join(format!("{}.json", name))).inspect_err(|_| {
+            let _ = fs::remove_file(&tmp);
+        })?;
+
+        Ok(())
+    }
+}
+
+fn normals_name(latitude: f64, longitude: f64) -> String {
+    let (latitude, longitude) = ClimateNormals::grid(latitude, longitude);
+    format!("normals-{:.1}_{:.1}", latitude, longitude)
+}
+
+/// Cache key for the configured location: the coordinates, the zipcode, or
+/// `ip` when the location comes from IP geolocation
+pub fn location_key(config: &LocationConfig) -> String {
+    let raw = if let (Some(lat), Some(lon)) = (config.latitude, config.longitude) {
+        format!("{:.4}_{:.4}", lat, lon)
+    } else if let Some(zipcode) = &config.zipcode {
+        format!("zip-{}", zipcode.trim().to_lowercase())
+    } else {
+        "ip".to_string()
+    };
+
+    // Keep the key safe to use as a file name
+    raw.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | '_') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::normals::DayNormal;
+    use crate::models::{CurrentWeather, DailyForecast, HourlyForecast};
+    use std::env;
+
+    fn temp_cache(name: &str) -> ForecastCache {
+        let dir = env::temp_dir().join(format!("wxman-cache-{}-{}", name, process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        ForecastCache::at(dir)
+    }
+
+    fn forecast() -> CachedForecast {
+        CachedForecast {
+            location: Location {
+                latitude: 34.0901,
+                longitude: -118.4065,
+                city: "Beverly Hills".to_string(),
+                region: Some("California".to_string()),
+                country: "United States".to_string(),
+                timezone: "America/Los_Angeles".to_string(),
+            },
+            weather: WeatherData {
+                current: CurrentWeather {
+                    temperature: 21.5,
+                    apparent_temperature: 21.0,
+                    humidity: Some(60),
+                    weather_code: 1,
+                    wind_speed: 10.0,
+                    wind_direction: 270,
+                    wind_gusts: Some(18.0),
+                    cloud_cover: Some(20),
+                    pressure: Some(1015.0),
+                    precipitation: 0.0,
+                    uv_index: Some(5.0),
+                    is_day: true,
+                    dew_point: Some(12.0),
+                    visibility: None,
+                },
+                hourly: vec![HourlyForecast {
+                    time: "2024-06-01T12:00".to_string(),
+                    temperature: 21.5,
+                    apparent_temperature: 21.0,
+                    precipitation_probability: Some(0),
+                    precipitation: 0.0,
+                    weather_code: 1,
+                    wind_speed: 10.0,
+                    humidity: Some(55),
+                    dew_point: Some(12.0),
+                    wind_direction: Some(270),
+                    wind_gusts: Some(18.0),
+                    cloud_cover: Some(20),
+                    visibility: None,
+                    uv_index: Some(5.0),
+                    snowfall: None,
+                }],
+                daily: vec![DailyForecast {
+                    date: "2024-06-01".to_string(),
+                    weather_code: 1,
+                    temp_max: 24.0,
+                    temp_min: 15.0,
+                    apparent_temp_max: 24.0,
+                    apparent_temp_min: 15.0,
+                    sunrise: "2024-06-01T05:42".to_string(),
+                    sunset: "2024-06-01T20:02".to_string(),
+                    precipitation_sum: 0.0,
+                    precipitation_probability: Some(0),
+                    wind_speed_max: 18.0,
+                    uv_index_max: 9.0,
+                }],
+                minutely: Vec::new(),
+            },
+            air_quality: None,
+            marine: None,
+            history: None,
+            ensemble: None,
+            last_updated: Local::now(),
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let cache = temp_cache("round-trip");
+        let stored = forecast();
+        cache.store("zip-90210", &stored).unwrap();
+
+        let loaded = cache.load("zip-90210").unwrap();
+        assert_eq!(loaded.location.city, "Beverly Hills");
+        assert_eq!(loaded.weather.current.temperature, 21.5);
+        assert_eq!(loaded.weather.daily[0].sunset, "2024-06-01T20:02");
+        assert_eq!(loaded.last_updated, stored.last_updated);
+
+        // Only the final file remains; temp files are renamed away
+        let files: Vec<_> = fs::read_dir(&cache.dir).unwrap().collect();
+        assert_eq!(files.len(), 1);
+        let _ = fs::remove_dir_all(&cache.dir);
+    }
+
+    #[test]
+    fn test_missing_or_corrupt_entry() {
+        let cache = temp_cache("corrupt");
+        assert!(cache.load("ip").is_none());
+
+        fs::create_dir_all(&cache.dir).unwrap();
+        fs::write(cache.path("ip"), "{not json").unwrap();
+        assert!(cache.load("ip").is_none());
+        let _ = fs::remove_dir_all(&cache.dir);
+    }
+
+    #[test]
+    fn test_ip_location_round_trip() {
+        let cache = temp_cache("ip-location");
+        assert!(cache.load_ip_location().is_none());
+
+        let entry = CachedIpLocation {
+            location: forecast().location,
+            fetched_at: Local::now(),
+        };
+        cache.store_ip_location(&entry).unwrap();
+
+        let loaded = cache.load_ip_location().unwrap();
+        assert_eq!(loaded.location.city, "Beverly Hills");
+        assert_eq!(loaded.fetched_at, entry.fetched_at);
+        // Kept apart from cached forecasts
+        assert!(cache.load("ip").is_none());
+        let _ = fs::remove_dir_all(&cache.dir);
+    }
+
+    #[test]
+    fn test_normals_round_trip() {
+        let cache = temp_cache("normals");
+        assert!(cache.load_normals(39.74, -104.99).is_none());
+
+        let mut days = vec![None; 366];
+        days[289] = Some(DayNormal {
+            temp_max: 18.0,
+            temp_min: 4.5,
+            precipitation_probability: 20.0,
+        });
+        cache
+            .store_normals(&ClimateNormals {
+                latitude: 39.7,
+                longitude: -105.0,
+                days,
+            })
+            .unwrap();
+
+        // Shared by anywhere on the same grid point
+        let loaded = cache.load_normals(39.71, -104.96).unwrap();
+        assert_eq!(loaded.days[289].as_ref().unwrap().temp_min, 4.5);
+        assert!(cache.dir.join("normals-39.7_-105.0.json").exists());
+        assert!(cache.load_normals(40.02, -105.27).is_none());
+        let _ = fs::remove_dir_all(&cache.dir);
+    }
+
+    #[test]
+    fn test_location_key() {
+        let mut config = LocationConfig::default();
+        assert_eq!(location_key(&config), "ip");
+
+        config.zipcode = Some("New York/NY".to_string());
+        assert_eq!(location_key(&config), "zip-new_york_ny");
+
+        // A picked place is identified by its coordinates, since the same
+        // search text can match several places
+        config.latitude = Some(34.09012);
+        config.longitude = Some(-118.40649);
+        assert_eq!(location_key(&config), "34.0901_-118.4065");
+    }
+}
@@ -0,0 +1,129 @@
+use anyhow::{anyhow, Result};
+
+use crate::config::EndpointsConfig;
+
+pub const USAGE: &str = "\
+Usage: wxman [OPTIONS]
+
+Options:
+      --weather-url <URL>         Open-Meteo forecast endpoint
+      --geocoding-url <URL>       Open-Meteo geocoding endpoint
+      --air-quality-url <URL>     Open-Meteo air-quality endpoint
+      --marine-url <URL>          Open-Meteo marine endpoint
+      --archive-url <URL>         Open-Meteo historical weather endpoint
+      --ensemble-url <URL>        Open-Meteo ensemble endpoint
+      --ip-url <URL>              ipapi.co geolocation endpoint
+      --ipwhois-url <URL>         ipwho.is geolocation endpoint
+      --ipinfo-url <URL>          ipinfo.io geolocation endpoint
+      --nws-url <URL>             National Weather Service base URL
+      --met-norway-url <URL>      MET Norway Locationforecast base URL
+      --openweathermap-url <URL>  OpenWeatherMap One Call base URL
+      --nominatim-url <URL>       Nominatim reverse geocoding base URL
+  -h, --help                      Print help
+  -V, --version                   Print version
+
+Endpoint options override [endpoints] in config.toml for this run only.";
+
+#[derive(Debug, Default)]
+pub struct Args {
+    pub help: bool,
+    pub version: bool,
+    /// Endpoint overrides given on the command line; never written to config
+    pub endpoints: EndpointsConfig,
+}
+
+impl Args {
+    /// Parse arguments, excluding the program name. Values may be given as
+    /// `--flag value` or `--flag=value`.
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self> {
+        let mut parsed = Args::default();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            let (flag, inline) = match arg.split_once('=') {
+                Some((flag, value)) => (flag.to_string(), Some(value.to_string())),
+                None => (arg, None),
+            };
+
+            let slot = match flag.as_str() {
+                "-h" | "--help" => {
+                    parsed.help = true;
+                    continue;
+                }
+                "-V" | "--version" => {
+                    parsed.version = true;
+                    continue;
+                }
+                "--weather-url" => &mut parsed.endpoints.weather,
+                "--geocoding-url" => &mut parsed.endpoints.geocoding,
+                "--air-quality-url" => &mut parsed.endpoints.air_quality,
+                "--marine-url" => &mut parsed.endpoints.marine,
+                "--archive-url" => &mut parsed.endpoints.archive,
+                "--ensemble-url" => &mut parsed.endpoints.ensemble,
+                "--ip-url" => &mut parsed.endpoints.ip_geolocation,
+                "--ipwhois-url" => &mut parsed.endpoints.ipwhois,
+                "--ipinfo-url" => &mut parsed.endpoints.ipinfo,
+                "--nws-url" => &mut parsed.endpoints.nws,
+                "--met-norway-url" => &mut parsed.endpoints.met_norway,
+                "--openweathermap-url" => &mut parsed.endpoints.openweathermap,
+                "--nominatim-url" => &mut parsed.endpoints.nominatim,
+                _ => return Err(anyhow!("Unknown argument: {}", flag)),
+            };
+
+            let value = inline
+                .or_else(|| args.next())
+                .ok_or_else(|| anyhow!("{} requires a URL", flag))?;
+            *slot = Some(value);
+        }
+
+        Ok(parsed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Args> {
+        Args::parse(args.iter().map(|a| a.to_string()))
+    }
+
+    #[test]
+    fn test_no_args() {
+        let args = parse(&[]).unwrap();
+        assert!(!args.help);
+        assert!(!args.version);
+        assert!(args.endpoints.is_empty());
+    }
+
+    #[test]
+    fn test_version_and_help() {
+        assert!(parse(&["-V"]).unwrap().version);
+        assert!(parse(&["--help"]).unwrap().help);
+    }
+
+    #[test]
+    fn test_endpoint_flags() {
+        let args = parse(&[
+            "--weather-url",
+            "http://localhost:8080/v1/forecast",
+            "--geocoding-url=http://localhost:8080/v1/search",
+        ])
+        .unwrap();
+        assert_eq!(
+            args.endpoints.weather.as_deref(),
+            Some("http://localhost:8080/v1/forecast")
+        );
+        assert_eq!(
+            args.endpoints.geocoding.as_deref(),
+            Some("http://localhost:8080/v1/search")
+        );
+        assert!(args.endpoints.ip_geolocation.is_none());
+    }
+
+    #[test]
+    fn test_invalid_args() {
+        assert!(parse(&["--bogus"]).is_err());
+        assert!(parse(&["--ip-url"]).is_err());
+    }
+}
@@ -3,12 +3,34 @@ use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
 
+use crate::api::air_quality::AIR_QUALITY_API_URL;
+use crate::api::ensemble::ENSEMBLE_API_URL;
+use crate::api::geocoding::GEOCODING_API_URL;
+use crate::api::geolocation::{IPINFO_API_URL, IPWHOIS_API_URL, IP_API_URL};
+use crate::api::history::ARCHIVE_API_URL;
+use crate::api::marine::MARINE_API_URL;
+use crate::api::metno::METNO_API_URL;
+use crate::api::nws::NWS_API_URL;
+use crate::api::openweathermap::OWM_API_URL;
+use crate::api::reverse_geocoding::NOMINATIM_API_URL;
+use crate::api::weather::WEATHER_API_URL;
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Config {
     #[serde(default)]
     pub location: LocationConfig,
     #[serde(default)]
     pub units: UnitsConfig,
+    #[serde(default)]
+    pub provider: ProviderConfig,
+    #[serde(default)]
+    pub geolocation: GeolocationConfig,
+    #[serde(default)]
+    pub display: DisplayConfig,
+    #[serde(default)]
+    pub alerts: AlertsConfig,
+    #[serde(default, skip_serializing_if = "EndpointsConfig::is_empty")]
+    pub endpoints: EndpointsConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -17,6 +39,365 @@ pub struct LocationConfig {
     pub latitude: Option<f64>,
     pub longitude: Option<f64>,
     pub city: Option<String>,
+    pub region: Option<String>,
+    pub country: Option<String>,
+    /// IANA timezone name, e.g. "America/Chicago"
+    pub timezone: Option<String>,
+    /// ISO 3166-1 alpha-2 code that zip code and city searches are limited
+    /// to unless the search names a country, e.g. "US"
+    pub country_code: Option<String>,
+    /// How city, country and timezone are filled in when only coordinates
+    /// are configured
+    #[serde(default)]
+    pub reverse_geocoder: ReverseGeocoderKind,
+}
+
+impl LocationConfig {
+    /// Whether a place is set, as opposed to relying on IP geolocation
+    pub fn has_place(&self) -> bool {
+        self.zipcode.is_some() || (self.latitude.is_some() && self.longitude.is_some())
+    }
+
+    /// Places picked before the full place was saved have a zipcode and
+    /// coordinates but no country or timezone. Their zipcode is looked up
+    /// again rather than reverse geocoding the coordinates.
+    pub fn is_incomplete_zipcode(&self) -> bool {
+        self.zipcode.is_some() && (self.country.is_none() || self.timezone.is_none())
+    }
+
+    /// Forget the configured place while keeping search and lookup preferences
+    pub fn clear_place(&mut self) {
+        *self = LocationConfig {
+            country_code: self.country_code.take(),
+            reverse_geocoder: self.reverse_geocoder,
+            ..Default::default()
+        };
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ReverseGeocoderKind {
+    /// OpenStreetMap Nominatim, falling back to the built-in city table
+    /// when it can't be reached. Sends the coordinates to OpenStreetMap.
+    Nominatim,
+    /// Only the built-in city table; never makes a request
+    #[default]
+    Offline,
+}
+
+/// Selects which backend supplies forecast data
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderConfig {
+    #[serde(default)]
+    pub name: ProviderKind,
+    /// Contact details (email or URL) appended to the User-Agent for
+    /// services that ask clients to identify themselves
+    pub contact: Option<String>,
+    /// API key for services that require one. OpenWeatherMap falls back to
+    /// the `OPENWEATHERMAP_API_KEY` environment variable when unset.
+    pub api_key: Option<String>,
+    /// Open-Meteo weather model, e.g. `ecmwf_ifs025` or `gfs_seamless`.
+    /// Unset uses Open-Meteo's best match for the location.
+    pub model: Option<String>,
+    /// Models shown side by side in the comparison view
+    #[serde(default = "default_compare_models")]
+    pub compare_models: Vec<String>,
+    /// Days of daily forecast, 1-16. Providers other than Open-Meteo stop
+    /// at what they publish, 7-10 days. Read as any integer so an out of
+    /// range value is clamped rather than failing the whole config.
+    #[serde(default = "default_forecast_days")]
+    pub forecast_days: i64,
+}
+
+impl Default for ProviderConfig {
+    fn default() -> Self {
+        Self {
+            name: ProviderKind::default(),
+            contact: None,
+            api_key: None,
+            model: None,
+            compare_models: default_compare_models(),
+            forecast_days: default_forecast_days(),
+        }
+    }
+}
+
+impl ProviderConfig {
+    /// The configured horizon brought into the supported range
+    pub fn forecast_days(&self) -> usize {
+        self.forecast_days.clamp(1, MAX_FORECAST_DAYS) as usize
+    }
+}
+
+/// Longest forecast Open-Meteo offers
+pub const MAX_FORECAST_DAYS: i64 = 16;
+
+fn default_forecast_days() -> i64 {
+    5
+}
+
+fn default_compare_models() -> Vec<String> {
+    [
+        "ecmwf_ifs025",
+        "gfs_seamless",
+        "icon_seamless",
+        "gem_seamless",
+    ]
+    .map(String::from)
+    .to_vec()
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ProviderKind {
+    #[default]
+    OpenMeteo,
+    /// US National Weather Service (api.weather.gov)
+    Nws,
+    /// MET Norway Locationforecast (api.met.no)
+    MetNorway,
+    /// OpenWeatherMap One Call 3.0 (api.openweathermap.org)
+    #[serde(rename = "openweathermap")]
+    OpenWeatherMap,
+}
+
+/// Optional parts of the dashboard
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DisplayConfig {
+    /// Show sea conditions: a marine panel in place of today's chart and
+    /// the waves column in the hourly forecast. Toggled with `m`.
+    #[serde(default)]
+    pub marine: bool,
+    /// Columns of the hourly forecast after the date and time, in order
+    #[serde(default = "default_hourly_columns")]
+    pub hourly_columns: Vec<HourlyColumn>,
+}
+
+impl Default for DisplayConfig {
+    fn default() -> Self {
+        Self {
+            marine: false,
+            hourly_columns: default_hourly_columns(),
+        }
+    }
+}
+
+fn default_hourly_columns() -> Vec<HourlyColumn> {
+    vec![
+        HourlyColumn::Temperature,
+        HourlyColumn::FeelsLike,
+        HourlyColumn::Conditions,
+        HourlyColumn::Wind,
+        HourlyColumn::Precipitation,
+        HourlyColumn::Waves,
+    ]
+}
+
+/// A column that can be shown in the hourly forecast
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum HourlyColumn {
+    Temperature,
+    FeelsLike,
+    /// Weather icon
+    Conditions,
+    Wind,
+    WindDirection,
+    Gusts,
+    /// Chance and amount
+    Precipitation,
+    Snowfall,
+    Humidity,
+    DewPoint,
+    CloudCover,
+    Visibility,
+    UvIndex,
+    /// US air quality index
+    Aqi,
+    /// Wave height and period; only shown while marine mode is on
+    Waves,
+}
+
+/// Where severe weather alerts come from
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertsConfig {
+    /// Check api.weather.gov when the location is in the US
+    #[serde(default = "default_true")]
+    pub nws: bool,
+    /// CAP alert documents or Atom feeds of them to check, e.g. a
+    /// MeteoAlarm country feed
+    #[serde(default)]
+    pub cap_feeds: Vec<String>,
+    /// Zone codes for alerts that name their areas instead of outlining
+    /// them, as `scheme:code` (e.g. `EMMA_ID:NL007`) or just the code
+    #[serde(default)]
+    pub zones: Vec<String>,
+}
+
+impl Default for AlertsConfig {
+    fn default() -> Self {
+        Self {
+            nws: true,
+            cap_feeds: Vec::new(),
+            zones: Vec::new(),
+        }
+    }
+}
+
+/// Controls locating this machine by its public IP when no place is set
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GeolocationConfig {
+    /// When false, no IP lookup is ever made and wxman asks for a location
+    /// on first run instead
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Services to try, in order, until one answers
+    #[serde(default = "default_ip_providers")]
+    pub providers: Vec<IpProviderKind>,
+}
+
+impl Default for GeolocationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            providers: default_ip_providers(),
+        }
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_ip_providers() -> Vec<IpProviderKind> {
+    vec![
+        IpProviderKind::Ipapi,
+        IpProviderKind::Ipwhois,
+        IpProviderKind::Ipinfo,
+    ]
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum IpProviderKind {
+    /// ipapi.co
+    Ipapi,
+    /// ipwho.is
+    Ipwhois,
+    /// ipinfo.io
+    Ipinfo,
+}
+
+/// Overrides for the URL each API call is made against, e.g. to point at a
+/// self-hosted Open-Meteo instance or a local mock server. Unset entries
+/// use the public service.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct EndpointsConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub weather: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub geocoding: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub air_quality: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub marine: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub archive: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ensemble: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ip_geolocation: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ipwhois: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ipinfo: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nws: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub met_norway: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub openweathermap: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nominatim: Option<String>,
+}
+
+impl EndpointsConfig {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Layer `overrides` (e.g. from the command line) on top of these endpoints
+    pub fn overridden_by(&self, overrides: &EndpointsConfig) -> EndpointsConfig {
+        let pick = |over: &Option<String>, base: &Option<String>| over.clone().or(base.clone());
+        EndpointsConfig {
+            weather: pick(&overrides.weather, &self.weather),
+            geocoding: pick(&overrides.geocoding, &self.geocoding),
+            air_quality: pick(&overrides.air_quality, &self.air_quality),
+            marine: pick(&overrides.marine, &self.marine),
+            archive: pick(&overrides.archive, &self.archive),
+            ensemble: pick(&overrides.ensemble, &self.ensemble),
+            ip_geolocation: pick(&overrides.ip_geolocation, &self.ip_geolocation),
+            ipwhois: pick(&overrides.ipwhois, &self.ipwhois),
+            ipinfo: pick(&overrides.ipinfo, &self.ipinfo),
+            nws: pick(&overrides.nws, &self.nws),
+            met_norway: pick(&overrides.met_norway, &self.met_norway),
+            openweathermap: pick(&overrides.openweathermap, &self.openweathermap),
+            nominatim: pick(&overrides.nominatim, &self.nominatim),
+        }
+    }
+
+    pub fn weather(&self) -> &str {
+        self.weather.as_deref().unwrap_or(WEATHER_API_URL)
+    }
+
+    pub fn geocoding(&self) -> &str {
+        self.geocoding.as_deref().unwrap_or(GEOCODING_API_URL)
+    }
+
+    pub fn air_quality(&self) -> &str {
+        self.air_quality.as_deref().unwrap_or(AIR_QUALITY_API_URL)
+    }
+
+    pub fn marine(&self) -> &str {
+        self.marine.as_deref().unwrap_or(MARINE_API_URL)
+    }
+
+    pub fn archive(&self) -> &str {
+        self.archive.as_deref().unwrap_or(ARCHIVE_API_URL)
+    }
+
+    pub fn ensemble(&self) -> &str {
+        self.ensemble.as_deref().unwrap_or(ENSEMBLE_API_URL)
+    }
+
+    pub fn ip_geolocation(&self) -> &str {
+        self.ip_geolocation.as_deref().unwrap_or(IP_API_URL)
+    }
+
+    pub fn ipwhois(&self) -> &str {
+        self.ipwhois.as_deref().unwrap_or(IPWHOIS_API_URL)
+    }
+
+    pub fn ipinfo(&self) -> &str {
+        self.ipinfo.as_deref().unwrap_or(IPINFO_API_URL)
+    }
+
+    pub fn nws(&self) -> &str {
+        self.nws.as_deref().unwrap_or(NWS_API_URL)
+    }
+
+    pub fn met_norway(&self) -> &str {
+        self.met_norway.as_deref().unwrap_or(METNO_API_URL)
+    }
+
+    pub fn openweathermap(&self) -> &str {
+        self.openweathermap.as_deref().unwrap_or(OWM_API_URL)
+    }
+
+    pub fn nominatim(&self) -> &str {
+        self.nominatim.as_deref().unwrap_or(NOMINATIM_API_URL)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -449,6 +830,11 @@ mod tests {
                     latitude: Some(34.0901),
                     longitude: Some(-118.4065),
                     city: Some("Beverly Hills".to_string()),
+                    region: Some("California".to_string()),
+                    country: Some("United States".to_string()),
+                    timezone: Some("America/Los_Angeles".to_string()),
+                    country_code: None,
+                    reverse_geocoder: ReverseGeocoderKind::Offline,
                 },
                 units: UnitsConfig {
                     temperature: TemperatureUnit::Celsius,
@@ -456,10 +842,163 @@ mod tests {
                     precipitation: PrecipitationUnit::Cm,
                     pressure: PressureUnit::Hpa,
                 },
+                provider: ProviderConfig::default(),
+                geolocation: GeolocationConfig::default(),
+                display: DisplayConfig::default(),
+                endpoints: EndpointsConfig::default(),
+                alerts: AlertsConfig::default(),
             };
             let toml_str = toml::to_string(&config).unwrap();
             assert!(toml_str.contains("zipcode = \"90210\""));
             assert!(toml_str.contains("temperature = \"celsius\""));
+            assert!(toml_str.contains("name = \"open-meteo\""));
+            assert!(toml_str.contains("reverse_geocoder = \"offline\""));
+            // Unset endpoints are left out of the saved file
+            assert!(!toml_str.contains("[endpoints]"));
+        }
+
+        #[test]
+        fn test_endpoints() {
+            let toml_str = r#"
+                [endpoints]
+                weather = "http://mirror.internal:8080/v1/forecast"
+            "#;
+            let config: Config = toml::from_str(toml_str).unwrap();
+            assert_eq!(
+                config.endpoints.weather(),
+                "http://mirror.internal:8080/v1/forecast"
+            );
+            assert_eq!(config.endpoints.geocoding(), GEOCODING_API_URL);
+
+            let cli = EndpointsConfig {
+                weather: Some("http://127.0.0.1:9000/forecast".to_string()),
+                geocoding: Some("http://127.0.0.1:9000/search".to_string()),
+                ..Default::default()
+            };
+            let resolved = config.endpoints.overridden_by(&cli);
+            assert_eq!(resolved.weather(), "http://127.0.0.1:9000/forecast");
+            assert_eq!(resolved.geocoding(), "http://127.0.0.1:9000/search");
+            assert_eq!(resolved.ip_geolocation(), IP_API_URL);
+        }
+
+        #[test]
+        fn test_default_provider() {
+            let config: Config = toml::from_str("").unwrap();
+            assert_eq!(config.provider.name, ProviderKind::OpenMeteo);
+            assert_eq!(config.provider.model, None);
+            assert_eq!(config.provider.compare_models.len(), 4);
+            assert_eq!(config.provider.forecast_days(), 5);
+            assert_eq!(
+                config.location.reverse_geocoder,
+                ReverseGeocoderKind::Offline
+            );
+        }
+
+        #[test]
+        fn test_forecast_days_clamped() {
+            let config: Config = toml::from_str("[provider]\nforecast_days = 30").unwrap();
+            assert_eq!(config.provider.forecast_days(), 16);
+            let config: Config = toml::from_str("[provider]\nforecast_days = 0").unwrap();
+            assert_eq!(config.provider.forecast_days(), 1);
+            // Past what a u8 holds, or negative
+            let config: Config = toml::from_str("[provider]\nforecast_days = 300").unwrap();
+            assert_eq!(config.provider.forecast_days(), 16);
+            let config: Config = toml::from_str("[provider]\nforecast_days = -1").unwrap();
+            assert_eq!(config.provider.forecast_days(), 1);
+        }
+
+        #[test]
+        fn test_hourly_columns() {
+            let config: Config = toml::from_str("").unwrap();
+            assert_eq!(config.display.hourly_columns.len(), 6);
+            assert_eq!(config.display.hourly_columns[0], HourlyColumn::Temperature);
+            assert_eq!(config.display.hourly_columns[5], HourlyColumn::Waves);
+
+            let toml_str = r#"
+                [display]
+                hourly_columns = ["temperature", "dew_point", "gusts", "uv_index", "aqi"]
+            "#;
+            let config: Config = toml::from_str(toml_str).unwrap();
+            assert_eq!(
+                config.display.hourly_columns,
+                vec![
+                    HourlyColumn::Temperature,
+                    HourlyColumn::DewPoint,
+                    HourlyColumn::Gusts,
+                    HourlyColumn::UvIndex,
+                    HourlyColumn::Aqi
+                ]
+            );
+        }
+
+        #[test]
+        fn test_incomplete_zipcode() {
+            // As saved by older versions when a zip code was entered
+            let toml_str = r#"
+                [location]
+                zipcode = "80202"
+                latitude = 39.7525
+                longitude = -104.9995
+                city = "Denver"
+            "#;
+            let mut config: Config = toml::from_str(toml_str).unwrap();
+            assert!(config.location.is_incomplete_zipcode());
+
+            config.location.country = Some("United States".to_string());
+            config.location.timezone = Some("America/Denver".to_string());
+            assert!(!config.location.is_incomplete_zipcode());
+
+            // Coordinates alone are completed by reverse geocoding instead
+            config.location.zipcode = None;
+            config.location.timezone = None;
+            assert!(!config.location.is_incomplete_zipcode());
+        }
+
+        #[test]
+        fn test_geolocation() {
+            let config: Config = toml::from_str("").unwrap();
+            assert!(config.geolocation.enabled);
+            assert_eq!(config.geolocation.providers[0], IpProviderKind::Ipapi);
+
+            let toml_str = r#"
+                [geolocation]
+                enabled = false
+                providers = ["ipinfo", "ipwhois"]
+            "#;
+            let config: Config = toml::from_str(toml_str).unwrap();
+            assert!(!config.geolocation.enabled);
+            assert_eq!(
+                config.geolocation.providers,
+                vec![IpProviderKind::Ipinfo, IpProviderKind::Ipwhois]
+            );
+        }
+
+        #[test]
+        fn test_deserialize_provider() {
+            let toml_str = r#"
+                [provider]
+                name = "nws"
+            "#;
+            let config: Config = toml::from_str(toml_str).unwrap();
+            assert_eq!(config.provider.name, ProviderKind::Nws);
+
+            let toml_str = r#"
+                [provider]
+                name = "met-norway"
+                contact = "me@example.com"
+            "#;
+            let config: Config = toml::from_str(toml_str).unwrap();
+            assert_eq!(config.provider.name, ProviderKind::MetNorway);
+            assert_eq!(config.provider.contact, Some("me@example.com".to_string()));
+
+            let toml_str = r#"
+                [provider]
+                name = "openweathermap"
+                api_key = "abc123"
+            "#;
+            let config: Config = toml::from_str(toml_str).unwrap();
+            assert_eq!(config.provider.name, ProviderKind::OpenWeatherMap);
+            assert_eq!(config.provider.api_key, Some("abc123".to_string()));
         }
 
         #[test]
@@ -1,5 +1,7 @@
 mod api;
 mod app;
+mod cache;
+mod cli;
 mod config;
 mod models;
 mod ui;
@@ -10,27 +12,45 @@ use std::time::Duration;
 
 use anyhow::Result;
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind},
+    event::{
+        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind, MouseEvent,
+        MouseEventKind,
+    },
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
-use ratatui::{backend::CrosstermBackend, Terminal};
+use ratatui::{backend::CrosstermBackend, layout::Position, Terminal};
 use tokio::time::Instant;
 
 use app::App;
+use cli::Args;
 use config::Config;
 
 const REFRESH_INTERVAL: Duration = Duration::from_secs(15 * 60); // 15 minutes
+const RETRY_BASE_DELAY: Duration = Duration::from_secs(30);
 const TICK_RATE: Duration = Duration::from_millis(250);
 const VERSION: &str = env!("CARGO_PKG_VERSION");
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    if env::args().any(|arg| arg == "--version" || arg == "-V") {
+    let args = match Args::parse(env::args().skip(1)) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("Error: {}\n\n{}", e, cli::USAGE);
+            std::process::exit(2);
+        }
+    };
+
+    if args.version {
         println!("wxman {VERSION}");
         return Ok(());
     }
 
+    if args.help {
+        println!("{}", cli::USAGE);
+        return Ok(());
+    }
+
     // Load configuration
     let config = Config::load().unwrap_or_else(|e| {
         eprintln!("Warning: Failed to load config: {}. Using defaults.", e);
@@ -45,7 +65,7 @@ async fn main() -> Result<()> {
     let mut terminal = Terminal::new(backend)?;
 
     // Create app
-    let mut app = App::new(config);
+    let mut app = App::new(config, &args.endpoints);
 
     // Run the app
     let result = run_app(&mut terminal, &mut app).await;
@@ -70,15 +90,20 @@ async fn run_app<B: ratatui::backend::Backend>(
     terminal: &mut Terminal<B>,
     app: &mut App,
 ) -> Result<()> {
-    // Initial weather load
-    if let Err(e) = app.load_weather().await {
-        app.set_error(e.to_string());
+    // Show the last cached forecast straight away and refresh behind it
+    app.load_cached();
+    if app.needs_location() {
+        // IP geolocation is off and nothing is configured yet: ask first
+        app.open_location_input();
     }
+    app.start_refresh();
 
     let mut last_refresh = Instant::now();
     let mut last_tick = Instant::now();
 
     loop {
+        app.poll_tasks();
+
         // Draw
         terminal.draw(|frame| ui::render(frame, app))?;
 
@@ -88,7 +113,11 @@ async fn run_app<B: ratatui::backend::Backend>(
             .unwrap_or_else(|| Duration::from_secs(0));
 
         if event::poll(timeout)? {
-            if let Event::Key(key) = event::read()? {
+            let event = event::read()?;
+            if let Event::Mouse(mouse) = event {
+                handle_mouse(app, mouse);
+            }
+            if let Event::Key(key) = event {
                 // Only handle key press events (not release)
                 if key.kind == KeyEventKind::Press {
                     // If help is showing, any key closes it
@@ -97,6 +126,45 @@ async fn run_app<B: ratatui::backend::Backend>(
                         continue;
                     }
 
+                    // The plume overlay closes with e, Esc or q
+                    if app.show_plume {
+                        if matches!(
+                            key.code,
+                            KeyCode::Char('e') | KeyCode::Esc | KeyCode::Char('q')
+                        ) {
+                            app.toggle_plume();
+                        } else if key.code == KeyCode::Char('r') {
+                            app.start_refresh();
+                            last_refresh = Instant::now();
+                        }
+                        continue;
+                    }
+
+                    if app.show_alerts {
+                        match key.code {
+                            KeyCode::Char('a') | KeyCode::Esc | KeyCode::Char('q') => {
+                                app.toggle_alerts();
+                            }
+                            KeyCode::Up | KeyCode::Char('k') => app.scroll_alerts(false),
+                            KeyCode::Down | KeyCode::Char('j') => app.scroll_alerts(true),
+                            _ => {}
+                        }
+                        continue;
+                    }
+
+                    if app.show_comparison {
+                        match key.code {
+                            KeyCode::Char('c') | KeyCode::Esc | KeyCode::Char('q') => {
+                                app.toggle_comparison();
+                            }
+                            KeyCode::Up | KeyCode::Char('k') => app.scroll_comparison(false),
+                            KeyCode::Down | KeyCode::Char('j') => app.scroll_comparison(true),
+                            KeyCode::Char('r') => app.start_comparison(),
+                            _ => {}
+                        }
+                        continue;
+                    }
+
                     // If units menu is showing, handle its navigation
                     if app.show_units_menu {
                         match key.code {
@@ -124,26 +192,30 @@ async fn run_app<B: ratatui::backend::Backend>(
                     // If location input is showing, handle text input
                     if app.show_location_input {
                         match key.code {
+                            // First Esc cancels a lookup in progress
+                            KeyCode::Esc if app.is_looking_up_location() => {
+                                app.cancel_location_lookup();
+                            }
+                            // Then it backs out of the list of matches
+                            KeyCode::Esc if app.has_location_results() => {
+                                app.clear_location_results();
+                            }
                             KeyCode::Esc => {
                                 app.close_location_input();
                             }
+                            KeyCode::Up => {
+                                app.location_selection_up();
+                            }
+                            KeyCode::Down => {
+                                app.location_selection_down();
+                            }
                             KeyCode::Enter => {
-                                match app.submit_location().await {
-                                    Ok(true) => {
-                                        // Location changed, reload weather
-                                        if let Err(e) = app.load_weather().await {
-                                            app.set_error(e.to_string());
-                                        }
-                                        last_refresh = Instant::now();
-                                    }
-                                    Ok(false) => {
-                                        // Error shown in popup, don't close
-                                    }
-                                    Err(e) => {
-                                        app.set_error(e.to_string());
-                                        app.close_location_input();
-                                    }
+                                // Weather reloads once a location is chosen
+                                if let Err(e) = app.submit_location() {
+                                    app.set_error(e.to_string());
+                                    app.close_location_input();
                                 }
+                                last_refresh = Instant::now();
                             }
                             KeyCode::Backspace => {
                                 app.location_input_backspace();
@@ -157,13 +229,15 @@ async fn run_app<B: ratatui::backend::Backend>(
                     }
 
                     match key.code {
+                        // Esc cancels a refresh in progress before it quits
+                        KeyCode::Esc if app.is_refreshing() => {
+                            app.cancel_refresh();
+                        }
                         KeyCode::Char('q') | KeyCode::Esc => {
                             app.should_quit = true;
                         }
                         KeyCode::Char('r') => {
-                            if let Err(e) = app.load_weather().await {
-                                app.set_error(e.to_string());
-                            }
+                            app.start_refresh();
                             last_refresh = Instant::now();
                         }
                         KeyCode::Char('u') => {
@@ -172,12 +246,33 @@ async fn run_app<B: ratatui::backend::Backend>(
                         KeyCode::Char('l') => {
                             app.open_location_input();
                         }
+                        KeyCode::Char('m') => {
+                            app.toggle_marine();
+                        }
+                        KeyCode::Char('e') => {
+                            app.toggle_plume();
+                        }
+                        KeyCode::Char('c') => {
+                            app.toggle_comparison();
+                        }
+                        KeyCode::Char('a') => {
+                            app.toggle_alerts();
+                        }
                         KeyCode::Up | KeyCode::Char('k') => {
                             app.scroll_hourly_up();
                         }
                         KeyCode::Down | KeyCode::Char('j') => {
                             app.scroll_hourly_down();
                         }
+                        KeyCode::Left => {
+                            app.scroll_daily(false);
+                        }
+                        KeyCode::Right => {
+                            app.scroll_daily(true);
+                        }
+                        KeyCode::Home => {
+                            app.daily_scroll = 0;
+                        }
                         KeyCode::Char('?') => {
                             app.toggle_help();
                         }
@@ -188,6 +283,7 @@ async fn run_app<B: ratatui::backend::Backend>(
         }
 
         if last_tick.elapsed() >= TICK_RATE {
+            app.on_tick();
             last_tick = Instant::now();
         }
 
@@ -197,11 +293,39 @@ async fn run_app<B: ratatui::backend::Backend>(
         }
 
         // Auto-refresh
-        if last_refresh.elapsed() >= REFRESH_INTERVAL {
-            if let Err(e) = app.load_weather().await {
-                app.set_error(e.to_string());
-            }
+        if !app.is_refreshing() && last_refresh.elapsed() >= refresh_delay(app.refresh_failures) {
+            app.start_refresh();
             last_refresh = Instant::now();
         }
     }
 }
+
+/// The mouse wheel scrolls the daily strip while the pointer is over it
+fn handle_mouse(app: &mut App, mouse: MouseEvent) {
+    let overlay = app.show_help
+        || app.show_plume
+        || app.show_comparison
+        || app.show_alerts
+        || app.show_units_menu
+        || app.show_location_input;
+    let position = Position::new(mouse.column, mouse.row);
+    if overlay || !app.daily_area.get().contains(position) {
+        return;
+    }
+    match mouse.kind {
+        MouseEventKind::ScrollDown | MouseEventKind::ScrollRight => app.scroll_daily(true),
+        MouseEventKind::ScrollUp | MouseEventKind::ScrollLeft => app.scroll_daily(false),
+        _ => {}
+    }
+}
+
+/// Time until the next automatic refresh: the normal interval, or a backoff
+/// starting at 30 seconds and doubling after each consecutive failure
+fn refresh_delay(failures: u32) -> Duration {
+    match failures {
+        0 => REFRESH_INTERVAL,
+        n => RETRY_BASE_DELAY
+            .saturating_mul(2u32.saturating_pow(n - 1))
+            .min(REFRESH_INTERVAL),
+    }
+}
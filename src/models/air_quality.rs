@@ -0,0 +1,304 @@
+use serde::{Deserialize, Serialize};
+
+/// Air quality from the Open-Meteo air-quality API. Pollutant
+/// concentrations are in µg/m³. Every value is optional because coverage
+/// varies by region and model.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AirQualityData {
+    pub current: AirQualityCurrent,
+    pub hourly: Vec<AirQualityHourly>,
+    /// Daily pollen outlook, today first. Empty outside the regions the
+    /// pollen model covers (currently Europe).
+    #[serde(default)]
+    pub pollen: Vec<PollenDay>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AirQualityCurrent {
+    pub us_aqi: Option<f64>,
+    pub european_aqi: Option<f64>,
+    pub pm2_5: Option<f64>,
+    pub pm10: Option<f64>,
+    pub ozone: Option<f64>,
+    pub nitrogen_dioxide: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AirQualityHourly {
+    pub time: String,
+    pub us_aqi: Option<f64>,
+    pub european_aqi: Option<f64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PollenType {
+    Grass,
+    Birch,
+    Alder,
+    Ragweed,
+    Olive,
+    Mugwort,
+}
+
+impl PollenType {
+    pub const ALL: [PollenType; 6] = [
+        PollenType::Grass,
+        PollenType::Birch,
+        PollenType::Alder,
+        PollenType::Ragweed,
+        PollenType::Olive,
+        PollenType::Mugwort,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Grass => "Grass",
+            Self::Birch => "Birch",
+            Self::Alder => "Alder",
+            Self::Ragweed => "Ragweed",
+            Self::Olive => "Olive",
+            Self::Mugwort => "Mugwort",
+        }
+    }
+
+    /// Allergy severity for a pollen count in grains/m³, using the National
+    /// Allergy Bureau bands for trees, grasses and weeds
+    pub fn level(&self, grains: f64) -> PollenLevel {
+        let [low, moderate, high, very_high] = match self {
+            Self::Alder | Self::Birch | Self::Olive => [1.0, 15.0, 90.0, 1500.0],
+            Self::Grass => [1.0, 5.0, 20.0, 200.0],
+            Self::Ragweed | Self::Mugwort => [1.0, 10.0, 50.0, 500.0],
+        };
+
+        if grains >= very_high {
+            PollenLevel::VeryHigh
+        } else if grains >= high {
+            PollenLevel::High
+        } else if grains >= moderate {
+            PollenLevel::Moderate
+        } else if grains >= low {
+            PollenLevel::Low
+        } else {
+            PollenLevel::None
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PollenLevel {
+    None,
+    Low,
+    Moderate,
+    High,
+    VeryHigh,
+}
+
+/// Peak pollen counts for one day
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PollenDay {
+    pub date: String,
+    /// Highest hourly count per species in grains/m³, for the species the
+    /// model reports at this location
+    pub peaks: Vec<(PollenType, f64)>,
+}
+
+impl PollenDay {
+    pub fn peak(&self, species: PollenType) -> Option<f64> {
+        self.peaks
+            .iter()
+            .find(|(s, _)| *s == species)
+            .map(|(_, grains)| *grains)
+    }
+}
+
+impl AirQualityData {
+    /// US AQI for the hour starting at `time` ("%Y-%m-%dT%H:%M")
+    pub fn us_aqi_at(&self, time: &str) -> Option<f64> {
+        self.hourly
+            .iter()
+            .find(|h| h.time == time)
+            .and_then(|h| h.us_aqi)
+    }
+}
+
+/// Raw API response from the Open-Meteo air-quality API
+#[derive(Debug, Clone, Deserialize)]
+pub struct OpenMeteoAirQualityResponse {
+    pub current: OpenMeteoAirQualityCurrent,
+    pub hourly: OpenMeteoAirQualityHourly,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct OpenMeteoAirQualityCurrent {
+    pub us_aqi: Option<f64>,
+    pub european_aqi: Option<f64>,
+    pub pm2_5: Option<f64>,
+    pub pm10: Option<f64>,
+    pub ozone: Option<f64>,
+    pub nitrogen_dioxide: Option<f64>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct OpenMeteoAirQualityHourly {
+    pub time: Vec<String>,
+    pub us_aqi: Vec<Option<f64>>,
+    pub european_aqi: Vec<Option<f64>>,
+    #[serde(default)]
+    pub grass_pollen: Vec<Option<f64>>,
+    #[serde(default)]
+    pub birch_pollen: Vec<Option<f64>>,
+    #[serde(default)]
+    pub alder_pollen: Vec<Option<f64>>,
+    #[serde(default)]
+    pub ragweed_pollen: Vec<Option<f64>>,
+    #[serde(default)]
+    pub olive_pollen: Vec<Option<f64>>,
+    #[serde(default)]
+    pub mugwort_pollen: Vec<Option<f64>>,
+}
+
+impl OpenMeteoAirQualityHourly {
+    fn pollen(&self, species: PollenType) -> &[Option<f64>] {
+        match species {
+            PollenType::Grass => &self.grass_pollen,
+            PollenType::Birch => &self.birch_pollen,
+            PollenType::Alder => &self.alder_pollen,
+            PollenType::Ragweed => &self.ragweed_pollen,
+            PollenType::Olive => &self.olive_pollen,
+            PollenType::Mugwort => &self.mugwort_pollen,
+        }
+    }
+
+    /// Daily peaks per species, dropping days without any pollen data
+    fn pollen_days(&self) -> Vec<PollenDay> {
+        let mut days: Vec<PollenDay> = Vec::new();
+        for (i, time) in self.time.iter().enumerate() {
+            let date = time.get(..10).unwrap_or(time);
+            if days.last().is_none_or(|d| d.date != date) {
+                days.push(PollenDay {
+                    date: date.to_string(),
+                    peaks: Vec::new(),
+                });
+            }
+            let day = days.last_mut().expect("a day was just pushed");
+
+            for species in PollenType::ALL {
+                let Some(grains) = self.pollen(species).get(i).copied().flatten() else {
+                    continue;
+                };
+                match day.peaks.iter_mut().find(|(s, _)| *s == species) {
+                    Some((_, peak)) => *peak = peak.max(grains),
+                    None => day.peaks.push((species, grains)),
+                }
+            }
+        }
+
+        days.retain(|d| !d.peaks.is_empty());
+        days
+    }
+}
+
+impl From<OpenMeteoAirQualityResponse> for AirQualityData {
+    fn from(resp: OpenMeteoAirQualityResponse) -> Self {
+        let current = AirQualityCurrent {
+            us_aqi: resp.current.us_aqi,
+            european_aqi: resp.current.european_aqi,
+            pm2_5: resp.current.pm2_5,
+            pm10: resp.current.pm10,
+            ozone: resp.current.ozone,
+            nitrogen_dioxide: resp.current.nitrogen_dioxide,
+        };
+
+        let pollen = resp.hourly.pollen_days();
+        let hourly = resp
+            .hourly
+            .time
+            .iter()
+            .enumerate()
+            .map(|(i, time)| AirQualityHourly {
+                time: time.clone(),
+                us_aqi: resp.hourly.us_aqi.get(i).copied().flatten(),
+                european_aqi: resp.hourly.european_aqi.get(i).copied().flatten(),
+            })
+            .collect();
+
+        Self {
+            current,
+            hourly,
+            pollen,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hourly(
+        time: &[&str],
+        grass: Vec<Option<f64>>,
+        birch: Vec<Option<f64>>,
+    ) -> OpenMeteoAirQualityHourly {
+        OpenMeteoAirQualityHourly {
+            time: time.iter().map(|t| t.to_string()).collect(),
+            us_aqi: vec![],
+            european_aqi: vec![],
+            grass_pollen: grass,
+            birch_pollen: birch,
+            alder_pollen: vec![],
+            ragweed_pollen: vec![],
+            olive_pollen: vec![],
+            mugwort_pollen: vec![],
+        }
+    }
+
+    #[test]
+    fn test_pollen_days_take_daily_peaks() {
+        let hourly = hourly(
+            &[
+                "2024-05-01T00:00",
+                "2024-05-01T12:00",
+                "2024-05-02T00:00",
+                "2024-05-02T12:00",
+                "2024-05-03T00:00",
+            ],
+            vec![Some(2.0), Some(31.5), Some(8.0), Some(4.0), None],
+            vec![Some(120.0), Some(40.0), None, None, None],
+        );
+
+        let days = hourly.pollen_days();
+        // The last day has no data and is dropped
+        assert_eq!(days.len(), 2);
+        assert_eq!(days[0].date, "2024-05-01");
+        assert_eq!(days[0].peak(PollenType::Grass), Some(31.5));
+        assert_eq!(days[0].peak(PollenType::Birch), Some(120.0));
+        assert_eq!(days[1].peak(PollenType::Grass), Some(8.0));
+        assert_eq!(days[1].peak(PollenType::Birch), None);
+        assert_eq!(days[1].peak(PollenType::Ragweed), None);
+    }
+
+    #[test]
+    fn test_no_pollen_outside_coverage() {
+        let hourly = hourly(
+            &["2024-05-01T00:00", "2024-05-01T01:00"],
+            vec![None, None],
+            vec![],
+        );
+        assert!(hourly.pollen_days().is_empty());
+    }
+
+    #[test]
+    fn test_pollen_levels() {
+        assert_eq!(PollenType::Grass.level(0.0), PollenLevel::None);
+        assert_eq!(PollenType::Grass.level(3.0), PollenLevel::Low);
+        assert_eq!(PollenType::Grass.level(12.0), PollenLevel::Moderate);
+        assert_eq!(PollenType::Grass.level(150.0), PollenLevel::High);
+        assert_eq!(PollenType::Grass.level(250.0), PollenLevel::VeryHigh);
+
+        // The same count is milder for trees than for grasses
+        assert_eq!(PollenType::Birch.level(50.0), PollenLevel::Moderate);
+        assert_eq!(PollenType::Birch.level(2000.0), PollenLevel::VeryHigh);
+        assert_eq!(PollenType::Ragweed.level(60.0), PollenLevel::High);
+    }
+}
@@ -0,0 +1,312 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, FixedOffset, Utc};
+use serde::Deserialize;
+
+/// How much harm an alert's hazard can do, least to most
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Unknown,
+    Minor,
+    Moderate,
+    Severe,
+    Extreme,
+}
+
+impl Severity {
+    pub fn parse(value: &str) -> Self {
+        match value.trim() {
+            "Extreme" => Severity::Extreme,
+            "Severe" => Severity::Severe,
+            "Moderate" => Severity::Moderate,
+            "Minor" => Severity::Minor,
+            _ => Severity::Unknown,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Severity::Extreme => "Extreme",
+            Severity::Severe => "Severe",
+            Severity::Moderate => "Moderate",
+            Severity::Minor => "Minor",
+            Severity::Unknown => "Unknown",
+        }
+    }
+}
+
+/// How soon action should be taken, least to most pressing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Urgency {
+    Unknown,
+    Past,
+    Future,
+    Expected,
+    Immediate,
+}
+
+impl Urgency {
+    pub fn parse(value: &str) -> Self {
+        match value.trim() {
+            "Immediate" => Urgency::Immediate,
+            "Expected" => Urgency::Expected,
+            "Future" => Urgency::Future,
+            "Past" => Urgency::Past,
+            _ => Urgency::Unknown,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Urgency::Immediate => "Immediate",
+            Urgency::Expected => "Expected",
+            Urgency::Future => "Future",
+            Urgency::Past => "Past",
+            Urgency::Unknown => "Unknown",
+        }
+    }
+}
+
+/// A weather warning, watch or advisory from a Common Alerting Protocol
+/// source, reduced to what's shown and what's needed to match a location
+#[derive(Debug, Clone)]
+pub struct Alert {
+    pub id: String,
+    pub event: String,
+    pub headline: Option<String>,
+    pub description: Option<String>,
+    pub instruction: Option<String>,
+    pub sender: Option<String>,
+    pub severity: Severity,
+    pub urgency: Urgency,
+    pub certainty: String,
+    pub effective: Option<DateTime<FixedOffset>>,
+    pub onset: Option<DateTime<FixedOffset>>,
+    pub expires: Option<DateTime<FixedOffset>>,
+    /// Human-readable list of the areas covered
+    pub area: String,
+    /// Rings of (latitude, longitude) points
+    pub polygons: Vec<Vec<(f64, f64)>>,
+    /// Centre latitude, longitude and radius in km
+    pub circles: Vec<(f64, f64, f64)>,
+    /// Area codes as (scheme, code), e.g. ("UGC", "COZ039") or
+    /// ("EMMA_ID", "NL009")
+    pub geocodes: Vec<(String, String)>,
+}
+
+impl Alert {
+    /// Still in force at `now`. Alerts without an expiry stay until the
+    /// source drops them.
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        self.expires.is_none_or(|expires| expires > now)
+    }
+
+    /// Whether the alert covers the point, either inside one of its shapes
+    /// or through a configured zone code. Zones are written `scheme:code`,
+    /// or just the code to match any scheme.
+    pub fn covers(&self, latitude: f64, longitude: f64, zones: &[String]) -> bool {
+        let in_shape =
+            self.polygons
+                .iter()
+                .any(|ring| point_in_polygon(latitude, longitude, ring))
+                || self.circles.iter().any(|&(lat, lon, radius)| {
+                    distance_km(latitude, longitude, lat, lon) <= radius
+                });
+
+        in_shape
+            || zones.iter().any(|zone| {
+                let (scheme, code) = match zone.split_once(':') {
+                    Some((scheme, code)) => (Some(scheme), code),
+                    None => (None, zone.as_str()),
+                };
+                self.geocodes.iter().any(|(name, value)| {
+                    value.eq_ignore_ascii_case(code.trim())
+                        && scheme.is_none_or(|s| name.eq_ignore_ascii_case(s.trim()))
+                })
+            })
+    }
+}
+
+/// Alerts for a location from every configured source, most severe first
+#[derive(Debug, Clone, Default)]
+pub struct Alerts {
+    pub alerts: Vec<Alert>,
+    /// Sources that couldn't be checked, so missing alerts aren't mistaken
+    /// for all-clear
+    pub failed_sources: Vec<String>,
+}
+
+impl Alerts {
+    pub fn active(&self, now: DateTime<Utc>) -> Vec<&Alert> {
+        self.alerts.iter().filter(|a| a.is_active(now)).collect()
+    }
+}
+
+/// Ray casting; good enough for warning polygons, which never straddle the
+/// antimeridian or a pole
+fn point_in_polygon(latitude: f64, longitude: f64, ring: &[(f64, f64)]) -> bool {
+    let mut inside = false;
+    let mut j = ring.len().wrapping_sub(1);
+    for i in 0..ring.len() {
+        let (lat_i, lon_i) = ring[i];
+        let (lat_j, lon_j) = ring[j];
+        if (lat_i > latitude) != (lat_j > latitude)
+            && longitude < (lon_j - lon_i) * (latitude - lat_i) / (lat_j - lat_i) + lon_i
+        {
+            inside = !inside;
+        }
+        j = i;
+    }
+    inside
+}
+
+/// Great-circle distance
+fn distance_km(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64 {
+    let (dlat, dlon) = ((lat2 - lat1).to_radians(), (lon2 - lon1).to_radians());
+    let a = (dlat / 2.0).sin().powi(2)
+        + lat1.to_radians().cos() * lat2.to_radians().cos() * (dlon / 2.0).sin().powi(2);
+    6371.0 * 2.0 * a.sqrt().asin()
+}
+
+/// Parse a CAP or NWS timestamp, e.g. "2024-06-01T14:00:00-04:00"
+pub fn parse_time(value: &str) -> Option<DateTime<FixedOffset>> {
+    DateTime::parse_from_rfc3339(value.trim()).ok()
+}
+
+/// Active alerts from api.weather.gov as JSON-LD. The service has already
+/// matched them to the requested point.
+#[derive(Debug, Clone, Deserialize)]
+pub struct NwsAlertsResponse {
+    #[serde(rename = "@graph", default)]
+    pub graph: Vec<NwsAlert>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NwsAlert {
+    pub id: String,
+    pub area_desc: String,
+    #[serde(default)]
+    pub geocode: HashMap<String, Vec<String>>,
+    pub effective: Option<String>,
+    pub onset: Option<String>,
+    pub expires: Option<String>,
+    /// When the hazard itself ends, which can be after the message expires
+    pub ends: Option<String>,
+    pub status: String,
+    pub message_type: String,
+    pub severity: String,
+    pub certainty: String,
+    pub urgency: String,
+    pub event: String,
+    pub sender_name: Option<String>,
+    pub headline: Option<String>,
+    pub description: Option<String>,
+    pub instruction: Option<String>,
+}
+
+impl NwsAlertsResponse {
+    /// Real alerts only; tests, exercises and cancellations are dropped
+    pub fn into_alerts(self) -> Vec<Alert> {
+        self.graph
+            .into_iter()
+            .filter(|a| a.status == "Actual" && a.message_type != "Cancel")
+            .map(Alert::from)
+            .collect()
+    }
+}
+
+impl From<NwsAlert> for Alert {
+    fn from(alert: NwsAlert) -> Self {
+        let time = |value: &Option<String>| value.as_deref().and_then(parse_time);
+        let mut geocodes: Vec<(String, String)> = alert
+            .geocode
+            .into_iter()
+            .flat_map(|(scheme, codes)| codes.into_iter().map(move |c| (scheme.clone(), c)))
+            .collect();
+        geocodes.sort();
+
+        Self {
+            id: alert.id,
+            event: alert.event,
+            headline: alert.headline,
+            description: alert.description,
+            instruction: alert.instruction,
+            sender: alert.sender_name,
+            severity: Severity::parse(&alert.severity),
+            urgency: Urgency::parse(&alert.urgency),
+            certainty: alert.certainty,
+            effective: time(&alert.effective),
+            onset: time(&alert.onset),
+            expires: time(&alert.ends).or(time(&alert.expires)),
+            area: alert.area_desc,
+            polygons: Vec::new(),
+            circles: Vec::new(),
+            geocodes,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn alert() -> Alert {
+        Alert {
+            id: "test".to_string(),
+            event: "Flood Warning".to_string(),
+            headline: None,
+            description: None,
+            instruction: None,
+            sender: None,
+            severity: Severity::Severe,
+            urgency: Urgency::Expected,
+            certainty: "Likely".to_string(),
+            effective: None,
+            onset: None,
+            expires: parse_time("2024-06-01T20:00:00-04:00"),
+            area: "Test County".to_string(),
+            // A square around Denver
+            polygons: vec![vec![
+                (39.5, -105.2),
+                (39.5, -104.8),
+                (39.9, -104.8),
+                (39.9, -105.2),
+                (39.5, -105.2),
+            ]],
+            circles: vec![(40.0, -100.0, 25.0)],
+            geocodes: vec![("UGC".to_string(), "COZ039".to_string())],
+        }
+    }
+
+    #[test]
+    fn test_covers() {
+        let alert = alert();
+        assert!(alert.covers(39.74, -104.99, &[]));
+        assert!(!alert.covers(40.5, -104.99, &[]));
+        // 11 km from the circle's centre
+        assert!(alert.covers(40.1, -100.0, &[]));
+        assert!(!alert.covers(40.5, -100.0, &[]));
+
+        assert!(alert.covers(45.0, -90.0, &["UGC:COZ039".to_string()]));
+        assert!(alert.covers(45.0, -90.0, &["coz039".to_string()]));
+        assert!(!alert.covers(45.0, -90.0, &["SAME:COZ039".to_string()]));
+    }
+
+    #[test]
+    fn test_is_active() {
+        let alert = alert();
+        let before = parse_time("2024-06-01T19:59:00-04:00").unwrap();
+        assert!(alert.is_active(before.with_timezone(&Utc)));
+        let after = parse_time("2024-06-02T00:01:00Z").unwrap();
+        assert!(!alert.is_active(after.with_timezone(&Utc)));
+    }
+
+    #[test]
+    fn test_severity_order() {
+        assert!(Severity::Extreme > Severity::Severe);
+        assert!(Severity::Minor > Severity::Unknown);
+        assert_eq!(Severity::parse("Moderate"), Severity::Moderate);
+        assert_eq!(Urgency::parse("Immediate"), Urgency::Immediate);
+    }
+}
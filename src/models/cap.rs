@@ -0,0 +1,289 @@
+use anyhow::{bail, Context, Result};
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+use crate::models::alerts::{parse_time, Alert, Severity, Urgency};
+
+/// Parse a Common Alerting Protocol document: a single `<alert>`, or an Atom
+/// feed of alerts like MeteoAlarm's, whose entries either embed the full
+/// alert or carry its main fields as `cap:` elements. Tests, exercises and
+/// cancellations are left out.
+pub fn parse_cap(xml: &str) -> Result<Vec<Alert>> {
+    let root = parse_tree(xml).context("Failed to parse CAP document")?;
+    match root.name.as_str() {
+        "alert" => Ok(alert_from_cap(&root).into_iter().collect()),
+        "feed" => Ok(root
+            .children("entry")
+            .filter_map(|entry| match entry.find("alert") {
+                Some(alert) => alert_from_cap(alert),
+                None => alert_from_atom_entry(entry),
+            })
+            .collect()),
+        other => bail!("Expected a CAP alert or Atom feed, found <{}>", other),
+    }
+}
+
+/// An element with namespace prefixes dropped, so `cap:event` and `event`
+/// read the same
+#[derive(Debug, Default)]
+struct Element {
+    name: String,
+    text: String,
+    children: Vec<Element>,
+}
+
+impl Element {
+    fn children<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Element> + 'a {
+        self.children.iter().filter(move |c| c.name == name)
+    }
+
+    fn child(&self, name: &str) -> Option<&Element> {
+        self.children.iter().find(|c| c.name == name)
+    }
+
+    /// Trimmed text of the first child with this name, if not blank
+    fn text_of(&self, name: &str) -> Option<String> {
+        let text = self.child(name)?.text.trim();
+        (!text.is_empty()).then(|| text.to_string())
+    }
+
+    /// The first element with this name at any depth
+    fn find(&self, name: &str) -> Option<&Element> {
+        self.children.iter().find_map(|c| {
+            if c.name == name {
+                Some(c)
+            } else {
+                c.find(name)
+            }
+        })
+    }
+}
+
+fn parse_tree(xml: &str) -> Result<Element> {
+    let mut reader = Reader::from_str(xml);
+    // Stack of open elements; the bottom one collects the document root
+    let mut stack = vec![Element::default()];
+
+    loop {
+        match reader.read_event()? {
+            Event::Start(start) => stack.push(Element {
+                name: String::from_utf8_lossy(start.local_name().as_ref()).into_owned(),
+                ..Default::default()
+            }),
+            Event::Empty(empty) => {
+                let element = Element {
+                    name: String::from_utf8_lossy(empty.local_name().as_ref()).into_owned(),
+                    ..Default::default()
+                };
+                if let Some(parent) = stack.last_mut() {
+                    parent.children.push(element);
+                }
+            }
+            Event::End(_) => {
+                let element = stack.pop().context("Unbalanced CAP document")?;
+                stack
+                    .last_mut()
+                    .context("Unbalanced CAP document")?
+                    .children
+                    .push(element);
+            }
+            Event::Text(text) => {
+                if let Some(element) = stack.last_mut() {
+                    element.text.push_str(&text.unescape()?);
+                }
+            }
+            Event::CData(data) => {
+                if let Some(element) = stack.last_mut() {
+                    element
+                        .text
+                        .push_str(&String::from_utf8_lossy(&data.into_inner()));
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    stack
+        .pop()
+        .and_then(|document| document.children.into_iter().next())
+        .context("Empty CAP document")
+}
+
+/// Only real alerts are shown
+fn is_actual(status: Option<String>, message_type: Option<String>) -> bool {
+    status.is_none_or(|s| s == "Actual") && message_type.is_none_or(|t| t != "Cancel")
+}
+
+fn alert_from_cap(alert: &Element) -> Option<Alert> {
+    if !is_actual(alert.text_of("status"), alert.text_of("msgType")) {
+        return None;
+    }
+
+    // Alerts can repeat their info block in several languages
+    let infos: Vec<&Element> = alert.children("info").collect();
+    let info = infos
+        .iter()
+        .find(|i| i.text_of("language").is_none_or(|l| l.starts_with("en")))
+        .or(infos.first())?;
+
+    let mut polygons = Vec::new();
+    let mut circles = Vec::new();
+    let mut geocodes = Vec::new();
+    for area in info.children("area") {
+        polygons.extend(
+            area.children("polygon")
+                .filter_map(|p| parse_polygon(&p.text)),
+        );
+        circles.extend(
+            area.children("circle")
+                .filter_map(|c| parse_circle(&c.text)),
+        );
+        geocodes.extend(area.children("geocode").filter_map(parse_geocode));
+    }
+    let area = info
+        .children("area")
+        .filter_map(|a| a.text_of("areaDesc"))
+        .collect::<Vec<_>>()
+        .join("; ");
+
+    let time = |name: &str| info.text_of(name).as_deref().and_then(parse_time);
+    Some(Alert {
+        id: alert.text_of("identifier").unwrap_or_default(),
+        event: info.text_of("event").unwrap_or_else(|| "Alert".to_string()),
+        headline: info.text_of("headline"),
+        description: info.text_of("description"),
+        instruction: info.text_of("instruction"),
+        sender: info.text_of("senderName").or(alert.text_of("sender")),
+        severity: Severity::parse(&info.text_of("severity").unwrap_or_default()),
+        urgency: Urgency::parse(&info.text_of("urgency").unwrap_or_default()),
+        certainty: info.text_of("certainty").unwrap_or_default(),
+        effective: time("effective").or(alert.text_of("sent").as_deref().and_then(parse_time)),
+        onset: time("onset"),
+        expires: time("expires"),
+        area,
+        polygons,
+        circles,
+        geocodes,
+    })
+}
+
+fn alert_from_atom_entry(entry: &Element) -> Option<Alert> {
+    if !is_actual(entry.text_of("status"), entry.text_of("msgType")) {
+        return None;
+    }
+    let event = entry.text_of("event")?;
+
+    let time = |name: &str| entry.text_of(name).as_deref().and_then(parse_time);
+    Some(Alert {
+        id: entry
+            .text_of("identifier")
+            .or(entry.text_of("id"))
+            .unwrap_or_default(),
+        event,
+        headline: entry.text_of("title"),
+        description: entry.text_of("summary"),
+        instruction: None,
+        sender: entry.child("author").and_then(|a| a.text_of("name")),
+        severity: Severity::parse(&entry.text_of("severity").unwrap_or_default()),
+        urgency: Urgency::parse(&entry.text_of("urgency").unwrap_or_default()),
+        certainty: entry.text_of("certainty").unwrap_or_default(),
+        effective: time("effective"),
+        onset: time("onset"),
+        expires: time("expires"),
+        area: entry.text_of("areaDesc").unwrap_or_default(),
+        polygons: entry
+            .children("polygon")
+            .filter_map(|p| parse_polygon(&p.text))
+            .collect(),
+        circles: entry
+            .children("circle")
+            .filter_map(|c| parse_circle(&c.text))
+            .collect(),
+        geocodes: entry
+            .children("geocode")
+            .filter_map(parse_geocode)
+            .collect(),
+    })
+}
+
+/// "lat,lon lat,lon ..." with at least a triangle
+fn parse_polygon(text: &str) -> Option<Vec<(f64, f64)>> {
+    let points: Option<Vec<(f64, f64)>> = text.split_whitespace().map(parse_point).collect();
+    points.filter(|p| p.len() >= 3)
+}
+
+/// "lat,lon radius" with the radius in km
+fn parse_circle(text: &str) -> Option<(f64, f64, f64)> {
+    let (centre, radius) = text.trim().split_once(' ')?;
+    let (lat, lon) = parse_point(centre)?;
+    Some((lat, lon, radius.trim().parse().ok()?))
+}
+
+fn parse_point(text: &str) -> Option<(f64, f64)> {
+    let (lat, lon) = text.split_once(',')?;
+    Some((lat.trim().parse().ok()?, lon.trim().parse().ok()?))
+}
+
+fn parse_geocode(geocode: &Element) -> Option<(String, String)> {
+    Some((geocode.text_of("valueName")?, geocode.text_of("value")?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WARNING: &str = include_str!("../../tests/fixtures/alerts/cap_warning.xml");
+    const FEED: &str = include_str!("../../tests/fixtures/alerts/meteoalarm_feed.xml");
+
+    #[test]
+    fn test_parse_cap_alert() {
+        let alerts = parse_cap(WARNING).unwrap();
+        assert_eq!(alerts.len(), 1);
+
+        let alert = &alerts[0];
+        assert_eq!(alert.event, "Severe Thunderstorm Warning");
+        assert_eq!(alert.severity, Severity::Severe);
+        assert_eq!(alert.urgency, Urgency::Immediate);
+        assert_eq!(alert.certainty, "Observed");
+        assert_eq!(alert.sender.as_deref(), Some("NWS Boulder CO"));
+        assert!(alert
+            .instruction
+            .as_deref()
+            .unwrap()
+            .starts_with("For your protection"));
+        assert_eq!(alert.expires, parse_time("2024-06-01T17:45:00-06:00"));
+        assert_eq!(alert.polygons.len(), 1);
+        assert!(alert
+            .geocodes
+            .contains(&("UGC".to_string(), "COZ039".to_string())));
+
+        // Denver is inside the warned area, Boulder isn't
+        assert!(alert.covers(39.74, -104.99, &[]));
+        assert!(!alert.covers(40.01, -105.27, &[]));
+    }
+
+    #[test]
+    fn test_parse_meteoalarm_feed() {
+        let alerts = parse_cap(FEED).unwrap();
+
+        // The cancelled entry and the exercise are skipped
+        assert_eq!(alerts.len(), 2);
+        let wind = &alerts[0];
+        assert_eq!(wind.event, "Moderate wind warning");
+        assert_eq!(wind.severity, Severity::Moderate);
+        assert_eq!(wind.area, "Noord-Holland");
+        assert!(wind.polygons.is_empty());
+
+        // No shapes, so only a configured zone places it
+        assert!(!wind.covers(52.37, 4.90, &[]));
+        assert!(wind.covers(52.37, 4.90, &["EMMA_ID:NL007".to_string()]));
+        assert!(!alerts[1].covers(52.37, 4.90, &["EMMA_ID:NL007".to_string()]));
+    }
+
+    #[test]
+    fn test_rejects_other_documents() {
+        assert!(parse_cap("<html><body>Not found</body></html>").is_err());
+        assert!(parse_cap("not xml <").is_err());
+    }
+}
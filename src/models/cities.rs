@@ -0,0 +1,295 @@
+//! Small built-in gazetteer used to name coordinate-only locations and pick
+//! their timezone without a network request.
+
+pub struct City {
+    pub name: &'static str,
+    pub region: Option<&'static str>,
+    pub country: &'static str,
+    pub latitude: f64,
+    pub longitude: f64,
+    /// IANA timezone name
+    pub timezone: &'static str,
+}
+
+const fn city(
+    name: &'static str,
+    region: Option<&'static str>,
+    country: &'static str,
+    latitude: f64,
+    longitude: f64,
+    timezone: &'static str,
+) -> City {
+    City {
+        name,
+        region,
+        country,
+        latitude,
+        longitude,
+        timezone,
+    }
+}
+
+const US: &str = "United States";
+const CA: &str = "Canada";
+const AU: &str = "Australia";
+const BR: &str = "Brazil";
+
+#[rustfmt::skip]
+pub static CITIES: &[City] = &[
+    // United States
+    city("New York", Some("New York"), US, 40.7128, -74.0060, "America/New_York"),
+    city("Boston", Some("Massachusetts"), US, 42.3601, -71.0589, "America/New_York"),
+    city("Philadelphia", Some("Pennsylvania"), US, 39.9526, -75.1652, "America/New_York"),
+    city("Pittsburgh", Some("Pennsylvania"), US, 40.4406, -79.9959, "America/New_York"),
+    city("Washington", Some("District of Columbia"), US, 38.9072, -77.0369, "America/New_York"),
+    city("Baltimore", Some("Maryland"), US, 39.2904, -76.6122, "America/New_York"),
+    city("Richmond", Some("Virginia"), US, 37.5407, -77.4360, "America/New_York"),
+    city("Charlotte", Some("North Carolina"), US, 35.2271, -80.8431, "America/New_York"),
+    city("Raleigh", Some("North Carolina"), US, 35.7796, -78.6382, "America/New_York"),
+    city("Atlanta", Some("Georgia"), US, 33.7490, -84.3880, "America/New_York"),
+    city("Jacksonville", Some("Florida"), US, 30.3322, -81.6557, "America/New_York"),
+    city("Orlando", Some("Florida"), US, 28.5384, -81.3789, "America/New_York"),
+    city("Miami", Some("Florida"), US, 25.7617, -80.1918, "America/New_York"),
+    city("Tampa", Some("Florida"), US, 27.9506, -82.4572, "America/New_York"),
+    city("Detroit", Some("Michigan"), US, 42.3314, -83.0458, "America/Detroit"),
+    city("Cleveland", Some("Ohio"), US, 41.4993, -81.6944, "America/New_York"),
+    city("Columbus", Some("Ohio"), US, 39.9612, -82.9988, "America/New_York"),
+    city("Indianapolis", Some("Indiana"), US, 39.7684, -86.1581, "America/Indiana/Indianapolis"),
+    city("Louisville", Some("Kentucky"), US, 38.2527, -85.7585, "America/Kentucky/Louisville"),
+    city("Nashville", Some("Tennessee"), US, 36.1627, -86.7816, "America/Chicago"),
+    city("Memphis", Some("Tennessee"), US, 35.1495, -90.0490, "America/Chicago"),
+    city("Birmingham", Some("Alabama"), US, 33.5186, -86.8104, "America/Chicago"),
+    city("New Orleans", Some("Louisiana"), US, 29.9511, -90.0715, "America/Chicago"),
+    city("Chicago", Some("Illinois"), US, 41.8781, -87.6298, "America/Chicago"),
+    city("Milwaukee", Some("Wisconsin"), US, 43.0389, -87.9065, "America/Chicago"),
+    city("Minneapolis", Some("Minnesota"), US, 44.9778, -93.2650, "America/Chicago"),
+    city("St. Louis", Some("Missouri"), US, 38.6270, -90.1994, "America/Chicago"),
+    city("Kansas City", Some("Missouri"), US, 39.0997, -94.5786, "America/Chicago"),
+    city("Omaha", Some("Nebraska"), US, 41.2565, -95.9345, "America/Chicago"),
+    city("Oklahoma City", Some("Oklahoma"), US, 35.4676, -97.5164, "America/Chicago"),
+    city("Dallas", Some("Texas"), US, 32.7767, -96.7970, "America/Chicago"),
+    city("Houston", Some("Texas"), US, 29.7604, -95.3698, "America/Chicago"),
+    city("San Antonio", Some("Texas"), US, 29.4241, -98.4936, "America/Chicago"),
+    city("Austin", Some("Texas"), US, 30.2672, -97.7431, "America/Chicago"),
+    city("El Paso", Some("Texas"), US, 31.7619, -106.4850, "America/Denver"),
+    city("Fargo", Some("North Dakota"), US, 46.8772, -96.7898, "America/Chicago"),
+    city("Denver", Some("Colorado"), US, 39.7392, -104.9903, "America/Denver"),
+    city("Albuquerque", Some("New Mexico"), US, 35.0844, -106.6504, "America/Denver"),
+    city("Salt Lake City", Some("Utah"), US, 40.7608, -111.8910, "America/Denver"),
+    city("Boise", Some("Idaho"), US, 43.6150, -116.2023, "America/Boise"),
+    city("Billings", Some("Montana"), US, 45.7833, -108.5007, "America/Denver"),
+    city("Phoenix", Some("Arizona"), US, 33.4484, -112.0740, "America/Phoenix"),
+    city("Las Vegas", Some("Nevada"), US, 36.1699, -115.1398, "America/Los_Angeles"),
+    city("Los Angeles", Some("California"), US, 34.0522, -118.2437, "America/Los_Angeles"),
+    city("San Diego", Some("California"), US, 32.7157, -117.1611, "America/Los_Angeles"),
+    city("San Francisco", Some("California"), US, 37.7749, -122.4194, "America/Los_Angeles"),
+    city("Sacramento", Some("California"), US, 38.5816, -121.4944, "America/Los_Angeles"),
+    city("Portland", Some("Oregon"), US, 45.5152, -122.6784, "America/Los_Angeles"),
+    city("Seattle", Some("Washington"), US, 47.6062, -122.3321, "America/Los_Angeles"),
+    city("Spokane", Some("Washington"), US, 47.6588, -117.4260, "America/Los_Angeles"),
+    city("Anchorage", Some("Alaska"), US, 61.2181, -149.9003, "America/Anchorage"),
+    city("Fairbanks", Some("Alaska"), US, 64.8378, -147.7164, "America/Anchorage"),
+    city("Honolulu", Some("Hawaii"), US, 21.3069, -157.8583, "Pacific/Honolulu"),
+    city("San Juan", Some("Puerto Rico"), US, 18.4655, -66.1057, "America/Puerto_Rico"),
+    // Canada
+    city("Toronto", Some("Ontario"), CA, 43.6532, -79.3832, "America/Toronto"),
+    city("Ottawa", Some("Ontario"), CA, 45.4215, -75.6972, "America/Toronto"),
+    city("Montreal", Some("Quebec"), CA, 45.5017, -73.5673, "America/Toronto"),
+    city("Halifax", Some("Nova Scotia"), CA, 44.6488, -63.5752, "America/Halifax"),
+    city("St. John's", Some("Newfoundland and Labrador"), CA, 47.5615, -52.7126, "America/St_Johns"),
+    city("Winnipeg", Some("Manitoba"), CA, 49.8951, -97.1384, "America/Winnipeg"),
+    city("Regina", Some("Saskatchewan"), CA, 50.4452, -104.6189, "America/Regina"),
+    city("Calgary", Some("Alberta"), CA, 51.0447, -114.0719, "America/Edmonton"),
+    city("Edmonton", Some("Alberta"), CA, 53.5461, -113.4938, "America/Edmonton"),
+    city("Vancouver", Some("British Columbia"), CA, 49.2827, -123.1207, "America/Vancouver"),
+    city("Whitehorse", Some("Yukon"), CA, 60.7212, -135.0568, "America/Whitehorse"),
+    city("Yellowknife", Some("Northwest Territories"), CA, 62.4540, -114.3718, "America/Yellowknife"),
+    // Latin America
+    city("Mexico City", None, "Mexico", 19.4326, -99.1332, "America/Mexico_City"),
+    city("Guadalajara", None, "Mexico", 20.6597, -103.3496, "America/Mexico_City"),
+    city("Monterrey", None, "Mexico", 25.6866, -100.3161, "America/Monterrey"),
+    city("Tijuana", None, "Mexico", 32.5149, -117.0382, "America/Tijuana"),
+    city("Guatemala City", None, "Guatemala", 14.6349, -90.5069, "America/Guatemala"),
+    city("Havana", None, "Cuba", 23.1136, -82.3666, "America/Havana"),
+    city("Panama City", None, "Panama", 8.9824, -79.5199, "America/Panama"),
+    city("Bogotá", None, "Colombia", 4.7110, -74.0721, "America/Bogota"),
+    city("Caracas", None, "Venezuela", 10.4806, -66.9036, "America/Caracas"),
+    city("Quito", None, "Ecuador", -0.1807, -78.4678, "America/Guayaquil"),
+    city("Lima", None, "Peru", -12.0464, -77.0428, "America/Lima"),
+    city("La Paz", None, "Bolivia", -16.4897, -68.1193, "America/La_Paz"),
+    city("Santiago", None, "Chile", -33.4489, -70.6693, "America/Santiago"),
+    city("Buenos Aires", None, "Argentina", -34.6037, -58.3816, "America/Argentina/Buenos_Aires"),
+    city("Montevideo", None, "Uruguay", -34.9011, -56.1645, "America/Montevideo"),
+    city("São Paulo", Some("São Paulo"), BR, -23.5505, -46.6333, "America/Sao_Paulo"),
+    city("Rio de Janeiro", Some("Rio de Janeiro"), BR, -22.9068, -43.1729, "America/Sao_Paulo"),
+    city("Brasília", Some("Federal District"), BR, -15.7939, -47.8828, "America/Sao_Paulo"),
+    city("Manaus", Some("Amazonas"), BR, -3.1190, -60.0217, "America/Manaus"),
+    city("Recife", Some("Pernambuco"), BR, -8.0476, -34.8770, "America/Recife"),
+    // Europe
+    city("London", Some("England"), "United Kingdom", 51.5074, -0.1278, "Europe/London"),
+    city("Manchester", Some("England"), "United Kingdom", 53.4808, -2.2426, "Europe/London"),
+    city("Edinburgh", Some("Scotland"), "United Kingdom", 55.9533, -3.1883, "Europe/London"),
+    city("Dublin", None, "Ireland", 53.3498, -6.2603, "Europe/Dublin"),
+    city("Reykjavík", None, "Iceland", 64.1466, -21.9426, "Atlantic/Reykjavik"),
+    city("Lisbon", None, "Portugal", 38.7223, -9.1393, "Europe/Lisbon"),
+    city("Madrid", None, "Spain", 40.4168, -3.7038, "Europe/Madrid"),
+    city("Barcelona", Some("Catalonia"), "Spain", 41.3874, 2.1686, "Europe/Madrid"),
+    city("Paris", Some("Île-de-France"), "France", 48.8566, 2.3522, "Europe/Paris"),
+    city("Marseille", None, "France", 43.2965, 5.3698, "Europe/Paris"),
+    city("Brussels", None, "Belgium", 50.8503, 4.3517, "Europe/Brussels"),
+    city("Amsterdam", None, "Netherlands", 52.3676, 4.9041, "Europe/Amsterdam"),
+    city("Berlin", None, "Germany", 52.5200, 13.4050, "Europe/Berlin"),
+    city("Hamburg", None, "Germany", 53.5511, 9.9937, "Europe/Berlin"),
+    city("Munich", Some("Bavaria"), "Germany", 48.1351, 11.5820, "Europe/Berlin"),
+    city("Frankfurt", Some("Hesse"), "Germany", 50.1109, 8.6821, "Europe/Berlin"),
+    city("Zurich", None, "Switzerland", 47.3769, 8.5417, "Europe/Zurich"),
+    city("Vienna", None, "Austria", 48.2082, 16.3738, "Europe/Vienna"),
+    city("Rome", Some("Lazio"), "Italy", 41.9028, 12.4964, "Europe/Rome"),
+    city("Milan", Some("Lombardy"), "Italy", 45.4642, 9.1900, "Europe/Rome"),
+    city("Copenhagen", None, "Denmark", 55.6761, 12.5683, "Europe/Copenhagen"),
+    city("Oslo", None, "Norway", 59.9139, 10.7522, "Europe/Oslo"),
+    city("Bergen", None, "Norway", 60.3913, 5.3221, "Europe/Oslo"),
+    city("Tromsø", None, "Norway", 69.6492, 18.9553, "Europe/Oslo"),
+    city("Stockholm", None, "Sweden", 59.3293, 18.0686, "Europe/Stockholm"),
+    city("Helsinki", None, "Finland", 60.1699, 24.9384, "Europe/Helsinki"),
+    city("Tallinn", None, "Estonia", 59.4370, 24.7536, "Europe/Tallinn"),
+    city("Riga", None, "Latvia", 56.9496, 24.1052, "Europe/Riga"),
+    city("Vilnius", None, "Lithuania", 54.6872, 25.2797, "Europe/Vilnius"),
+    city("Warsaw", None, "Poland", 52.2297, 21.0122, "Europe/Warsaw"),
+    city("Prague", None, "Czechia", 50.0755, 14.4378, "Europe/Prague"),
+    city("Budapest", None, "Hungary", 47.4979, 19.0402, "Europe/Budapest"),
+    city("Belgrade", None, "Serbia", 44.7866, 20.4489, "Europe/Belgrade"),
+    city("Bucharest", None, "Romania", 44.4268, 26.1025, "Europe/Bucharest"),
+    city("Sofia", None, "Bulgaria", 42.6977, 23.3219, "Europe/Sofia"),
+    city("Athens", None, "Greece", 37.9838, 23.7275, "Europe/Athens"),
+    city("Istanbul", None, "Turkey", 41.0082, 28.9784, "Europe/Istanbul"),
+    city("Kyiv", None, "Ukraine", 50.4501, 30.5234, "Europe/Kyiv"),
+    city("Minsk", None, "Belarus", 53.9006, 27.5590, "Europe/Minsk"),
+    city("Moscow", None, "Russia", 55.7558, 37.6173, "Europe/Moscow"),
+    city("Saint Petersburg", None, "Russia", 59.9311, 30.3609, "Europe/Moscow"),
+    // Africa and the Middle East
+    city("Cairo", None, "Egypt", 30.0444, 31.2357, "Africa/Cairo"),
+    city("Casablanca", None, "Morocco", 33.5731, -7.5898, "Africa/Casablanca"),
+    city("Algiers", None, "Algeria", 36.7538, 3.0588, "Africa/Algiers"),
+    city("Tunis", None, "Tunisia", 36.8065, 10.1815, "Africa/Tunis"),
+    city("Dakar", None, "Senegal", 14.7167, -17.4677, "Africa/Dakar"),
+    city("Lagos", None, "Nigeria", 6.5244, 3.3792, "Africa/Lagos"),
+    city("Accra", None, "Ghana", 5.6037, -0.1870, "Africa/Accra"),
+    city("Kinshasa", None, "DR Congo", -4.4419, 15.2663, "Africa/Kinshasa"),
+    city("Addis Ababa", None, "Ethiopia", 9.0300, 38.7400, "Africa/Addis_Ababa"),
+    city("Nairobi", None, "Kenya", -1.2921, 36.8219, "Africa/Nairobi"),
+    city("Dar es Salaam", None, "Tanzania", -6.7924, 39.2083, "Africa/Dar_es_Salaam"),
+    city("Luanda", None, "Angola", -8.8390, 13.2894, "Africa/Luanda"),
+    city("Johannesburg", Some("Gauteng"), "South Africa", -26.2041, 28.0473, "Africa/Johannesburg"),
+    city("Cape Town", Some("Western Cape"), "South Africa", -33.9249, 18.4241, "Africa/Johannesburg"),
+    city("Antananarivo", None, "Madagascar", -18.8792, 47.5079, "Indian/Antananarivo"),
+    city("Tel Aviv", None, "Israel", 32.0853, 34.7818, "Asia/Jerusalem"),
+    city("Amman", None, "Jordan", 31.9454, 35.9284, "Asia/Amman"),
+    city("Riyadh", None, "Saudi Arabia", 24.7136, 46.6753, "Asia/Riyadh"),
+    city("Dubai", None, "United Arab Emirates", 25.2048, 55.2708, "Asia/Dubai"),
+    city("Tehran", None, "Iran", 35.6892, 51.3890, "Asia/Tehran"),
+    city("Baghdad", None, "Iraq", 33.3152, 44.3661, "Asia/Baghdad"),
+    // Asia
+    city("Karachi", None, "Pakistan", 24.8607, 67.0011, "Asia/Karachi"),
+    city("Kabul", None, "Afghanistan", 34.5553, 69.2075, "Asia/Kabul"),
+    city("Tashkent", None, "Uzbekistan", 41.2995, 69.2401, "Asia/Tashkent"),
+    city("Almaty", None, "Kazakhstan", 43.2220, 76.8512, "Asia/Almaty"),
+    city("Delhi", None, "India", 28.7041, 77.1025, "Asia/Kolkata"),
+    city("Mumbai", Some("Maharashtra"), "India", 19.0760, 72.8777, "Asia/Kolkata"),
+    city("Bengaluru", Some("Karnataka"), "India", 12.9716, 77.5946, "Asia/Kolkata"),
+    city("Kolkata", Some("West Bengal"), "India", 22.5726, 88.3639, "Asia/Kolkata"),
+    city("Colombo", None, "Sri Lanka", 6.9271, 79.8612, "Asia/Colombo"),
+    city("Kathmandu", None, "Nepal", 27.7172, 85.3240, "Asia/Kathmandu"),
+    city("Dhaka", None, "Bangladesh", 23.8103, 90.4125, "Asia/Dhaka"),
+    city("Yangon", None, "Myanmar", 16.8409, 96.1735, "Asia/Yangon"),
+    city("Bangkok", None, "Thailand", 13.7563, 100.5018, "Asia/Bangkok"),
+    city("Hanoi", None, "Vietnam", 21.0278, 105.8342, "Asia/Bangkok"),
+    city("Ho Chi Minh City", None, "Vietnam", 10.8231, 106.6297, "Asia/Ho_Chi_Minh"),
+    city("Kuala Lumpur", None, "Malaysia", 3.1390, 101.6869, "Asia/Kuala_Lumpur"),
+    city("Singapore", None, "Singapore", 1.3521, 103.8198, "Asia/Singapore"),
+    city("Jakarta", None, "Indonesia", -6.2088, 106.8456, "Asia/Jakarta"),
+    city("Manila", None, "Philippines", 14.5995, 120.9842, "Asia/Manila"),
+    city("Hong Kong", None, "China", 22.3193, 114.1694, "Asia/Hong_Kong"),
+    city("Guangzhou", Some("Guangdong"), "China", 23.1291, 113.2644, "Asia/Shanghai"),
+    city("Shanghai", None, "China", 31.2304, 121.4737, "Asia/Shanghai"),
+    city("Beijing", None, "China", 39.9042, 116.4074, "Asia/Shanghai"),
+    city("Chengdu", Some("Sichuan"), "China", 30.5728, 104.0668, "Asia/Shanghai"),
+    city("Ürümqi", Some("Xinjiang"), "China", 43.8256, 87.6168, "Asia/Urumqi"),
+    city("Taipei", None, "Taiwan", 25.0330, 121.5654, "Asia/Taipei"),
+    city("Seoul", None, "South Korea", 37.5665, 126.9780, "Asia/Seoul"),
+    city("Ulaanbaatar", None, "Mongolia", 47.8864, 106.9057, "Asia/Ulaanbaatar"),
+    city("Tokyo", None, "Japan", 35.6762, 139.6503, "Asia/Tokyo"),
+    city("Osaka", None, "Japan", 34.6937, 135.5023, "Asia/Tokyo"),
+    city("Sapporo", Some("Hokkaido"), "Japan", 43.0618, 141.3545, "Asia/Tokyo"),
+    city("Novosibirsk", None, "Russia", 55.0084, 82.9357, "Asia/Novosibirsk"),
+    city("Yekaterinburg", None, "Russia", 56.8389, 60.6057, "Asia/Yekaterinburg"),
+    city("Irkutsk", None, "Russia", 52.2870, 104.3050, "Asia/Irkutsk"),
+    city("Vladivostok", None, "Russia", 43.1198, 131.8869, "Asia/Vladivostok"),
+    // Oceania
+    city("Perth", Some("Western Australia"), AU, -31.9505, 115.8605, "Australia/Perth"),
+    city("Darwin", Some("Northern Territory"), AU, -12.4634, 130.8456, "Australia/Darwin"),
+    city("Adelaide", Some("South Australia"), AU, -34.9285, 138.6007, "Australia/Adelaide"),
+    city("Melbourne", Some("Victoria"), AU, -37.8136, 144.9631, "Australia/Melbourne"),
+    city("Hobart", Some("Tasmania"), AU, -42.8821, 147.3272, "Australia/Hobart"),
+    city("Sydney", Some("New South Wales"), AU, -33.8688, 151.2093, "Australia/Sydney"),
+    city("Brisbane", Some("Queensland"), AU, -27.4698, 153.0251, "Australia/Brisbane"),
+    city("Auckland", None, "New Zealand", -36.8485, 174.7633, "Pacific/Auckland"),
+    city("Wellington", None, "New Zealand", -41.2865, 174.7762, "Pacific/Auckland"),
+    city("Suva", None, "Fiji", -18.1248, 178.4501, "Pacific/Fiji"),
+    city("Port Moresby", None, "Papua New Guinea", -9.4438, 147.1803, "Pacific/Port_Moresby"),
+];
+
+/// Great-circle distance in kilometres
+pub fn distance_km(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64 {
+    const EARTH_RADIUS_KM: f64 = 6371.0;
+    let (phi1, phi2) = (lat1.to_radians(), lat2.to_radians());
+    let d_phi = (lat2 - lat1).to_radians();
+    let d_lambda = (lon2 - lon1).to_radians();
+    let a = (d_phi / 2.0).sin().powi(2) + phi1.cos() * phi2.cos() * (d_lambda / 2.0).sin().powi(2);
+    2.0 * EARTH_RADIUS_KM * a.sqrt().asin()
+}
+
+/// The closest city in the table and its distance in kilometres
+pub fn nearest_city(latitude: f64, longitude: f64) -> (&'static City, f64) {
+    CITIES
+        .iter()
+        .map(|c| (c, distance_km(latitude, longitude, c.latitude, c.longitude)))
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .expect("city table is not empty")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono_tz::Tz;
+
+    #[test]
+    fn test_timezones_are_valid() {
+        for city in CITIES {
+            assert!(
+                city.timezone.parse::<Tz>().is_ok(),
+                "{} has unknown timezone {}",
+                city.name,
+                city.timezone
+            );
+        }
+    }
+
+    #[test]
+    fn test_distance() {
+        // New York to London is roughly 5570 km
+        let d = distance_km(40.7128, -74.0060, 51.5074, -0.1278);
+        assert!((d - 5570.0).abs() < 20.0);
+    }
+
+    #[test]
+    fn test_nearest_city() {
+        let (city, distance) = nearest_city(40.73, -73.99);
+        assert_eq!(city.name, "New York");
+        assert!(distance < 5.0);
+
+        let (city, _) = nearest_city(39.55, -105.78);
+        assert_eq!(city.name, "Denver");
+        assert_eq!(city.timezone, "America/Denver");
+    }
+}
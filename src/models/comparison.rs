@@ -0,0 +1,155 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+/// Temperature spread between models (°C) worth flagging
+pub const TEMPERATURE_DISAGREEMENT_C: f64 = 3.0;
+
+/// Hourly precipitation (mm) a model needs to count as calling for rain
+const WET_HOUR_MM: f64 = 0.1;
+
+/// Hourly temperature and precipitation from several weather models for the
+/// same place, in the order the models were requested
+#[derive(Debug, Clone)]
+pub struct ModelComparison {
+    pub models: Vec<String>,
+    pub hourly: Vec<ComparisonHour>,
+}
+
+/// One hour across every model. A value is missing where a model's run
+/// doesn't reach that far or doesn't cover the location.
+#[derive(Debug, Clone)]
+pub struct ComparisonHour {
+    pub time: String,
+    pub temperature: Vec<Option<f64>>,
+    pub precipitation: Vec<Option<f64>>,
+}
+
+impl ComparisonHour {
+    /// Difference between the warmest and coldest model, when at least two
+    /// have a value
+    pub fn temperature_spread(&self) -> Option<f64> {
+        let values: Vec<f64> = self.temperature.iter().flatten().copied().collect();
+        if values.len() < 2 {
+            return None;
+        }
+        let max = values.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        let min = values.iter().cloned().fold(f64::INFINITY, f64::min);
+        Some(max - min)
+    }
+
+    /// True when some models have rain this hour and others have none
+    pub fn precipitation_disagrees(&self) -> bool {
+        let values: Vec<f64> = self.precipitation.iter().flatten().copied().collect();
+        values.iter().any(|&mm| mm >= WET_HOUR_MM) && values.iter().any(|&mm| mm < WET_HOUR_MM)
+    }
+}
+
+/// Short display name for an Open-Meteo model id
+pub fn model_label(model: &str) -> &str {
+    match model {
+        "best_match" => "Best match",
+        "ecmwf_ifs025" | "ecmwf_ifs04" => "ECMWF",
+        "ecmwf_aifs025" | "ecmwf_aifs025_single" => "AIFS",
+        "gfs_seamless" | "gfs_global" | "ncep_gfs025" => "GFS",
+        "icon_seamless" | "icon_global" => "ICON",
+        "gem_seamless" | "gem_global" => "GEM",
+        "meteofrance_seamless" | "meteofrance_arpege_world" => "ARPEGE",
+        "jma_seamless" | "jma_gsm" => "JMA",
+        "ukmo_seamless" | "ukmo_global_deterministic_10km" => "UKMO",
+        "cma_grapes_global" => "CMA",
+        "bom_access_global" => "ACCESS",
+        _ => model,
+    }
+}
+
+/// Raw API response from the Open-Meteo forecast API when several models
+/// are requested. Each variable comes back once per model, suffixed with
+/// the model id (`temperature_2m_gfs_seamless`); with a single model the
+/// suffix is left off.
+#[derive(Debug, Clone, Deserialize)]
+pub struct OpenMeteoComparisonResponse {
+    pub hourly: OpenMeteoComparisonHourly,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct OpenMeteoComparisonHourly {
+    pub time: Vec<String>,
+    #[serde(flatten)]
+    pub series: HashMap<String, Vec<Option<f64>>>,
+}
+
+impl ModelComparison {
+    pub fn from_response(resp: OpenMeteoComparisonResponse, models: &[String]) -> Self {
+        let hourly_resp = resp.hourly;
+        let series = |variable: &str, model: &str| {
+            hourly_resp
+                .series
+                .get(&format!("{}_{}", variable, model))
+                .or_else(|| (models.len() == 1).then(|| hourly_resp.series.get(variable))?)
+        };
+        let temperature: Vec<_> = models.iter().map(|m| series("temperature_2m", m)).collect();
+        let precipitation: Vec<_> = models.iter().map(|m| series("precipitation", m)).collect();
+
+        let at = |series: &[Option<&Vec<Option<f64>>>], i: usize| -> Vec<Option<f64>> {
+            series
+                .iter()
+                .map(|s| s.and_then(|s| s.get(i).copied().flatten()))
+                .collect()
+        };
+
+        let hourly = hourly_resp
+            .time
+            .iter()
+            .enumerate()
+            .map(|(i, time)| ComparisonHour {
+                time: time.clone(),
+                temperature: at(&temperature, i),
+                precipitation: at(&precipitation, i),
+            })
+            .collect();
+
+        Self {
+            models: models.to_vec(),
+            hourly,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hour(temperature: &[Option<f64>], precipitation: &[Option<f64>]) -> ComparisonHour {
+        ComparisonHour {
+            time: "2024-08-10T12:00".to_string(),
+            temperature: temperature.to_vec(),
+            precipitation: precipitation.to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_disagreement() {
+        let agree = hour(&[Some(20.0), Some(21.0), None], &[Some(0.0), Some(0.0)]);
+        assert_eq!(agree.temperature_spread(), Some(1.0));
+        assert!(!agree.precipitation_disagrees());
+
+        let disagree = hour(&[Some(18.0), Some(23.5)], &[Some(1.2), Some(0.0), None]);
+        assert_eq!(disagree.temperature_spread(), Some(5.5));
+        assert!(disagree.precipitation_disagrees());
+
+        let single = hour(&[Some(18.0), None], &[None, None]);
+        assert_eq!(single.temperature_spread(), None);
+        assert!(!single.precipitation_disagrees());
+    }
+
+    #[test]
+    fn test_model_label() {
+        assert_eq!(model_label("ecmwf_ifs025"), "ECMWF");
+        assert_eq!(model_label("gfs_seamless"), "GFS");
+        assert_eq!(
+            model_label("knmi_harmonie_arome_europe"),
+            "knmi_harmonie_arome_europe"
+        );
+    }
+}
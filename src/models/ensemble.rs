@@ -0,0 +1,259 @@
+use std::collections::{BTreeMap, HashMap};
+
+use serde::{Deserialize, Serialize};
+
+/// Daily precipitation (mm) a member needs to count as a wet day
+const WET_DAY_MM: f64 = 1.0;
+
+/// Spread of ensemble members summarised as percentiles, in the same metric
+/// units as the rest of the weather data
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Spread {
+    pub p10: f64,
+    pub p25: f64,
+    pub median: f64,
+    pub p75: f64,
+    pub p90: f64,
+}
+
+/// How far the ensemble members agree on a day
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Confidence {
+    High,
+    Medium,
+    Low,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnsembleHour {
+    pub time: String,
+    pub temperature: Spread,
+    pub precipitation: Spread,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnsembleDay {
+    pub date: String,
+    pub temp_max: Spread,
+    pub temp_min: Spread,
+    pub precipitation: Spread,
+    /// Share of members with a wet day, 0-100
+    pub wet_probability: f64,
+    pub confidence: Confidence,
+}
+
+/// Ensemble forecast summarised from every member's run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnsembleData {
+    pub members: usize,
+    pub hourly: Vec<EnsembleHour>,
+    pub daily: Vec<EnsembleDay>,
+}
+
+impl EnsembleData {
+    pub fn day(&self, date: &str) -> Option<&EnsembleDay> {
+        self.daily.iter().find(|d| d.date == date)
+    }
+}
+
+impl Spread {
+    /// None when there are no values
+    pub fn of(values: &mut [f64]) -> Option<Self> {
+        if values.is_empty() {
+            return None;
+        }
+        values.sort_by(f64::total_cmp);
+        Some(Self {
+            p10: percentile(values, 0.10),
+            p25: percentile(values, 0.25),
+            median: percentile(values, 0.50),
+            p75: percentile(values, 0.75),
+            p90: percentile(values, 0.90),
+        })
+    }
+
+    /// Range covered by the middle 80% of members
+    pub fn width(&self) -> f64 {
+        self.p90 - self.p10
+    }
+}
+
+impl Confidence {
+    /// Members agreeing on the high within 3°C and mostly agreeing on
+    /// whether it rains is high confidence; highs spread over 6°C or a
+    /// coin flip on rain is low
+    pub fn from_spread(temp_max: &Spread, wet_probability: f64) -> Self {
+        let temp_spread = temp_max.width();
+        let rain_split = (35.0..=65.0).contains(&wet_probability);
+        let rain_agreed = wet_probability <= 20.0 || wet_probability >= 80.0;
+
+        if temp_spread >= 6.0 || rain_split {
+            Confidence::Low
+        } else if temp_spread < 3.0 && rain_agreed {
+            Confidence::High
+        } else {
+            Confidence::Medium
+        }
+    }
+}
+
+/// Linear interpolation between the closest ranks of sorted values
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = p * (sorted.len() - 1) as f64;
+    let (lower, upper) = (rank.floor() as usize, rank.ceil() as usize);
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - lower as f64)
+}
+
+/// Raw API response from the Open-Meteo ensemble API. Each member comes as
+/// its own series: `temperature_2m` for the control run, then
+/// `temperature_2m_member01`, `temperature_2m_member02`, ...
+#[derive(Debug, Clone, Deserialize)]
+pub struct OpenMeteoEnsembleResponse {
+    pub hourly: OpenMeteoEnsembleHourly,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct OpenMeteoEnsembleHourly {
+    pub time: Vec<String>,
+    #[serde(flatten)]
+    pub series: HashMap<String, Vec<Option<f64>>>,
+}
+
+impl OpenMeteoEnsembleHourly {
+    /// Every member's series for a variable, control run first
+    fn members(&self, variable: &str) -> Vec<&Vec<Option<f64>>> {
+        let prefix = format!("{}_member", variable);
+        let mut names: Vec<&String> = self
+            .series
+            .keys()
+            .filter(|name| *name == variable || name.starts_with(&prefix))
+            .collect();
+        names.sort();
+        names.iter().map(|name| &self.series[*name]).collect()
+    }
+}
+
+impl From<OpenMeteoEnsembleResponse> for EnsembleData {
+    fn from(resp: OpenMeteoEnsembleResponse) -> Self {
+        let hourly_resp = &resp.hourly;
+        let temperature = hourly_resp.members("temperature_2m");
+        let precipitation = hourly_resp.members("precipitation");
+
+        let at = |series: &[&Vec<Option<f64>>], i: usize| -> Vec<f64> {
+            series
+                .iter()
+                .filter_map(|s| s.get(i).copied().flatten())
+                .collect()
+        };
+
+        let hourly = hourly_resp
+            .time
+            .iter()
+            .enumerate()
+            .filter_map(|(i, time)| {
+                Some(EnsembleHour {
+                    time: time.clone(),
+                    temperature: Spread::of(&mut at(&temperature, i))?,
+                    precipitation: Spread::of(&mut at(&precipitation, i))?,
+                })
+            })
+            .collect();
+
+        // Hour indices for each local date, in order
+        let mut days: BTreeMap<&str, Vec<usize>> = BTreeMap::new();
+        for (i, time) in hourly_resp.time.iter().enumerate() {
+            days.entry(time.get(..10).unwrap_or(time))
+                .or_default()
+                .push(i);
+        }
+
+        let daily = days
+            .into_iter()
+            .filter_map(|(date, hours)| {
+                // A day cut short by the forecast horizon would give
+                // misleading extremes, as would members with gaps
+                if hours.len() < 23 {
+                    return None;
+                }
+                let complete = |series: &Vec<Option<f64>>| -> Option<Vec<f64>> {
+                    hours
+                        .iter()
+                        .map(|&i| series.get(i).copied().flatten())
+                        .collect()
+                };
+                let mut highs = Vec::new();
+                let mut lows = Vec::new();
+                for values in temperature.iter().filter_map(|s| complete(s)) {
+                    highs.push(values.iter().cloned().fold(f64::NEG_INFINITY, f64::max));
+                    lows.push(values.iter().cloned().fold(f64::INFINITY, f64::min));
+                }
+                let mut totals: Vec<f64> = precipitation
+                    .iter()
+                    .filter_map(|s| complete(s))
+                    .map(|values| values.iter().sum())
+                    .collect();
+
+                let wet = totals.iter().filter(|&&mm| mm >= WET_DAY_MM).count();
+                let wet_probability = 100.0 * wet as f64 / totals.len().max(1) as f64;
+                let temp_max = Spread::of(&mut highs)?;
+
+                Some(EnsembleDay {
+                    date: date.to_string(),
+                    temp_max,
+                    temp_min: Spread::of(&mut lows)?,
+                    precipitation: Spread::of(&mut totals)?,
+                    wet_probability,
+                    confidence: Confidence::from_spread(&temp_max, wet_probability),
+                })
+            })
+            .collect();
+
+        Self {
+            members: temperature.len(),
+            hourly,
+            daily,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spread() {
+        let mut values = vec![5.0, 1.0, 3.0, 2.0, 4.0];
+        let spread = Spread::of(&mut values).unwrap();
+        assert_eq!(spread.median, 3.0);
+        assert_eq!(spread.p25, 2.0);
+        assert!((spread.p10 - 1.4).abs() < 1e-9);
+        assert!((spread.p90 - 4.6).abs() < 1e-9);
+        assert!(Spread::of(&mut []).is_none());
+    }
+
+    #[test]
+    fn test_confidence() {
+        let spread = |width: f64| Spread {
+            p10: 20.0,
+            p25: 20.0,
+            median: 20.0,
+            p75: 20.0,
+            p90: 20.0 + width,
+        };
+        assert_eq!(Confidence::from_spread(&spread(1.5), 5.0), Confidence::High);
+        assert_eq!(
+            Confidence::from_spread(&spread(1.5), 90.0),
+            Confidence::High
+        );
+        assert_eq!(
+            Confidence::from_spread(&spread(4.0), 5.0),
+            Confidence::Medium
+        );
+        assert_eq!(
+            Confidence::from_spread(&spread(1.5), 30.0),
+            Confidence::Medium
+        );
+        assert_eq!(Confidence::from_spread(&spread(1.5), 50.0), Confidence::Low);
+        assert_eq!(Confidence::from_spread(&spread(7.0), 0.0), Confidence::Low);
+    }
+}
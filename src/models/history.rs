@@ -0,0 +1,138 @@
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+/// Averages for one calendar date over past years, from the Open-Meteo
+/// archive. Temperatures are in Celsius and precipitation in mm.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DayHistory {
+    /// The day being compared ("%Y-%m-%d")
+    pub date: String,
+    /// Number of past years that had data for the date
+    pub years: usize,
+    pub temp_max: f64,
+    pub temp_min: f64,
+    pub precipitation: f64,
+}
+
+/// Raw API response from the Open-Meteo archive API
+#[derive(Debug, Clone, Deserialize)]
+pub struct OpenMeteoArchiveResponse {
+    pub daily: OpenMeteoArchiveDaily,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct OpenMeteoArchiveDaily {
+    pub time: Vec<String>,
+    pub temperature_2m_max: Vec<Option<f64>>,
+    pub temperature_2m_min: Vec<Option<f64>>,
+    pub precipitation_sum: Vec<Option<f64>>,
+}
+
+impl DayHistory {
+    /// Average the days in an archive response that fall on the same month
+    /// and day as `date`. February 29 is compared with February 28 in
+    /// years that don't have one. None when no year has complete data.
+    pub fn from_archive(resp: &OpenMeteoArchiveResponse, date: NaiveDate) -> Option<Self> {
+        let daily = &resp.daily;
+        let mut totals = (0.0, 0.0, 0.0);
+        let mut years = 0;
+
+        for (i, time) in daily.time.iter().enumerate() {
+            let Ok(day) = NaiveDate::parse_from_str(time, "%Y-%m-%d") else {
+                continue;
+            };
+            if day >= date || !same_calendar_day(day, date) {
+                continue;
+            }
+            let values = (
+                daily.temperature_2m_max.get(i).copied().flatten(),
+                daily.temperature_2m_min.get(i).copied().flatten(),
+                daily.precipitation_sum.get(i).copied().flatten(),
+            );
+            if let (Some(max), Some(min), Some(precipitation)) = values {
+                totals.0 += max;
+                totals.1 += min;
+                totals.2 += precipitation;
+                years += 1;
+            }
+        }
+
+        if years == 0 {
+            return None;
+        }
+        let n = years as f64;
+        Some(Self {
+            date: date.format("%Y-%m-%d").to_string(),
+            years,
+            temp_max: totals.0 / n,
+            temp_min: totals.1 / n,
+            precipitation: totals.2 / n,
+        })
+    }
+}
+
+fn same_calendar_day(day: NaiveDate, target: NaiveDate) -> bool {
+    if (day.month(), day.day()) == (target.month(), target.day()) {
+        return true;
+    }
+    // Stand-in for February 29 in years without one
+    let leap_day = (target.month(), target.day()) == (2, 29);
+    leap_day && !day.leap_year() && (day.month(), day.day()) == (2, 28)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn archive(days: &[(&str, f64, f64, Option<f64>)]) -> OpenMeteoArchiveResponse {
+        OpenMeteoArchiveResponse {
+            daily: OpenMeteoArchiveDaily {
+                time: days.iter().map(|d| d.0.to_string()).collect(),
+                temperature_2m_max: days.iter().map(|d| Some(d.1)).collect(),
+                temperature_2m_min: days.iter().map(|d| Some(d.2)).collect(),
+                precipitation_sum: days.iter().map(|d| d.3).collect(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_averages_same_date() {
+        let resp = archive(&[
+            ("2021-10-16", 18.0, 6.0, Some(0.0)),
+            ("2021-10-17", 30.0, 20.0, Some(9.0)),
+            ("2022-10-16", 20.0, 8.0, Some(3.0)),
+            ("2023-10-16", 22.0, 10.0, None),
+            ("2024-10-16", 16.0, 4.0, Some(1.5)),
+        ]);
+        let date = NaiveDate::from_ymd_opt(2025, 10, 16).unwrap();
+
+        let history = DayHistory::from_archive(&resp, date).unwrap();
+        assert_eq!(history.date, "2025-10-16");
+        // 2023 is missing precipitation, so it's left out
+        assert_eq!(history.years, 3);
+        assert_eq!(history.temp_max, 18.0);
+        assert_eq!(history.temp_min, 6.0);
+        assert_eq!(history.precipitation, 1.5);
+    }
+
+    #[test]
+    fn test_leap_day() {
+        let resp = archive(&[
+            ("2020-02-28", 5.0, 0.0, Some(0.0)),
+            ("2020-02-29", 7.0, 1.0, Some(2.0)),
+            ("2021-02-28", 9.0, 3.0, Some(4.0)),
+        ]);
+        let date = NaiveDate::from_ymd_opt(2024, 2, 29).unwrap();
+
+        let history = DayHistory::from_archive(&resp, date).unwrap();
+        assert_eq!(history.years, 2);
+        assert_eq!(history.temp_max, 8.0);
+    }
+
+    #[test]
+    fn test_no_matching_days() {
+        let resp = archive(&[("2024-10-15", 18.0, 6.0, Some(0.0))]);
+        let date = NaiveDate::from_ymd_opt(2025, 10, 16).unwrap();
+        assert!(DayHistory::from_archive(&resp, date).is_none());
+    }
+}
@@ -1,6 +1,8 @@
-use serde::Deserialize;
+use chrono::{DateTime, Local, NaiveDateTime, Utc};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[allow(dead_code)]
 pub struct Location {
     pub latitude: f64,
@@ -18,6 +20,15 @@ impl Location {
             None => format!("{}, {}", self.city, self.country),
         }
     }
+
+    /// Convert a UTC instant to wall-clock time at this location. Falls back
+    /// to the system timezone when the IANA name is unknown (e.g. "auto").
+    pub fn local_time(&self, utc: DateTime<Utc>) -> NaiveDateTime {
+        match self.timezone.parse::<Tz>() {
+            Ok(tz) => utc.with_timezone(&tz).naive_local(),
+            Err(_) => utc.with_timezone(&Local).naive_local(),
+        }
+    }
 }
 
 /// Response from ipapi.co for IP geolocation
@@ -44,6 +55,37 @@ impl From<IpApiResponse> for Location {
     }
 }
 
+/// Response from ipwho.is for IP geolocation
+#[derive(Debug, Deserialize)]
+pub struct IpWhoisResponse {
+    pub success: bool,
+    /// Why the lookup failed when `success` is false
+    pub message: Option<String>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub city: Option<String>,
+    pub region: Option<String>,
+    pub country: Option<String>,
+    pub timezone: Option<IpWhoisTimezone>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct IpWhoisTimezone {
+    pub id: String,
+}
+
+/// Response from ipinfo.io for IP geolocation
+#[derive(Debug, Deserialize)]
+pub struct IpInfoResponse {
+    /// "latitude,longitude"
+    pub loc: String,
+    pub city: String,
+    pub region: Option<String>,
+    /// ISO 3166-1 alpha-2 code
+    pub country: String,
+    pub timezone: String,
+}
+
 /// Response from Open-Meteo geocoding API
 #[derive(Debug, Deserialize)]
 pub struct GeocodingResponse {
@@ -57,6 +99,8 @@ pub struct GeocodingResult {
     pub longitude: f64,
     pub timezone: String,
     pub country: String,
+    #[serde(default)]
+    pub country_code: Option<String>,
     pub admin1: Option<String>,
 }
 
@@ -72,3 +116,39 @@ impl From<GeocodingResult> for Location {
         }
     }
 }
+
+/// Response from Nominatim's `/reverse` endpoint (`format=jsonv2`)
+#[derive(Debug, Deserialize)]
+pub struct NominatimResponse {
+    pub address: Option<NominatimAddress>,
+    /// Set instead of `address` when nothing is found, e.g. over open sea
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NominatimAddress {
+    pub city: Option<String>,
+    pub town: Option<String>,
+    pub village: Option<String>,
+    pub hamlet: Option<String>,
+    pub municipality: Option<String>,
+    pub county: Option<String>,
+    pub state: Option<String>,
+    pub country: Option<String>,
+}
+
+impl NominatimAddress {
+    /// The most specific settlement name available
+    pub fn place_name(&self) -> Option<&str> {
+        [
+            &self.city,
+            &self.town,
+            &self.village,
+            &self.hamlet,
+            &self.municipality,
+            &self.county,
+        ]
+        .into_iter()
+        .find_map(|name| name.as_deref())
+    }
+}
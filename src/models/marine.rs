@@ -0,0 +1,97 @@
+use serde::{Deserialize, Serialize};
+
+/// Sea conditions from the Open-Meteo marine API. Heights are in metres,
+/// periods in seconds, directions in degrees (where the waves come from)
+/// and sea surface temperature in Celsius. Values are missing inland or
+/// where the marine models have no coverage.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarineData {
+    pub current: MarineCurrent,
+    pub hourly: Vec<MarineHourly>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarineCurrent {
+    pub wave_height: Option<f64>,
+    pub wave_direction: Option<f64>,
+    pub wave_period: Option<f64>,
+    pub swell_wave_height: Option<f64>,
+    pub swell_wave_direction: Option<f64>,
+    pub swell_wave_period: Option<f64>,
+    pub sea_surface_temperature: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarineHourly {
+    pub time: String,
+    pub wave_height: Option<f64>,
+    pub wave_period: Option<f64>,
+    pub swell_wave_height: Option<f64>,
+}
+
+impl MarineData {
+    /// Conditions for the hour starting at `time` ("%Y-%m-%dT%H:%M")
+    pub fn at(&self, time: &str) -> Option<&MarineHourly> {
+        self.hourly.iter().find(|h| h.time == time)
+    }
+
+    /// False for inland points, where every value comes back empty
+    pub fn has_data(&self) -> bool {
+        self.current.wave_height.is_some() || self.hourly.iter().any(|h| h.wave_height.is_some())
+    }
+}
+
+/// Raw API response from the Open-Meteo marine API
+#[derive(Debug, Clone, Deserialize)]
+pub struct OpenMeteoMarineResponse {
+    pub current: OpenMeteoMarineCurrent,
+    pub hourly: OpenMeteoMarineHourly,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct OpenMeteoMarineCurrent {
+    pub wave_height: Option<f64>,
+    pub wave_direction: Option<f64>,
+    pub wave_period: Option<f64>,
+    pub swell_wave_height: Option<f64>,
+    pub swell_wave_direction: Option<f64>,
+    pub swell_wave_period: Option<f64>,
+    pub sea_surface_temperature: Option<f64>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct OpenMeteoMarineHourly {
+    pub time: Vec<String>,
+    pub wave_height: Vec<Option<f64>>,
+    pub wave_period: Vec<Option<f64>>,
+    pub swell_wave_height: Vec<Option<f64>>,
+}
+
+impl From<OpenMeteoMarineResponse> for MarineData {
+    fn from(resp: OpenMeteoMarineResponse) -> Self {
+        let current = MarineCurrent {
+            wave_height: resp.current.wave_height,
+            wave_direction: resp.current.wave_direction,
+            wave_period: resp.current.wave_period,
+            swell_wave_height: resp.current.swell_wave_height,
+            swell_wave_direction: resp.current.swell_wave_direction,
+            swell_wave_period: resp.current.swell_wave_period,
+            sea_surface_temperature: resp.current.sea_surface_temperature,
+        };
+
+        let hourly = resp
+            .hourly
+            .time
+            .iter()
+            .enumerate()
+            .map(|(i, time)| MarineHourly {
+                time: time.clone(),
+                wave_height: resp.hourly.wave_height.get(i).copied().flatten(),
+                wave_period: resp.hourly.wave_period.get(i).copied().flatten(),
+                swell_wave_height: resp.hourly.swell_wave_height.get(i).copied().flatten(),
+            })
+            .collect();
+
+        Self { current, hourly }
+    }
+}
@@ -0,0 +1,383 @@
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Timelike, Utc};
+use serde::Deserialize;
+
+use crate::models::location::Location;
+use crate::models::sun::sunrise_sunset;
+use crate::models::weather::{
+    apparent_temperature, CurrentWeather, DailyForecast, HourlyForecast, WeatherData,
+};
+
+/// Response from MET Norway's Locationforecast 2.0 `compact` endpoint
+#[derive(Debug, Clone, Deserialize)]
+pub struct MetNoResponse {
+    pub properties: MetNoProperties,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct MetNoProperties {
+    pub timeseries: Vec<MetNoTimestep>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct MetNoTimestep {
+    pub time: DateTime<Utc>,
+    pub data: MetNoData,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct MetNoData {
+    pub instant: MetNoInstant,
+    pub next_1_hours: Option<MetNoPeriod>,
+    pub next_6_hours: Option<MetNoPeriod>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct MetNoInstant {
+    pub details: MetNoInstantDetails,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct MetNoInstantDetails {
+    pub air_temperature: f64,
+    #[serde(default)]
+    pub air_pressure_at_sea_level: Option<f64>,
+    #[serde(default)]
+    pub cloud_area_fraction: Option<f64>,
+    #[serde(default)]
+    pub relative_humidity: Option<f64>,
+    #[serde(default)]
+    pub dew_point_temperature: Option<f64>,
+    #[serde(default)]
+    pub wind_from_direction: Option<f64>,
+    /// Metres per second
+    #[serde(default)]
+    pub wind_speed: Option<f64>,
+    #[serde(default)]
+    pub ultraviolet_index_clear_sky: Option<f64>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct MetNoPeriod {
+    pub summary: MetNoSummary,
+    #[serde(default)]
+    pub details: Option<MetNoPeriodDetails>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct MetNoSummary {
+    pub symbol_code: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct MetNoPeriodDetails {
+    #[serde(default)]
+    pub precipitation_amount: Option<f64>,
+}
+
+impl MetNoTimestep {
+    /// The shortest forecast period attached to this step. The feed is hourly
+    /// for the first couple of days and six-hourly after that.
+    fn period(&self) -> Option<&MetNoPeriod> {
+        self.data
+            .next_1_hours
+            .as_ref()
+            .or(self.data.next_6_hours.as_ref())
+    }
+
+    fn precipitation(&self) -> f64 {
+        self.period()
+            .and_then(|p| p.details.as_ref())
+            .and_then(|d| d.precipitation_amount)
+            .unwrap_or(0.0)
+    }
+
+    fn wind_kmh(&self) -> f64 {
+        self.data.instant.details.wind_speed.unwrap_or(0.0) * 3.6
+    }
+}
+
+/// A Locationforecast response paired with the location it was requested for,
+/// which is needed to bucket the UTC timeseries into local days
+#[derive(Debug, Clone)]
+pub struct MetNoForecast {
+    pub location: Location,
+    pub response: MetNoResponse,
+}
+
+impl From<MetNoForecast> for WeatherData {
+    fn from(forecast: MetNoForecast) -> Self {
+        let location = &forecast.location;
+        let steps = &forecast.response.properties.timeseries;
+
+        // Past the first couple of days the feed thins to six-hourly steps,
+        // which only feed the daily aggregates
+        let hourly: Vec<HourlyForecast> = steps
+            .iter()
+            .take_while(|step| step.data.next_1_hours.is_some())
+            .map(|step| {
+                let details = &step.data.instant.details;
+                let wind_speed = step.wind_kmh();
+                HourlyForecast {
+                    time: location
+                        .local_time(step.time)
+                        .format("%Y-%m-%dT%H:%M")
+                        .to_string(),
+                    temperature: details.air_temperature,
+                    apparent_temperature: apparent_temperature(
+                        details.air_temperature,
+                        details.relative_humidity,
+                        wind_speed,
+                    ),
+                    // The compact format carries no precipitation probability
+                    precipitation_probability: None,
+                    precipitation: step.precipitation(),
+                    weather_code: step
+                        .period()
+                        .map(|p| symbol_to_wmo(&p.summary.symbol_code))
+                        .unwrap_or(-1),
+                    wind_speed,
+                    humidity: details.relative_humidity.map(|h| h.round() as i32),
+                    dew_point: details.dew_point_temperature,
+                    wind_direction: details.wind_from_direction.map(|d| d.round() as i32),
+                    wind_gusts: None,
+                    cloud_cover: details.cloud_area_fraction.map(|c| c.round() as i32),
+                    visibility: None,
+                    uv_index: details.ultraviolet_index_clear_sky,
+                    snowfall: None,
+                }
+            })
+            .collect();
+
+        let current = steps
+            .first()
+            .map(current_from_step)
+            .unwrap_or_else(empty_current);
+
+        let daily = build_daily(steps, location);
+
+        Self {
+            current,
+            hourly,
+            daily,
+            minutely: Vec::new(),
+        }
+    }
+}
+
+fn current_from_step(step: &MetNoTimestep) -> CurrentWeather {
+    let details = &step.data.instant.details;
+    let wind_speed = step.wind_kmh();
+    let symbol = step.period().map(|p| p.summary.symbol_code.as_str());
+
+    CurrentWeather {
+        temperature: details.air_temperature,
+        apparent_temperature: apparent_temperature(
+            details.air_temperature,
+            details.relative_humidity,
+            wind_speed,
+        ),
+        humidity: details.relative_humidity.map(|h| h.round() as i32),
+        weather_code: symbol.map(symbol_to_wmo).unwrap_or(-1),
+        wind_speed,
+        wind_direction: details.wind_from_direction.unwrap_or(0.0).round() as i32,
+        // Gusts are only in the `complete` format
+        wind_gusts: None,
+        cloud_cover: details.cloud_area_fraction.map(|c| c.round() as i32),
+        pressure: details.air_pressure_at_sea_level,
+        precipitation: step.precipitation(),
+        uv_index: details.ultraviolet_index_clear_sky,
+        is_day: symbol.map(symbol_is_day).unwrap_or(true),
+        dew_point: details.dew_point_temperature,
+        visibility: None,
+    }
+}
+
+fn empty_current() -> CurrentWeather {
+    CurrentWeather {
+        temperature: 0.0,
+        apparent_temperature: 0.0,
+        humidity: None,
+        weather_code: -1,
+        wind_speed: 0.0,
+        wind_direction: 0,
+        wind_gusts: None,
+        cloud_cover: None,
+        pressure: None,
+        precipitation: 0.0,
+        uv_index: None,
+        is_day: true,
+        dew_point: None,
+        visibility: None,
+    }
+}
+
+/// Locationforecast has no daily block, so highs, lows and precipitation
+/// sums are aggregated from the timeseries per local calendar day
+fn build_daily(steps: &[MetNoTimestep], location: &Location) -> Vec<DailyForecast> {
+    let mut days: Vec<(NaiveDate, Vec<(NaiveDateTime, &MetNoTimestep)>)> = Vec::new();
+    for step in steps {
+        let local = location.local_time(step.time);
+        match days.last_mut() {
+            Some((date, entries)) if *date == local.date() => entries.push((local, step)),
+            _ => days.push((local.date(), vec![(local, step)])),
+        }
+    }
+
+    days.into_iter()
+        .map(|(date, entries)| {
+            let temps = entries
+                .iter()
+                .map(|(_, s)| s.data.instant.details.air_temperature);
+            let temp_max = temps.clone().fold(f64::NEG_INFINITY, f64::max);
+            let temp_min = temps.fold(f64::INFINITY, f64::min);
+
+            let apparent: Vec<f64> = entries
+                .iter()
+                .map(|(_, s)| {
+                    let d = &s.data.instant.details;
+                    apparent_temperature(d.air_temperature, d.relative_humidity, s.wind_kmh())
+                })
+                .collect();
+
+            // Use the summary closest to midday as the day's condition,
+            // preferring the longer six-hour outlook where available
+            let weather_code = entries
+                .iter()
+                .min_by_key(|(local, _)| (local.hour() as i32 - 12).abs())
+                .and_then(|(_, s)| s.data.next_6_hours.as_ref().or(s.period()))
+                .map(|p| symbol_to_wmo(&p.summary.symbol_code))
+                .unwrap_or(-1);
+
+            let (sunrise, sunset) =
+                match sunrise_sunset(date, location.latitude, location.longitude) {
+                    Some((rise, set)) => (
+                        location
+                            .local_time(rise)
+                            .format("%Y-%m-%dT%H:%M")
+                            .to_string(),
+                        location
+                            .local_time(set)
+                            .format("%Y-%m-%dT%H:%M")
+                            .to_string(),
+                    ),
+                    None => (String::new(), String::new()),
+                };
+
+            DailyForecast {
+                date: date.format("%Y-%m-%d").to_string(),
+                weather_code,
+                temp_max,
+                temp_min,
+                apparent_temp_max: apparent.iter().cloned().fold(f64::NEG_INFINITY, f64::max),
+                apparent_temp_min: apparent.iter().cloned().fold(f64::INFINITY, f64::min),
+                sunrise,
+                sunset,
+                precipitation_sum: entries.iter().map(|(_, s)| s.precipitation()).sum(),
+                precipitation_probability: None,
+                wind_speed_max: entries
+                    .iter()
+                    .map(|(_, s)| s.wind_kmh())
+                    .fold(0.0, f64::max),
+                uv_index_max: entries
+                    .iter()
+                    .filter_map(|(_, s)| s.data.instant.details.ultraviolet_index_clear_sky)
+                    .fold(0.0, f64::max),
+            }
+        })
+        .collect()
+}
+
+fn symbol_is_day(symbol: &str) -> bool {
+    !symbol.ends_with("_night") && !symbol.ends_with("_polartwilight")
+}
+
+/// Map a MET Norway symbol code (e.g. `lightrainshowers_day`) to a WMO code
+pub fn symbol_to_wmo(symbol: &str) -> i32 {
+    let base = symbol.split('_').next().unwrap_or(symbol);
+
+    // Includes the feed's misspelled "lightssleet…andthunder" variants
+    if base.contains("thunder") {
+        return 95;
+    }
+
+    let (intensity, rest) = if let Some(rest) = base.strip_prefix("light") {
+        (0, rest)
+    } else if let Some(rest) = base.strip_prefix("heavy") {
+        (2, rest)
+    } else {
+        (1, base)
+    };
+
+    match rest {
+        "clearsky" => 0,
+        "fair" => 1,
+        "partlycloudy" => 2,
+        "cloudy" => 3,
+        "fog" => 45,
+        "rain" => [61, 63, 65][intensity],
+        "rainshowers" => [80, 81, 82][intensity],
+        // Rain and snow mixed, not freezing rain
+        "sleet" => [68, 68, 69][intensity],
+        "sleetshowers" => [83, 83, 84][intensity],
+        "snow" => [71, 73, 75][intensity],
+        "snowshowers" => [85, 85, 86][intensity],
+        _ => -1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_readings_are_unknown() {
+        let step: MetNoTimestep = serde_json::from_str(
+            r#"{
+                "time": "2024-06-01T20:00:00Z",
+                "data": {
+                    "instant": { "details": { "air_temperature": 14.0 } },
+                    "next_1_hours": { "summary": { "symbol_code": "cloudy" } }
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let current = current_from_step(&step);
+        assert_eq!(current.temperature, 14.0);
+        assert_eq!(current.humidity, None);
+        assert_eq!(current.cloud_cover, None);
+        assert_eq!(current.pressure, None);
+        assert_eq!(current.uv_index, None);
+    }
+
+    #[test]
+    fn test_symbol_codes() {
+        assert_eq!(symbol_to_wmo("clearsky_day"), 0);
+        assert_eq!(symbol_to_wmo("clearsky_night"), 0);
+        assert_eq!(symbol_to_wmo("fair_polartwilight"), 1);
+        assert_eq!(symbol_to_wmo("partlycloudy_day"), 2);
+        assert_eq!(symbol_to_wmo("cloudy"), 3);
+        assert_eq!(symbol_to_wmo("fog"), 45);
+        assert_eq!(symbol_to_wmo("lightrain"), 61);
+        assert_eq!(symbol_to_wmo("rain"), 63);
+        assert_eq!(symbol_to_wmo("heavyrain"), 65);
+        assert_eq!(symbol_to_wmo("lightrainshowers_day"), 80);
+        assert_eq!(symbol_to_wmo("heavyrainshowers_night"), 82);
+        assert_eq!(symbol_to_wmo("lightsnow"), 71);
+        assert_eq!(symbol_to_wmo("heavysnowshowers_day"), 86);
+        assert_eq!(symbol_to_wmo("lightssleetshowersandthunder_day"), 95);
+        assert_eq!(symbol_to_wmo("lightssnowshowersandthunder_day"), 95);
+        assert_eq!(symbol_to_wmo("sleet"), 68);
+        assert_eq!(symbol_to_wmo("heavysleet"), 69);
+        assert_eq!(symbol_to_wmo("lightsleetshowers_day"), 83);
+        assert_eq!(symbol_to_wmo("unknown"), -1);
+    }
+
+    #[test]
+    fn test_symbol_is_day() {
+        assert!(symbol_is_day("clearsky_day"));
+        assert!(symbol_is_day("cloudy"));
+        assert!(!symbol_is_day("clearsky_night"));
+        assert!(!symbol_is_day("fair_polartwilight"));
+    }
+}
@@ -1,4 +1,18 @@
+pub mod air_quality;
+pub mod alerts;
+pub mod cap;
+pub mod cities;
+pub mod comparison;
+pub mod ensemble;
+pub mod history;
 pub mod location;
+pub mod marine;
+pub mod metno;
+pub mod normals;
+pub mod nowcast;
+pub mod nws;
+pub mod openweathermap;
+pub mod sun;
 pub mod weather;
 
 pub use location::Location;
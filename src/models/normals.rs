@@ -0,0 +1,184 @@
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+use crate::models::history::OpenMeteoArchiveResponse;
+
+/// Days either side of a date pooled into its normal, to smooth out the
+/// noise thirty samples per calendar day would leave
+const WINDOW_DAYS: i32 = 7;
+
+/// Daily precipitation (mm) that counts as a wet day
+const WET_DAY_MM: f64 = 1.0;
+
+/// Leap year used to number calendar days, so February 29 gets a slot
+const REFERENCE_YEAR: i32 = 2000;
+
+/// 30-year climatological normals for every calendar day at one grid point.
+/// Temperatures are in Celsius.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClimateNormals {
+    /// Grid point the normals were computed for, see [`ClimateNormals::grid`]
+    pub latitude: f64,
+    pub longitude: f64,
+    /// One entry per calendar day, January 1 to December 31 of a leap year.
+    /// Empty where the archive had no data near that day.
+    pub days: Vec<Option<DayNormal>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DayNormal {
+    pub temp_max: f64,
+    pub temp_min: f64,
+    /// Share of days with measurable precipitation, 0-100
+    pub precipitation_probability: f64,
+}
+
+impl ClimateNormals {
+    /// Normals barely change over a few kilometres, so coordinates are
+    /// snapped to a 0.1° grid and nearby locations share one table
+    pub fn grid(latitude: f64, longitude: f64) -> (f64, f64) {
+        (
+            (latitude * 10.0).round() / 10.0,
+            (longitude * 10.0).round() / 10.0,
+        )
+    }
+
+    pub fn covers(&self, latitude: f64, longitude: f64) -> bool {
+        Self::grid(latitude, longitude) == (self.latitude, self.longitude)
+    }
+
+    pub fn for_date(&self, date: NaiveDate) -> Option<&DayNormal> {
+        self.days.get(calendar_index(date)?)?.as_ref()
+    }
+
+    /// Build the table from a multi-decade archive response. Days with
+    /// missing values are skipped; None when nothing usable came back.
+    pub fn from_archive(
+        resp: &OpenMeteoArchiveResponse,
+        latitude: f64,
+        longitude: f64,
+    ) -> Option<Self> {
+        let daily = &resp.daily;
+        // (sum of highs, sum of lows, wet days, samples) per calendar day
+        let mut totals = vec![(0.0, 0.0, 0usize, 0usize); 366];
+
+        for (i, time) in daily.time.iter().enumerate() {
+            let Some(index) = NaiveDate::parse_from_str(time, "%Y-%m-%d")
+                .ok()
+                .and_then(calendar_index)
+            else {
+                continue;
+            };
+            let values = (
+                daily.temperature_2m_max.get(i).copied().flatten(),
+                daily.temperature_2m_min.get(i).copied().flatten(),
+                daily.precipitation_sum.get(i).copied().flatten(),
+            );
+            if let (Some(max), Some(min), Some(precipitation)) = values {
+                let entry = &mut totals[index];
+                entry.0 += max;
+                entry.1 += min;
+                entry.2 += usize::from(precipitation >= WET_DAY_MM);
+                entry.3 += 1;
+            }
+        }
+
+        let days: Vec<Option<DayNormal>> = (0..366i32)
+            .map(|index| {
+                let mut sum = (0.0, 0.0, 0, 0);
+                for offset in -WINDOW_DAYS..=WINDOW_DAYS {
+                    let t = totals[(index + offset).rem_euclid(366) as usize];
+                    sum = (sum.0 + t.0, sum.1 + t.1, sum.2 + t.2, sum.3 + t.3);
+                }
+                (sum.3 > 0).then(|| DayNormal {
+                    temp_max: sum.0 / sum.3 as f64,
+                    temp_min: sum.1 / sum.3 as f64,
+                    precipitation_probability: 100.0 * sum.2 as f64 / sum.3 as f64,
+                })
+            })
+            .collect();
+        if days.iter().all(Option::is_none) {
+            return None;
+        }
+
+        let (latitude, longitude) = Self::grid(latitude, longitude);
+        Some(Self {
+            latitude,
+            longitude,
+            days,
+        })
+    }
+}
+
+/// Position of a date's month and day in the reference leap year
+fn calendar_index(date: NaiveDate) -> Option<usize> {
+    NaiveDate::from_ymd_opt(REFERENCE_YEAR, date.month(), date.day()).map(|d| d.ordinal0() as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::history::OpenMeteoArchiveDaily;
+
+    /// Two years of data where highs follow the day of the year and it
+    /// rains every other day
+    fn archive() -> OpenMeteoArchiveResponse {
+        let start = NaiveDate::from_ymd_opt(1993, 1, 1).unwrap();
+        let dates: Vec<NaiveDate> = start.iter_days().take(730).collect();
+        OpenMeteoArchiveResponse {
+            daily: OpenMeteoArchiveDaily {
+                time: dates
+                    .iter()
+                    .map(|d| d.format("%Y-%m-%d").to_string())
+                    .collect(),
+                temperature_2m_max: dates.iter().map(|d| Some(d.ordinal() as f64)).collect(),
+                temperature_2m_min: dates.iter().map(|_| Some(-5.0)).collect(),
+                precipitation_sum: dates
+                    .iter()
+                    .map(|d| Some(if d.ordinal() % 2 == 0 { 4.0 } else { 0.0 }))
+                    .collect(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_from_archive() {
+        let normals = ClimateNormals::from_archive(&archive(), 39.7392, -104.9903).unwrap();
+
+        assert_eq!((normals.latitude, normals.longitude), (39.7, -105.0));
+        assert_eq!(normals.days.len(), 366);
+
+        // Mid-year the window is symmetric, so the mean is the day itself
+        let july = normals
+            .for_date(NaiveDate::from_ymd_opt(2025, 7, 1).unwrap())
+            .unwrap();
+        assert_eq!(july.temp_max, 182.0);
+        assert_eq!(july.temp_min, -5.0);
+        assert!((july.precipitation_probability - 46.67).abs() < 0.01);
+
+        // No leap day in the data, but the window still covers it
+        assert!(normals
+            .for_date(NaiveDate::from_ymd_opt(2024, 2, 29).unwrap())
+            .is_some());
+    }
+
+    #[test]
+    fn test_covers() {
+        let normals = ClimateNormals::from_archive(&archive(), 39.7392, -104.9903).unwrap();
+        assert!(normals.covers(39.71, -104.96));
+        assert!(!normals.covers(40.02, -105.27));
+    }
+
+    #[test]
+    fn test_empty_archive() {
+        let resp = OpenMeteoArchiveResponse {
+            daily: OpenMeteoArchiveDaily {
+                time: vec![],
+                temperature_2m_max: vec![],
+                temperature_2m_min: vec![],
+                precipitation_sum: vec![],
+            },
+        };
+        assert!(ClimateNormals::from_archive(&resp, 0.0, 0.0).is_none());
+    }
+}
@@ -0,0 +1,223 @@
+use chrono::{NaiveDateTime, TimeDelta};
+
+use crate::models::weather::MinutelyPrecipitation;
+
+/// Number of 15-minute steps in the nowcast window
+pub const NOWCAST_STEPS: usize = 8;
+
+/// Precipitation (mm) in a 15-minute step that counts as wet
+const WET_STEP_MM: f64 = 0.1;
+
+/// Rates (mm/h) where rain turns moderate and heavy
+const MODERATE_MM_H: f64 = 2.5;
+const HEAVY_MM_H: f64 = 7.6;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Intensity {
+    Light,
+    Moderate,
+    Heavy,
+}
+
+impl Intensity {
+    /// None when the amount is too small to count as precipitation
+    pub fn from_step(mm: f64) -> Option<Self> {
+        let rate = mm * 4.0;
+        if mm < WET_STEP_MM {
+            None
+        } else if rate >= HEAVY_MM_H {
+            Some(Intensity::Heavy)
+        } else if rate >= MODERATE_MM_H {
+            Some(Intensity::Moderate)
+        } else {
+            Some(Intensity::Light)
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Intensity::Light => "Light",
+            Intensity::Moderate => "Moderate",
+            Intensity::Heavy => "Heavy",
+        }
+    }
+}
+
+/// One 15-minute step of the nowcast
+#[derive(Debug, Clone, PartialEq)]
+pub struct NowcastStep {
+    pub start: NaiveDateTime,
+    pub precipitation: f64,
+    pub snowfall: f64,
+}
+
+impl NowcastStep {
+    pub fn end(&self) -> NaiveDateTime {
+        self.start + TimeDelta::minutes(15)
+    }
+
+    pub fn intensity(&self) -> Option<Intensity> {
+        Intensity::from_step(self.precipitation)
+    }
+}
+
+/// Precipitation over the next two hours in 15-minute steps, starting with
+/// the step in progress
+#[derive(Debug, Clone)]
+pub struct Nowcast {
+    pub now: NaiveDateTime,
+    pub steps: Vec<NowcastStep>,
+}
+
+impl Nowcast {
+    /// None when the data doesn't reach past `now`, e.g. from a stale cache
+    /// or a provider without 15-minute data
+    pub fn new(minutely: &[MinutelyPrecipitation], now: NaiveDateTime) -> Option<Self> {
+        let steps: Vec<NowcastStep> = minutely
+            .iter()
+            .filter_map(|m| {
+                // Each value is the total for the 15 minutes before its time
+                let end = NaiveDateTime::parse_from_str(&m.time, "%Y-%m-%dT%H:%M").ok()?;
+                Some(NowcastStep {
+                    start: end - TimeDelta::minutes(15),
+                    precipitation: m.precipitation,
+                    snowfall: m.snowfall,
+                })
+            })
+            .filter(|step| step.end() > now)
+            .take(NOWCAST_STEPS)
+            .collect();
+
+        if steps.is_empty() {
+            return None;
+        }
+        Some(Self { now, steps })
+    }
+
+    /// When the window ends
+    pub fn end(&self) -> NaiveDateTime {
+        self.steps.last().map(|s| s.end()).unwrap_or(self.now)
+    }
+
+    /// One sentence on what's coming, e.g. "Light rain starting in ~35 min,
+    /// ending ~1:15pm"
+    pub fn summary(&self) -> String {
+        let Some(first_wet) = self.steps.iter().position(|s| s.intensity().is_some()) else {
+            return format!("No precipitation expected before {}", clock(self.end()));
+        };
+        let spell_end = self.steps[first_wet..]
+            .iter()
+            .position(|s| s.intensity().is_none())
+            .map(|i| first_wet + i);
+        let spell = &self.steps[first_wet..spell_end.unwrap_or(self.steps.len())];
+
+        let intensity = spell
+            .iter()
+            .filter_map(|s| s.intensity())
+            .max()
+            .unwrap_or(Intensity::Light);
+        let kind = if spell.iter().any(|s| s.snowfall > 0.0) {
+            "snow"
+        } else {
+            "rain"
+        };
+        let what = format!("{} {}", intensity.label(), kind);
+
+        if first_wet == 0 {
+            match spell_end {
+                Some(i) => format!(
+                    "{} ending in {}",
+                    what,
+                    minutes_until(self.now, self.steps[i].start)
+                ),
+                None => format!("{} continuing past {}", what, clock(self.end())),
+            }
+        } else {
+            let starting = format!(
+                "{} starting in {}",
+                what,
+                minutes_until(self.now, self.steps[first_wet].start)
+            );
+            match spell_end {
+                Some(i) => format!("{}, ending ~{}", starting, clock(self.steps[i].start)),
+                None => starting,
+            }
+        }
+    }
+}
+
+/// "~35 min" or "~1 h 20 min", rounded to 5 minutes since the data is only
+/// good to 15
+fn minutes_until(now: NaiveDateTime, then: NaiveDateTime) -> String {
+    let minutes = ((then - now).num_minutes() as f64 / 5.0).round().max(1.0) as i64 * 5;
+    match (minutes / 60, minutes % 60) {
+        (0, m) => format!("~{} min", m),
+        (h, 0) => format!("~{} h", h),
+        (h, m) => format!("~{} h {} min", h, m),
+    }
+}
+
+fn clock(time: NaiveDateTime) -> String {
+    time.format("%-I:%M%P").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn minutely(values: &[f64]) -> Vec<MinutelyPrecipitation> {
+        values
+            .iter()
+            .enumerate()
+            .map(|(i, &mm)| MinutelyPrecipitation {
+                time: format!("2024-05-02T{:02}:{:02}", 12 + (i + 1) / 4, (i + 1) % 4 * 15),
+                precipitation: mm,
+                snowfall: 0.0,
+            })
+            .collect()
+    }
+
+    fn at(time: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(time, "%Y-%m-%dT%H:%M").unwrap()
+    }
+
+    #[test]
+    fn test_window() {
+        let data = minutely(&[0.0; 12]);
+        let nowcast = Nowcast::new(&data, at("2024-05-02T12:20")).unwrap();
+
+        // The step covering 12:15-12:30 is in progress
+        assert_eq!(nowcast.steps.len(), NOWCAST_STEPS);
+        assert_eq!(nowcast.steps[0].start, at("2024-05-02T12:15"));
+        assert_eq!(nowcast.end(), at("2024-05-02T14:15"));
+        assert_eq!(nowcast.summary(), "No precipitation expected before 2:15pm");
+
+        assert!(Nowcast::new(&data, at("2024-05-02T15:00")).is_none());
+    }
+
+    #[test]
+    fn test_rain_starting() {
+        let data = minutely(&[0.0, 0.0, 0.0, 0.2, 0.4, 0.3, 0.0, 0.0, 0.0]);
+        let nowcast = Nowcast::new(&data, at("2024-05-02T12:10")).unwrap();
+        assert_eq!(
+            nowcast.summary(),
+            "Light rain starting in ~35 min, ending ~1:30pm"
+        );
+
+        let data = minutely(&[0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 2.5, 3.0, 1.0]);
+        let nowcast = Nowcast::new(&data, at("2024-05-02T12:00")).unwrap();
+        assert_eq!(nowcast.summary(), "Heavy rain starting in ~1 h 30 min");
+    }
+
+    #[test]
+    fn test_rain_ending() {
+        let data = minutely(&[0.8, 0.7, 0.1, 0.0, 0.0, 0.0, 0.0, 0.0]);
+        let nowcast = Nowcast::new(&data, at("2024-05-02T12:05")).unwrap();
+        assert_eq!(nowcast.summary(), "Moderate rain ending in ~40 min");
+
+        let mut data = minutely(&[0.2; 8]);
+        data[2].snowfall = 0.3;
+        let nowcast = Nowcast::new(&data, at("2024-05-02T12:00")).unwrap();
+        assert_eq!(nowcast.summary(), "Light snow continuing past 2:00pm");
+    }
+}
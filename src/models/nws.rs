@@ -0,0 +1,616 @@
+use chrono::{DateTime, FixedOffset, NaiveDate};
+use serde::Deserialize;
+
+use crate::models::sun::sunrise_sunset;
+use crate::models::weather::{
+    apparent_temperature, CurrentWeather, DailyForecast, HourlyForecast, WeatherData,
+};
+
+/// Response from the NWS `/points/{lat},{lon}` endpoint
+#[derive(Debug, Clone, Deserialize)]
+pub struct NwsPointsResponse {
+    pub properties: NwsPointProperties,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NwsPointProperties {
+    pub forecast: String,
+    pub forecast_hourly: String,
+    pub observation_stations: Option<String>,
+}
+
+/// Response from the gridpoint `/forecast` and `/forecast/hourly` endpoints
+#[derive(Debug, Clone, Deserialize)]
+pub struct NwsForecastResponse {
+    pub properties: NwsForecastProperties,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct NwsForecastProperties {
+    pub periods: Vec<NwsPeriod>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NwsPeriod {
+    pub start_time: String,
+    pub is_daytime: bool,
+    pub temperature: f64,
+    pub temperature_unit: String,
+    #[serde(default)]
+    pub probability_of_precipitation: Option<NwsValue>,
+    #[serde(default)]
+    pub relative_humidity: Option<NwsValue>,
+    #[serde(default)]
+    pub dewpoint: Option<NwsValue>,
+    #[serde(default)]
+    pub wind_speed: Option<String>,
+    /// Compass point, e.g. "SW"
+    #[serde(default)]
+    pub wind_direction: Option<String>,
+    #[serde(default)]
+    pub icon: Option<String>,
+    #[serde(default)]
+    pub short_forecast: String,
+}
+
+/// A quantitative value with a WMO unit code, e.g. `wmoUnit:degC`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NwsValue {
+    pub value: Option<f64>,
+    #[serde(default)]
+    pub unit_code: String,
+}
+
+impl NwsValue {
+    /// Value converted to the crate's metric base units
+    pub fn metric(&self) -> Option<f64> {
+        let value = self.value?;
+        let unit = self.unit_code.rsplit(':').next().unwrap_or("");
+        Some(match unit {
+            "degF" => (value - 32.0) * 5.0 / 9.0,
+            "m_s-1" => value * 3.6,
+            "Pa" => value / 100.0,
+            _ => value,
+        })
+    }
+}
+
+/// Response from the `/stations` list linked from a point
+#[derive(Debug, Clone, Deserialize)]
+pub struct NwsStationsResponse {
+    pub features: Vec<NwsStationFeature>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct NwsStationFeature {
+    pub id: String,
+}
+
+/// Response from `/stations/{id}/observations/latest`
+#[derive(Debug, Clone, Deserialize)]
+pub struct NwsObservationResponse {
+    pub properties: NwsObservation,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NwsObservation {
+    #[serde(default)]
+    pub text_description: String,
+    #[serde(default)]
+    pub icon: Option<String>,
+    pub temperature: NwsValue,
+    pub wind_direction: Option<NwsValue>,
+    pub wind_speed: Option<NwsValue>,
+    pub wind_gust: Option<NwsValue>,
+    pub barometric_pressure: Option<NwsValue>,
+    pub sea_level_pressure: Option<NwsValue>,
+    pub precipitation_last_hour: Option<NwsValue>,
+    pub relative_humidity: Option<NwsValue>,
+    pub dewpoint: Option<NwsValue>,
+    pub visibility: Option<NwsValue>,
+    pub wind_chill: Option<NwsValue>,
+    pub heat_index: Option<NwsValue>,
+}
+
+/// Everything fetched from NWS for one location
+#[derive(Debug, Clone)]
+pub struct NwsForecastBundle {
+    pub latitude: f64,
+    pub longitude: f64,
+    pub hourly: NwsForecastResponse,
+    pub daily: NwsForecastResponse,
+    pub observation: Option<NwsObservationResponse>,
+}
+
+impl From<NwsForecastBundle> for WeatherData {
+    fn from(bundle: NwsForecastBundle) -> Self {
+        let hourly: Vec<HourlyForecast> = bundle
+            .hourly
+            .properties
+            .periods
+            .iter()
+            .filter_map(hourly_from_period)
+            .collect();
+
+        let is_day = bundle
+            .hourly
+            .properties
+            .periods
+            .first()
+            .map(|p| p.is_daytime)
+            .unwrap_or(true);
+
+        let current = bundle
+            .observation
+            .as_ref()
+            .and_then(|obs| current_from_observation(&obs.properties, is_day))
+            .or_else(|| {
+                let period = bundle.hourly.properties.periods.first()?;
+                current_from_period(period)
+            })
+            .unwrap_or_else(|| empty_current(is_day));
+
+        let daily = build_daily(
+            &bundle.daily.properties.periods,
+            &bundle.hourly.properties.periods,
+            &hourly,
+            bundle.latitude,
+            bundle.longitude,
+        );
+
+        Self {
+            current,
+            hourly,
+            daily,
+            minutely: Vec::new(),
+        }
+    }
+}
+
+fn parse_time(s: &str) -> Option<DateTime<FixedOffset>> {
+    DateTime::parse_from_rfc3339(s).ok()
+}
+
+fn period_celsius(period: &NwsPeriod) -> f64 {
+    if period.temperature_unit.eq_ignore_ascii_case("F") {
+        (period.temperature - 32.0) * 5.0 / 9.0
+    } else {
+        period.temperature
+    }
+}
+
+fn period_wind_kmh(period: &NwsPeriod) -> f64 {
+    period
+        .wind_speed
+        .as_deref()
+        .map(parse_wind_speed)
+        .unwrap_or(0.0)
+}
+
+fn period_code(period: &NwsPeriod) -> i32 {
+    nws_weather_code(period.icon.as_deref(), &period.short_forecast)
+}
+
+fn hourly_from_period(period: &NwsPeriod) -> Option<HourlyForecast> {
+    let start = parse_time(&period.start_time)?;
+    let temperature = period_celsius(period);
+    let wind_speed = period_wind_kmh(period);
+    let humidity = period.relative_humidity.as_ref().and_then(|v| v.value);
+
+    Some(HourlyForecast {
+        time: start.naive_local().format("%Y-%m-%dT%H:%M").to_string(),
+        temperature,
+        apparent_temperature: apparent_temperature(temperature, humidity, wind_speed),
+        precipitation_probability: pop(period),
+        // The gridpoint forecasts don't carry quantitative precipitation
+        precipitation: 0.0,
+        weather_code: period_code(period),
+        wind_speed,
+        humidity: humidity.map(|h| h.round() as i32),
+        dew_point: period.dewpoint.as_ref().and_then(NwsValue::metric),
+        wind_direction: period.wind_direction.as_deref().and_then(compass_degrees),
+        wind_gusts: None,
+        cloud_cover: None,
+        visibility: None,
+        uv_index: None,
+        snowfall: None,
+    })
+}
+
+fn pop(period: &NwsPeriod) -> Option<i32> {
+    period
+        .probability_of_precipitation
+        .as_ref()
+        .and_then(|v| v.value)
+        .map(|v| v as i32)
+}
+
+fn current_from_observation(obs: &NwsObservation, is_day: bool) -> Option<CurrentWeather> {
+    let temperature = obs.temperature.metric()?;
+    let metric = |v: &Option<NwsValue>| v.as_ref().and_then(NwsValue::metric);
+
+    let humidity = metric(&obs.relative_humidity);
+    let wind_speed = metric(&obs.wind_speed).unwrap_or(0.0);
+    let weather_code = nws_weather_code(obs.icon.as_deref(), &obs.text_description);
+
+    Some(CurrentWeather {
+        temperature,
+        apparent_temperature: metric(&obs.heat_index)
+            .or_else(|| metric(&obs.wind_chill))
+            .unwrap_or_else(|| apparent_temperature(temperature, humidity, wind_speed)),
+        humidity: humidity.map(|h| h.round() as i32),
+        weather_code,
+        wind_speed,
+        wind_direction: metric(&obs.wind_direction).unwrap_or(0.0) as i32,
+        wind_gusts: metric(&obs.wind_gust),
+        // NWS publishes sky condition as a code, not a percentage
+        cloud_cover: None,
+        pressure: metric(&obs.sea_level_pressure).or_else(|| metric(&obs.barometric_pressure)),
+        precipitation: metric(&obs.precipitation_last_hour).unwrap_or(0.0),
+        // NWS observations don't report UV
+        uv_index: None,
+        is_day,
+        dew_point: metric(&obs.dewpoint),
+        visibility: metric(&obs.visibility),
+    })
+}
+
+fn current_from_period(period: &NwsPeriod) -> Option<CurrentWeather> {
+    let hour = hourly_from_period(period)?;
+    let humidity = period.relative_humidity.as_ref().and_then(|v| v.value);
+
+    Some(CurrentWeather {
+        temperature: hour.temperature,
+        apparent_temperature: hour.apparent_temperature,
+        humidity: humidity.map(|h| h.round() as i32),
+        weather_code: hour.weather_code,
+        wind_speed: hour.wind_speed,
+        wind_direction: hour.wind_direction.unwrap_or(0),
+        wind_gusts: None,
+        cloud_cover: None,
+        pressure: None,
+        precipitation: 0.0,
+        uv_index: None,
+        is_day: period.is_daytime,
+        dew_point: hour.dew_point,
+        visibility: None,
+    })
+}
+
+fn empty_current(is_day: bool) -> CurrentWeather {
+    CurrentWeather {
+        temperature: 0.0,
+        apparent_temperature: 0.0,
+        humidity: None,
+        weather_code: -1,
+        wind_speed: 0.0,
+        wind_direction: 0,
+        wind_gusts: None,
+        cloud_cover: None,
+        pressure: None,
+        precipitation: 0.0,
+        uv_index: None,
+        is_day,
+        dew_point: None,
+        visibility: None,
+    }
+}
+
+/// Degrees for a 16-point compass direction, e.g. "SW" is 225
+fn compass_degrees(point: &str) -> Option<i32> {
+    const POINTS: [&str; 16] = [
+        "N", "NNE", "NE", "ENE", "E", "ESE", "SE", "SSE", "S", "SSW", "SW", "WSW", "W", "WNW",
+        "NW", "NNW",
+    ];
+    let index = POINTS
+        .iter()
+        .position(|p| p.eq_ignore_ascii_case(point.trim()))?;
+    Some((index as f64 * 22.5).round() as i32)
+}
+
+/// Combine the 12-hour day/night periods with the hourly periods into one
+/// entry per calendar date. Day periods provide highs and conditions, night
+/// periods provide lows, and the hourly data fills in anything missing.
+fn build_daily(
+    periods: &[NwsPeriod],
+    hourly_periods: &[NwsPeriod],
+    hourly: &[HourlyForecast],
+    latitude: f64,
+    longitude: f64,
+) -> Vec<DailyForecast> {
+    let offset = periods
+        .iter()
+        .chain(hourly_periods)
+        .find_map(|p| parse_time(&p.start_time))
+        .map(|t| *t.offset());
+
+    let mut dates: Vec<NaiveDate> = Vec::new();
+    for period in periods {
+        if let Some(start) = parse_time(&period.start_time) {
+            let date = start.date_naive();
+            if !dates.contains(&date) {
+                dates.push(date);
+            }
+        }
+    }
+
+    let on_date = |p: &&NwsPeriod, date: NaiveDate, daytime: bool| {
+        p.is_daytime == daytime && parse_time(&p.start_time).map(|t| t.date_naive()) == Some(date)
+    };
+
+    dates
+        .into_iter()
+        .map(|date| {
+            let day = periods.iter().find(|p| on_date(p, date, true));
+            let night = periods.iter().find(|p| on_date(p, date, false));
+            let prefix = date.format("%Y-%m-%d").to_string();
+            let hours: Vec<&HourlyForecast> = hourly
+                .iter()
+                .filter(|h| h.time.starts_with(&prefix))
+                .collect();
+
+            let max_of = |f: fn(&HourlyForecast) -> f64| {
+                hours
+                    .iter()
+                    .map(|h| f(h))
+                    .fold(None, |acc: Option<f64>, v| {
+                        Some(acc.map_or(v, |a| a.max(v)))
+                    })
+            };
+            let min_of = |f: fn(&HourlyForecast) -> f64| {
+                hours
+                    .iter()
+                    .map(|h| f(h))
+                    .fold(None, |acc: Option<f64>, v| {
+                        Some(acc.map_or(v, |a| a.min(v)))
+                    })
+            };
+
+            let temp_max = day
+                .map(period_celsius)
+                .or_else(|| max_of(|h| h.temperature))
+                .or_else(|| night.map(period_celsius))
+                .unwrap_or(0.0);
+            let temp_min = night
+                .map(period_celsius)
+                .or_else(|| min_of(|h| h.temperature))
+                .unwrap_or(temp_max);
+
+            let precipitation_probability = day
+                .iter()
+                .chain(night.iter())
+                .map(|p| pop(p))
+                .chain(hours.iter().map(|h| h.precipitation_probability))
+                .flatten()
+                .max();
+
+            let wind_speed_max = day
+                .iter()
+                .chain(night.iter())
+                .map(|p| period_wind_kmh(p))
+                .chain(hours.iter().map(|h| h.wind_speed))
+                .fold(0.0, f64::max);
+
+            let (sunrise, sunset) = match (offset, sunrise_sunset(date, latitude, longitude)) {
+                (Some(offset), Some((rise, set))) => (
+                    rise.with_timezone(&offset)
+                        .format("%Y-%m-%dT%H:%M")
+                        .to_string(),
+                    set.with_timezone(&offset)
+                        .format("%Y-%m-%dT%H:%M")
+                        .to_string(),
+                ),
+                _ => (String::new(), String::new()),
+            };
+
+            DailyForecast {
+                date: prefix,
+                weather_code: day.or(night).map(period_code).unwrap_or(-1),
+                temp_max,
+                temp_min,
+                apparent_temp_max: max_of(|h| h.apparent_temperature).unwrap_or(temp_max),
+                apparent_temp_min: min_of(|h| h.apparent_temperature).unwrap_or(temp_min),
+                sunrise,
+                sunset,
+                precipitation_sum: 0.0,
+                precipitation_probability,
+                wind_speed_max,
+                uv_index_max: 0.0,
+            }
+        })
+        .collect()
+}
+
+/// Parse NWS wind text like "10 mph" or "5 to 15 mph" into km/h, taking the
+/// upper bound of a range
+pub fn parse_wind_speed(text: &str) -> f64 {
+    let max = text
+        .split_whitespace()
+        .filter_map(|word| word.parse::<f64>().ok())
+        .fold(0.0, f64::max);
+
+    if text.contains("km/h") {
+        max
+    } else if text.contains("kt") {
+        max * 1.852
+    } else {
+        max * 1.609344
+    }
+}
+
+/// Map an NWS icon URL (or, failing that, the short forecast text) to a WMO
+/// weather code so `WeatherCondition::from_wmo_code` can render it.
+pub fn nws_weather_code(icon: Option<&str>, short_forecast: &str) -> i32 {
+    icon.and_then(icon_condition)
+        .and_then(icon_code)
+        .unwrap_or_else(|| text_code(short_forecast))
+}
+
+/// Extract the first condition token from an icon URL such as
+/// `https://api.weather.gov/icons/land/day/tsra_sct,40/rain,60?size=small`
+fn icon_condition(icon: &str) -> Option<&str> {
+    let path = icon.split('?').next()?;
+    let mut segments = path.split('/');
+    segments.find(|s| *s == "day" || *s == "night")?;
+    let condition = segments.next()?;
+    condition.split(',').next()
+}
+
+fn icon_code(condition: &str) -> Option<i32> {
+    let condition = condition.strip_prefix("wind_").unwrap_or(condition);
+    Some(match condition {
+        "skc" | "hot" | "cold" => 0,
+        "few" => 1,
+        "sct" => 2,
+        "bkn" | "ovc" => 3,
+        "fog" | "haze" | "smoke" | "dust" => 45,
+        "rain" => 63,
+        "rain_showers" | "rain_showers_hi" => 80,
+        "fzra" | "rain_fzra" | "snow_fzra" => 66,
+        "rain_snow" | "rain_sleet" | "snow_sleet" | "sleet" => 68,
+        "snow" => 73,
+        "blizzard" => 75,
+        "tsra" | "tsra_sct" | "tsra_hi" => 95,
+        "tornado" | "hurricane" | "tropical_storm" => 99,
+        _ => return None,
+    })
+}
+
+fn text_code(text: &str) -> i32 {
+    let text = text.to_lowercase();
+    let heavy = text.contains("heavy");
+
+    if text.contains("thunder") {
+        95
+    } else if text.contains("blizzard") || (heavy && text.contains("snow")) {
+        75
+    } else if text.contains("freezing") {
+        66
+    } else if text.contains("sleet") || text.contains("rain and snow") {
+        if heavy {
+            69
+        } else {
+            68
+        }
+    } else if text.contains("snow") || text.contains("flurries") {
+        73
+    } else if text.contains("drizzle") {
+        53
+    } else if text.contains("shower") {
+        80
+    } else if text.contains("rain") {
+        if heavy {
+            65
+        } else {
+            63
+        }
+    } else if text.contains("fog") || text.contains("haze") || text.contains("smoke") {
+        45
+    } else if text.contains("partly") || text.contains("mostly cloudy") {
+        2
+    } else if text.contains("cloudy") || text.contains("overcast") {
+        3
+    } else if text.contains("mostly") {
+        1
+    } else if text.contains("sunny") || text.contains("clear") || text.contains("fair") {
+        0
+    } else {
+        -1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_icon_condition() {
+        assert_eq!(
+            icon_condition("https://api.weather.gov/icons/land/day/tsra_sct,40/rain,60?size=small"),
+            Some("tsra_sct")
+        );
+        assert_eq!(
+            icon_condition("https://api.weather.gov/icons/land/night/skc?size=medium"),
+            Some("skc")
+        );
+        assert_eq!(icon_condition("not-an-icon"), None);
+    }
+
+    #[test]
+    fn test_icon_weather_codes() {
+        let code = |c: &str| {
+            nws_weather_code(
+                Some(&format!("https://api.weather.gov/icons/land/day/{c}")),
+                "",
+            )
+        };
+        assert_eq!(code("skc"), 0);
+        assert_eq!(code("few"), 1);
+        assert_eq!(code("sct"), 2);
+        assert_eq!(code("bkn"), 3);
+        assert_eq!(code("ovc"), 3);
+        assert_eq!(code("wind_ovc"), 3);
+        assert_eq!(code("rain_showers,30"), 80);
+        assert_eq!(code("snow"), 73);
+        assert_eq!(code("fzra"), 66);
+        assert_eq!(code("rain_snow,50"), 68);
+        assert_eq!(code("sleet"), 68);
+        assert_eq!(code("tsra_hi,20"), 95);
+        assert_eq!(code("fog"), 45);
+    }
+
+    #[test]
+    fn test_text_fallback() {
+        assert_eq!(nws_weather_code(None, "Sunny"), 0);
+        assert_eq!(nws_weather_code(None, "Mostly Sunny"), 1);
+        assert_eq!(nws_weather_code(None, "Partly Cloudy"), 2);
+        assert_eq!(nws_weather_code(None, "Cloudy"), 3);
+        assert_eq!(nws_weather_code(None, "Chance Rain Showers"), 80);
+        assert_eq!(nws_weather_code(None, "Heavy Rain"), 65);
+        assert_eq!(nws_weather_code(None, "Slight Chance Thunderstorms"), 95);
+        assert_eq!(nws_weather_code(None, "Patchy Fog"), 45);
+        assert_eq!(nws_weather_code(None, "Rain And Snow Likely"), 68);
+        assert_eq!(nws_weather_code(None, "Chance Sleet"), 68);
+        assert_eq!(nws_weather_code(None, "Heavy Sleet"), 69);
+        // Unknown icon tokens fall back to the text
+        assert_eq!(
+            nws_weather_code(
+                Some("https://api.weather.gov/icons/land/day/xyz"),
+                "Snow Likely"
+            ),
+            73
+        );
+    }
+
+    #[test]
+    fn test_parse_wind_speed() {
+        assert!((parse_wind_speed("10 mph") - 16.09).abs() < 0.01);
+        assert!((parse_wind_speed("5 to 15 mph") - 24.14).abs() < 0.01);
+        assert_eq!(parse_wind_speed("20 km/h"), 20.0);
+        assert_eq!(parse_wind_speed(""), 0.0);
+    }
+
+    #[test]
+    fn test_value_conversion() {
+        let value = |v: f64, unit: &str| NwsValue {
+            value: Some(v),
+            unit_code: unit.to_string(),
+        };
+        assert_eq!(value(20.0, "wmoUnit:degC").metric(), Some(20.0));
+        assert!((value(68.0, "wmoUnit:degF").metric().unwrap() - 20.0).abs() < 0.001);
+        assert_eq!(value(101325.0, "wmoUnit:Pa").metric(), Some(1013.25));
+        assert_eq!(value(10.0, "wmoUnit:m_s-1").metric(), Some(36.0));
+        assert_eq!(
+            NwsValue {
+                value: None,
+                unit_code: "wmoUnit:degC".to_string()
+            }
+            .metric(),
+            None
+        );
+    }
+}
@@ -0,0 +1,294 @@
+use chrono::{DateTime, NaiveDateTime};
+use serde::Deserialize;
+
+use crate::models::weather::{
+    CurrentWeather, DailyForecast, HourlyForecast, WeatherData, SNOW_CM_PER_MM,
+};
+
+const KELVIN_OFFSET: f64 = 273.15;
+
+/// Response from the OpenWeatherMap One Call 3.0 API, requested with
+/// `units=standard` (Kelvin, m/s, hPa, mm)
+#[derive(Debug, Clone, Deserialize)]
+pub struct OwmOneCallResponse {
+    /// Shift in seconds from UTC for the requested location
+    pub timezone_offset: i64,
+    pub current: OwmCurrent,
+    #[serde(default)]
+    pub hourly: Vec<OwmHourly>,
+    #[serde(default)]
+    pub daily: Vec<OwmDaily>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct OwmCurrent {
+    pub temp: f64,
+    pub feels_like: f64,
+    pub pressure: f64,
+    pub humidity: i32,
+    pub uvi: f64,
+    pub clouds: i32,
+    pub wind_speed: f64,
+    pub wind_deg: i32,
+    pub wind_gust: Option<f64>,
+    pub weather: Vec<OwmCondition>,
+    pub rain: Option<OwmVolume>,
+    pub snow: Option<OwmVolume>,
+    /// Kelvin
+    pub dew_point: Option<f64>,
+    /// Metres
+    pub visibility: Option<f64>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct OwmHourly {
+    pub dt: i64,
+    pub temp: f64,
+    pub feels_like: f64,
+    pub wind_speed: f64,
+    pub weather: Vec<OwmCondition>,
+    /// Probability of precipitation, 0.0 to 1.0
+    #[serde(default)]
+    pub pop: f64,
+    pub rain: Option<OwmVolume>,
+    pub snow: Option<OwmVolume>,
+    pub humidity: Option<i32>,
+    /// Kelvin
+    pub dew_point: Option<f64>,
+    pub wind_deg: Option<i32>,
+    pub wind_gust: Option<f64>,
+    pub clouds: Option<i32>,
+    /// Metres
+    pub visibility: Option<f64>,
+    pub uvi: Option<f64>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct OwmDaily {
+    pub dt: i64,
+    pub sunrise: i64,
+    pub sunset: i64,
+    pub temp: OwmDailyTemp,
+    pub feels_like: OwmDailyFeelsLike,
+    pub wind_speed: f64,
+    pub weather: Vec<OwmCondition>,
+    #[serde(default)]
+    pub pop: f64,
+    /// Daily totals are plain numbers rather than `{"1h": ..}` objects
+    pub rain: Option<f64>,
+    pub snow: Option<f64>,
+    pub uvi: f64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct OwmDailyTemp {
+    pub min: f64,
+    pub max: f64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct OwmDailyFeelsLike {
+    pub day: f64,
+    pub night: f64,
+    pub eve: f64,
+    pub morn: f64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct OwmCondition {
+    pub id: i32,
+    pub icon: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct OwmVolume {
+    #[serde(rename = "1h", default)]
+    pub one_hour: f64,
+}
+
+/// Error body returned alongside non-2xx statuses
+#[derive(Debug, Clone, Deserialize)]
+pub struct OwmError {
+    pub message: String,
+}
+
+impl From<OwmOneCallResponse> for WeatherData {
+    fn from(resp: OwmOneCallResponse) -> Self {
+        let offset = resp.timezone_offset;
+        let local = |ts: i64| -> String {
+            local_time(ts, offset)
+                .map(|t| t.format("%Y-%m-%dT%H:%M").to_string())
+                .unwrap_or_default()
+        };
+
+        let condition = resp.current.weather.first();
+        let wind_speed = ms_to_kmh(resp.current.wind_speed);
+        let current = CurrentWeather {
+            temperature: kelvin_to_celsius(resp.current.temp),
+            apparent_temperature: kelvin_to_celsius(resp.current.feels_like),
+            humidity: Some(resp.current.humidity),
+            weather_code: condition.map(|c| owm_to_wmo(c.id)).unwrap_or(-1),
+            wind_speed,
+            wind_direction: resp.current.wind_deg,
+            wind_gusts: resp.current.wind_gust.map(ms_to_kmh),
+            cloud_cover: Some(resp.current.clouds),
+            pressure: Some(resp.current.pressure),
+            precipitation: volume(&resp.current.rain) + volume(&resp.current.snow),
+            uv_index: Some(resp.current.uvi),
+            is_day: condition.map(|c| !c.icon.ends_with('n')).unwrap_or(true),
+            dew_point: resp.current.dew_point.map(kelvin_to_celsius),
+            visibility: resp.current.visibility,
+        };
+
+        let hourly = resp
+            .hourly
+            .iter()
+            .map(|h| HourlyForecast {
+                time: local(h.dt),
+                temperature: kelvin_to_celsius(h.temp),
+                apparent_temperature: kelvin_to_celsius(h.feels_like),
+                precipitation_probability: Some((h.pop * 100.0).round() as i32),
+                precipitation: volume(&h.rain) + volume(&h.snow),
+                weather_code: h.weather.first().map(|c| owm_to_wmo(c.id)).unwrap_or(-1),
+                wind_speed: ms_to_kmh(h.wind_speed),
+                humidity: h.humidity,
+                dew_point: h.dew_point.map(kelvin_to_celsius),
+                wind_direction: h.wind_deg,
+                wind_gusts: h.wind_gust.map(ms_to_kmh),
+                cloud_cover: h.clouds,
+                visibility: h.visibility,
+                uv_index: h.uvi,
+                // Snow is reported as its water equivalent
+                snowfall: h.snow.as_ref().map(|s| s.one_hour * SNOW_CM_PER_MM),
+            })
+            .collect();
+
+        let daily = resp
+            .daily
+            .iter()
+            .map(|d| {
+                let feels = [
+                    d.feels_like.morn,
+                    d.feels_like.day,
+                    d.feels_like.eve,
+                    d.feels_like.night,
+                ];
+                DailyForecast {
+                    date: local_time(d.dt, offset)
+                        .map(|t| t.format("%Y-%m-%d").to_string())
+                        .unwrap_or_default(),
+                    weather_code: d.weather.first().map(|c| owm_to_wmo(c.id)).unwrap_or(-1),
+                    temp_max: kelvin_to_celsius(d.temp.max),
+                    temp_min: kelvin_to_celsius(d.temp.min),
+                    apparent_temp_max: kelvin_to_celsius(
+                        feels.iter().cloned().fold(f64::NEG_INFINITY, f64::max),
+                    ),
+                    apparent_temp_min: kelvin_to_celsius(
+                        feels.iter().cloned().fold(f64::INFINITY, f64::min),
+                    ),
+                    sunrise: local(d.sunrise),
+                    sunset: local(d.sunset),
+                    precipitation_sum: d.rain.unwrap_or(0.0) + d.snow.unwrap_or(0.0),
+                    precipitation_probability: Some((d.pop * 100.0).round() as i32),
+                    wind_speed_max: ms_to_kmh(d.wind_speed),
+                    uv_index_max: d.uvi,
+                }
+            })
+            .collect();
+
+        Self {
+            current,
+            hourly,
+            daily,
+            minutely: Vec::new(),
+        }
+    }
+}
+
+fn local_time(timestamp: i64, offset_seconds: i64) -> Option<NaiveDateTime> {
+    DateTime::from_timestamp(timestamp + offset_seconds, 0).map(|t| t.naive_utc())
+}
+
+fn volume(v: &Option<OwmVolume>) -> f64 {
+    v.as_ref().map(|v| v.one_hour).unwrap_or(0.0)
+}
+
+pub fn kelvin_to_celsius(kelvin: f64) -> f64 {
+    kelvin - KELVIN_OFFSET
+}
+
+pub fn ms_to_kmh(ms: f64) -> f64 {
+    ms * 3.6
+}
+
+/// Map an OpenWeatherMap condition ID to the closest WMO weather code
+pub fn owm_to_wmo(id: i32) -> i32 {
+    match id {
+        200..=299 => 95,
+        300 => 51,
+        301 | 310 | 311 => 53,
+        302 | 312..=314 => 55,
+        321 => 80,
+        500 => 61,
+        501 => 63,
+        502..=504 => 65,
+        511 => 66,
+        520 => 80,
+        521 | 531 => 81,
+        522 => 82,
+        600 => 71,
+        601 => 73,
+        602 => 75,
+        611..=616 => 66,
+        620 | 621 => 85,
+        622 => 86,
+        781 => 99,
+        701..=771 => 45,
+        800 => 0,
+        801 => 1,
+        802 | 803 => 2,
+        804 => 3,
+        _ => -1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unit_conversions() {
+        assert!((kelvin_to_celsius(273.15) - 0.0).abs() < 0.001);
+        assert!((kelvin_to_celsius(293.15) - 20.0).abs() < 0.001);
+        assert!((ms_to_kmh(10.0) - 36.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_condition_codes() {
+        assert_eq!(owm_to_wmo(800), 0);
+        assert_eq!(owm_to_wmo(801), 1);
+        assert_eq!(owm_to_wmo(803), 2);
+        assert_eq!(owm_to_wmo(804), 3);
+        assert_eq!(owm_to_wmo(211), 95);
+        assert_eq!(owm_to_wmo(300), 51);
+        assert_eq!(owm_to_wmo(500), 61);
+        assert_eq!(owm_to_wmo(502), 65);
+        assert_eq!(owm_to_wmo(511), 66);
+        assert_eq!(owm_to_wmo(521), 81);
+        assert_eq!(owm_to_wmo(601), 73);
+        assert_eq!(owm_to_wmo(622), 86);
+        assert_eq!(owm_to_wmo(741), 45);
+        assert_eq!(owm_to_wmo(781), 99);
+        assert_eq!(owm_to_wmo(999), -1);
+    }
+
+    #[test]
+    fn test_local_time_applies_offset() {
+        // 2024-06-01T12:00:00Z at UTC-5
+        let local = local_time(1717243200, -18000).unwrap();
+        assert_eq!(
+            local.format("%Y-%m-%dT%H:%M").to_string(),
+            "2024-06-01T07:00"
+        );
+    }
+}
@@ -0,0 +1,78 @@
+use chrono::{DateTime, NaiveDate, Utc};
+
+const J2000: f64 = 2451545.0;
+const UNIX_EPOCH_JD: f64 = 2440587.5;
+
+/// Sunrise and sunset in UTC for a date and position, using the standard
+/// sunrise equation. Returns `None` during polar day or polar night.
+/// Used by providers whose feeds don't include sun times.
+pub fn sunrise_sunset(
+    date: NaiveDate,
+    latitude: f64,
+    longitude: f64,
+) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+    let epoch = NaiveDate::from_ymd_opt(1970, 1, 1)?;
+    let jd_noon = (date - epoch).num_days() as f64 + UNIX_EPOCH_JD + 0.5;
+    let n = (jd_noon - J2000 + 0.0008).round();
+
+    // Mean solar time, solar anomaly, equation of center
+    let j_star = n - longitude / 360.0;
+    let m = (357.5291 + 0.98560028 * j_star).rem_euclid(360.0);
+    let m_rad = m.to_radians();
+    let c = 1.9148 * m_rad.sin() + 0.02 * (2.0 * m_rad).sin() + 0.0003 * (3.0 * m_rad).sin();
+
+    // Ecliptic longitude and solar transit
+    let lambda = (m + c + 180.0 + 102.9372).rem_euclid(360.0).to_radians();
+    let transit = J2000 + j_star + 0.0053 * m_rad.sin() - 0.0069 * (2.0 * lambda).sin();
+
+    // Declination and hour angle (-0.833° accounts for refraction and the solar disc)
+    let sin_decl = lambda.sin() * 23.4397_f64.to_radians().sin();
+    let cos_decl = sin_decl.asin().cos();
+    let lat = latitude.to_radians();
+    let cos_omega =
+        ((-0.833_f64).to_radians().sin() - lat.sin() * sin_decl) / (lat.cos() * cos_decl);
+    if !(-1.0..=1.0).contains(&cos_omega) {
+        return None;
+    }
+    let omega = cos_omega.acos().to_degrees();
+
+    let to_utc = |jd: f64| DateTime::from_timestamp(((jd - UNIX_EPOCH_JD) * 86400.0) as i64, 0);
+    Some((
+        to_utc(transit - omega / 360.0)?,
+        to_utc(transit + omega / 360.0)?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn minutes_apart(a: DateTime<Utc>, b: DateTime<Utc>) -> i64 {
+        (a - b).num_minutes().abs()
+    }
+
+    #[test]
+    fn test_new_york_summer_solstice() {
+        let date = NaiveDate::from_ymd_opt(2024, 6, 21).unwrap();
+        let (rise, set) = sunrise_sunset(date, 40.7128, -74.0060).unwrap();
+        // 5:25am / 8:31pm EDT
+        assert!(minutes_apart(rise, Utc.with_ymd_and_hms(2024, 6, 21, 9, 25, 0).unwrap()) <= 3);
+        assert!(minutes_apart(set, Utc.with_ymd_and_hms(2024, 6, 22, 0, 31, 0).unwrap()) <= 3);
+    }
+
+    #[test]
+    fn test_oslo_winter() {
+        let date = NaiveDate::from_ymd_opt(2024, 12, 21).unwrap();
+        let (rise, set) = sunrise_sunset(date, 59.9139, 10.7522).unwrap();
+        // 9:18am / 3:12pm CET
+        assert!(minutes_apart(rise, Utc.with_ymd_and_hms(2024, 12, 21, 8, 18, 0).unwrap()) <= 3);
+        assert!(minutes_apart(set, Utc.with_ymd_and_hms(2024, 12, 21, 14, 12, 0).unwrap()) <= 3);
+    }
+
+    #[test]
+    fn test_polar_night() {
+        let date = NaiveDate::from_ymd_opt(2024, 12, 21).unwrap();
+        assert!(sunrise_sunset(date, 78.22, 15.65).is_none());
+    }
+}
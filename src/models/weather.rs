@@ -1,41 +1,80 @@
-use serde::Deserialize;
+use chrono::{NaiveDateTime, TimeDelta};
+use serde::{Deserialize, Serialize};
 
 /// Complete weather data from Open-Meteo API
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WeatherData {
     pub current: CurrentWeather,
     pub hourly: Vec<HourlyForecast>,
     pub daily: Vec<DailyForecast>,
+    /// Precipitation in 15-minute steps for the next few hours, where the
+    /// provider has it
+    #[serde(default)]
+    pub minutely: Vec<MinutelyPrecipitation>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CurrentWeather {
     pub temperature: f64,
     pub apparent_temperature: f64,
-    pub humidity: i32,
+    /// The readings below are `None` where the provider doesn't report them
+    #[serde(default)]
+    pub humidity: Option<i32>,
     pub weather_code: i32,
     pub wind_speed: f64,
     pub wind_direction: i32,
-    pub wind_gusts: f64,
-    pub cloud_cover: i32,
-    pub pressure: f64,
+    #[serde(default)]
+    pub wind_gusts: Option<f64>,
+    #[serde(default)]
+    pub cloud_cover: Option<i32>,
+    /// Hectopascals
+    #[serde(default)]
+    pub pressure: Option<f64>,
     pub precipitation: f64,
-    pub uv_index: f64,
+    #[serde(default)]
+    pub uv_index: Option<f64>,
     pub is_day: bool,
+    #[serde(default)]
+    pub dew_point: Option<f64>,
+    /// Metres
+    #[serde(default)]
+    pub visibility: Option<f64>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HourlyForecast {
     pub time: String,
     pub temperature: f64,
     pub apparent_temperature: f64,
-    pub precipitation_probability: i32,
+    /// `None` where the provider or model doesn't forecast a chance of rain
+    #[serde(default)]
+    pub precipitation_probability: Option<i32>,
     pub precipitation: f64,
     pub weather_code: i32,
     pub wind_speed: f64,
+    // Not every provider forecasts these hour by hour, and older caches
+    // predate them
+    #[serde(default)]
+    pub humidity: Option<i32>,
+    #[serde(default)]
+    pub dew_point: Option<f64>,
+    #[serde(default)]
+    pub wind_direction: Option<i32>,
+    #[serde(default)]
+    pub wind_gusts: Option<f64>,
+    #[serde(default)]
+    pub cloud_cover: Option<i32>,
+    /// Metres
+    #[serde(default)]
+    pub visibility: Option<f64>,
+    #[serde(default)]
+    pub uv_index: Option<f64>,
+    /// Centimetres of fresh snow
+    #[serde(default)]
+    pub snowfall: Option<f64>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DailyForecast {
     pub date: String,
     pub weather_code: i32,
@@ -46,124 +85,314 @@ pub struct DailyForecast {
     pub sunrise: String,
     pub sunset: String,
     pub precipitation_sum: f64,
-    pub precipitation_probability: i32,
+    #[serde(default)]
+    pub precipitation_probability: Option<i32>,
     pub wind_speed_max: f64,
     pub uv_index_max: f64,
 }
 
+/// Windows (hours) for the rolling precipitation totals
+pub const PRECIPITATION_WINDOWS: [i64; 3] = [24, 48, 72];
+
+impl WeatherData {
+    /// Precipitation (mm) over the `hours` leading up to `now`, or None when
+    /// the hourly data doesn't reach back that far, as with providers that
+    /// only forecast
+    pub fn precipitation_over(&self, hours: i64, now: NaiveDateTime) -> Option<f64> {
+        let start = now - TimeDelta::hours(hours);
+        let mut covered = false;
+        let mut total = 0.0;
+        for hour in &self.hourly {
+            let Ok(time) = NaiveDateTime::parse_from_str(&hour.time, "%Y-%m-%dT%H:%M") else {
+                continue;
+            };
+            // Each amount is for the hour leading up to its time
+            covered |= time - TimeDelta::hours(1) <= start;
+            if time > start && time <= now {
+                total += hour.precipitation;
+            }
+        }
+        covered.then_some(total)
+    }
+}
+
+/// Precipitation over the 15 minutes leading up to `time`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MinutelyPrecipitation {
+    pub time: String,
+    /// Water equivalent in mm
+    pub precipitation: f64,
+    /// Snow depth in cm
+    pub snowfall: f64,
+}
+
 /// Raw API response from Open-Meteo
 #[derive(Debug, Clone, Deserialize)]
 pub struct OpenMeteoResponse {
     pub current: OpenMeteoCurrent,
     pub hourly: OpenMeteoHourly,
     pub daily: OpenMeteoDaily,
+    /// Missing for models without 15-minute data
+    #[serde(default)]
+    pub minutely_15: Option<OpenMeteoMinutely15>,
+}
+
+/// The part of a forecast response naming the IANA timezone Open-Meteo
+/// picked for the coordinates with `timezone=auto`
+#[derive(Debug, Clone, Deserialize)]
+pub struct OpenMeteoTimezone {
+    pub timezone: String,
 }
 
+/// Nullable like the hourly series; a single model may not report the
+/// current conditions at all
 #[derive(Debug, Clone, Deserialize)]
 pub struct OpenMeteoCurrent {
-    pub temperature_2m: f64,
-    pub relative_humidity_2m: i32,
-    pub apparent_temperature: f64,
-    pub precipitation: f64,
-    pub weather_code: i32,
-    pub wind_speed_10m: f64,
-    pub wind_direction_10m: i32,
-    pub wind_gusts_10m: f64,
-    pub cloud_cover: i32,
-    pub pressure_msl: f64,
-    pub uv_index: f64,
-    pub is_day: i32,
+    pub time: String,
+    #[serde(default)]
+    pub temperature_2m: Option<f64>,
+    #[serde(default)]
+    pub relative_humidity_2m: Option<i32>,
+    #[serde(default)]
+    pub apparent_temperature: Option<f64>,
+    #[serde(default)]
+    pub precipitation: Option<f64>,
+    #[serde(default)]
+    pub weather_code: Option<i32>,
+    #[serde(default)]
+    pub wind_speed_10m: Option<f64>,
+    #[serde(default)]
+    pub wind_direction_10m: Option<i32>,
+    #[serde(default)]
+    pub wind_gusts_10m: Option<f64>,
+    #[serde(default)]
+    pub cloud_cover: Option<i32>,
+    #[serde(default)]
+    pub pressure_msl: Option<f64>,
+    #[serde(default)]
+    pub uv_index: Option<f64>,
+    #[serde(default)]
+    pub is_day: Option<i32>,
+    #[serde(default)]
+    pub dew_point_2m: Option<f64>,
+    #[serde(default)]
+    pub visibility: Option<f64>,
 }
 
+/// Any variable can be null for hours a single model (`models=...`)
+/// doesn't cover, or leave out, such as precipitation probability
 #[derive(Debug, Clone, Deserialize)]
 pub struct OpenMeteoHourly {
     pub time: Vec<String>,
-    pub temperature_2m: Vec<f64>,
-    pub apparent_temperature: Vec<f64>,
-    pub precipitation_probability: Vec<i32>,
-    pub precipitation: Vec<f64>,
-    pub weather_code: Vec<i32>,
-    pub wind_speed_10m: Vec<f64>,
+    pub temperature_2m: Vec<Option<f64>>,
+    pub apparent_temperature: Vec<Option<f64>>,
+    pub precipitation_probability: Vec<Option<i32>>,
+    pub precipitation: Vec<Option<f64>>,
+    pub weather_code: Vec<Option<i32>>,
+    pub wind_speed_10m: Vec<Option<f64>>,
+    // Some models leave these out entirely
+    #[serde(default)]
+    pub relative_humidity_2m: Vec<Option<i32>>,
+    #[serde(default)]
+    pub dew_point_2m: Vec<Option<f64>>,
+    #[serde(default)]
+    pub wind_direction_10m: Vec<Option<i32>>,
+    #[serde(default)]
+    pub wind_gusts_10m: Vec<Option<f64>>,
+    #[serde(default)]
+    pub cloud_cover: Vec<Option<i32>>,
+    #[serde(default)]
+    pub visibility: Vec<Option<f64>>,
+    #[serde(default)]
+    pub uv_index: Vec<Option<f64>>,
+    #[serde(default)]
+    pub snowfall: Vec<Option<f64>>,
 }
 
+/// Nullable like the hourly series, except the dates and sun times, which
+/// don't depend on the model
 #[derive(Debug, Clone, Deserialize)]
 pub struct OpenMeteoDaily {
     pub time: Vec<String>,
-    pub weather_code: Vec<i32>,
-    pub temperature_2m_max: Vec<f64>,
-    pub temperature_2m_min: Vec<f64>,
-    pub apparent_temperature_max: Vec<f64>,
-    pub apparent_temperature_min: Vec<f64>,
+    pub weather_code: Vec<Option<i32>>,
+    pub temperature_2m_max: Vec<Option<f64>>,
+    pub temperature_2m_min: Vec<Option<f64>>,
+    pub apparent_temperature_max: Vec<Option<f64>>,
+    pub apparent_temperature_min: Vec<Option<f64>>,
     pub sunrise: Vec<String>,
     pub sunset: Vec<String>,
-    pub precipitation_sum: Vec<f64>,
-    pub precipitation_probability_max: Vec<i32>,
-    pub wind_speed_10m_max: Vec<f64>,
-    pub uv_index_max: Vec<f64>,
+    pub precipitation_sum: Vec<Option<f64>>,
+    pub precipitation_probability_max: Vec<Option<i32>>,
+    pub wind_speed_10m_max: Vec<Option<f64>>,
+    pub uv_index_max: Vec<Option<f64>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct OpenMeteoMinutely15 {
+    pub time: Vec<String>,
+    pub precipitation: Vec<Option<f64>>,
+    #[serde(default)]
+    pub snowfall: Vec<Option<f64>>,
 }
 
 impl From<OpenMeteoResponse> for WeatherData {
     fn from(resp: OpenMeteoResponse) -> Self {
-        let current = CurrentWeather {
-            temperature: resp.current.temperature_2m,
-            apparent_temperature: resp.current.apparent_temperature,
-            humidity: resp.current.relative_humidity_2m,
-            weather_code: resp.current.weather_code,
-            wind_speed: resp.current.wind_speed_10m,
-            wind_direction: resp.current.wind_direction_10m,
-            wind_gusts: resp.current.wind_gusts_10m,
-            cloud_cover: resp.current.cloud_cover,
-            pressure: resp.current.pressure_msl,
-            precipitation: resp.current.precipitation,
-            uv_index: resp.current.uv_index,
-            is_day: resp.current.is_day == 1,
-        };
-
-        let hourly: Vec<HourlyForecast> = resp
-            .hourly
+        let h = &resp.hourly;
+        let at = |values: &[Option<f64>], i: usize| values.get(i).copied().flatten();
+        let at_int = |values: &[Option<i32>], i: usize| values.get(i).copied().flatten();
+        // Hours without a temperature have nothing worth showing; other gaps
+        // fall back to the feels-like matching the temperature, an unknown
+        // condition, or zero
+        let hourly: Vec<HourlyForecast> = h
             .time
             .iter()
             .enumerate()
-            .map(|(i, time)| HourlyForecast {
-                time: time.clone(),
-                temperature: resp.hourly.temperature_2m[i],
-                apparent_temperature: resp.hourly.apparent_temperature[i],
-                precipitation_probability: resp.hourly.precipitation_probability[i],
-                precipitation: resp.hourly.precipitation[i],
-                weather_code: resp.hourly.weather_code[i],
-                wind_speed: resp.hourly.wind_speed_10m[i],
+            .filter_map(|(i, time)| {
+                let temperature = at(&h.temperature_2m, i)?;
+                Some(HourlyForecast {
+                    time: time.clone(),
+                    temperature,
+                    apparent_temperature: at(&h.apparent_temperature, i).unwrap_or(temperature),
+                    precipitation_probability: at_int(&h.precipitation_probability, i),
+                    precipitation: at(&h.precipitation, i).unwrap_or(0.0),
+                    weather_code: at_int(&h.weather_code, i).unwrap_or(-1),
+                    wind_speed: at(&h.wind_speed_10m, i).unwrap_or(0.0),
+                    humidity: at_int(&h.relative_humidity_2m, i),
+                    dew_point: at(&h.dew_point_2m, i),
+                    wind_direction: at_int(&h.wind_direction_10m, i),
+                    wind_gusts: at(&h.wind_gusts_10m, i),
+                    cloud_cover: at_int(&h.cloud_cover, i),
+                    visibility: at(&h.visibility, i),
+                    uv_index: at(&h.uv_index, i),
+                    snowfall: at(&h.snowfall, i),
+                })
             })
             .collect();
 
-        let daily: Vec<DailyForecast> = resp
-            .daily
+        // Gaps in the current conditions are filled from the forecast for
+        // the hour they fall in, the latest hour with data
+        let c = &resp.current;
+        let hour = hourly
+            .iter()
+            .rev()
+            .find(|h| h.time <= c.time)
+            .or(hourly.first());
+        let temperature = c
+            .temperature_2m
+            .or(hour.map(|h| h.temperature))
+            .unwrap_or_default();
+        let current = CurrentWeather {
+            temperature,
+            apparent_temperature: c
+                .apparent_temperature
+                .or(hour.map(|h| h.apparent_temperature))
+                .unwrap_or(temperature),
+            humidity: c.relative_humidity_2m.or(hour.and_then(|h| h.humidity)),
+            weather_code: c
+                .weather_code
+                .or(hour.map(|h| h.weather_code))
+                .unwrap_or(-1),
+            wind_speed: c
+                .wind_speed_10m
+                .or(hour.map(|h| h.wind_speed))
+                .unwrap_or_default(),
+            wind_direction: c
+                .wind_direction_10m
+                .or(hour.and_then(|h| h.wind_direction))
+                .unwrap_or_default(),
+            wind_gusts: c.wind_gusts_10m.or(hour.and_then(|h| h.wind_gusts)),
+            cloud_cover: c.cloud_cover.or(hour.and_then(|h| h.cloud_cover)),
+            pressure: c.pressure_msl,
+            precipitation: c.precipitation.unwrap_or_default(),
+            uv_index: c.uv_index.or(hour.and_then(|h| h.uv_index)),
+            is_day: c.is_day.is_none_or(|d| d == 1),
+            dew_point: c.dew_point_2m.or(hour.and_then(|h| h.dew_point)),
+            visibility: c.visibility.or(hour.and_then(|h| h.visibility)),
+        };
+
+        // Likewise for days without a high and low
+        let d = &resp.daily;
+        let daily: Vec<DailyForecast> = d
             .time
             .iter()
             .enumerate()
-            .map(|(i, date)| DailyForecast {
-                date: date.clone(),
-                weather_code: resp.daily.weather_code[i],
-                temp_max: resp.daily.temperature_2m_max[i],
-                temp_min: resp.daily.temperature_2m_min[i],
-                apparent_temp_max: resp.daily.apparent_temperature_max[i],
-                apparent_temp_min: resp.daily.apparent_temperature_min[i],
-                sunrise: resp.daily.sunrise[i].clone(),
-                sunset: resp.daily.sunset[i].clone(),
-                precipitation_sum: resp.daily.precipitation_sum[i],
-                precipitation_probability: resp.daily.precipitation_probability_max[i],
-                wind_speed_max: resp.daily.wind_speed_10m_max[i],
-                uv_index_max: resp.daily.uv_index_max[i],
+            .filter_map(|(i, date)| {
+                let temp_max = at(&d.temperature_2m_max, i)?;
+                let temp_min = at(&d.temperature_2m_min, i)?;
+                Some(DailyForecast {
+                    date: date.clone(),
+                    weather_code: at_int(&d.weather_code, i).unwrap_or(-1),
+                    temp_max,
+                    temp_min,
+                    apparent_temp_max: at(&d.apparent_temperature_max, i).unwrap_or(temp_max),
+                    apparent_temp_min: at(&d.apparent_temperature_min, i).unwrap_or(temp_min),
+                    sunrise: d.sunrise.get(i).cloned().unwrap_or_default(),
+                    sunset: d.sunset.get(i).cloned().unwrap_or_default(),
+                    precipitation_sum: at(&d.precipitation_sum, i).unwrap_or(0.0),
+                    precipitation_probability: at_int(&d.precipitation_probability_max, i),
+                    wind_speed_max: at(&d.wind_speed_10m_max, i).unwrap_or(0.0),
+                    uv_index_max: at(&d.uv_index_max, i).unwrap_or(0.0),
+                })
             })
             .collect();
 
+        // Outside the regions with 15-minute models the values are
+        // interpolated from hourly data, and gaps come back as null
+        let minutely = resp
+            .minutely_15
+            .map(|m| {
+                m.time
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(i, time)| {
+                        Some(MinutelyPrecipitation {
+                            time: time.clone(),
+                            precipitation: m.precipitation.get(i).copied().flatten()?,
+                            snowfall: m.snowfall.get(i).copied().flatten().unwrap_or(0.0),
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
         Self {
             current,
             hourly,
             daily,
+            minutely,
         }
     }
 }
 
+/// Centimetres of fresh snow per millimetre of water, the ratio Open-Meteo
+/// uses for its snowfall
+pub const SNOW_CM_PER_MM: f64 = 0.7;
+
+/// Feels-like temperature from wind chill or heat index where they apply
+pub fn apparent_temperature(temp_c: f64, humidity: Option<f64>, wind_kmh: f64) -> f64 {
+    if temp_c <= 10.0 && wind_kmh > 4.8 {
+        let v = wind_kmh.powf(0.16);
+        return 13.12 + 0.6215 * temp_c - 11.37 * v + 0.3965 * temp_c * v;
+    }
+
+    if let Some(rh) = humidity {
+        if temp_c >= 26.7 {
+            // Rothfusz regression, defined in Fahrenheit
+            let t = temp_c * 9.0 / 5.0 + 32.0;
+            let hi = -42.379 + 2.04901523 * t + 10.14333127 * rh
+                - 0.22475541 * t * rh
+                - 0.00683783 * t * t
+                - 0.05481717 * rh * rh
+                + 0.00122874 * t * t * rh
+                + 0.00085282 * t * rh * rh
+                - 0.00000199 * t * t * rh * rh;
+            return (hi - 32.0) * 5.0 / 9.0;
+        }
+    }
+
+    temp_c
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -171,18 +400,21 @@ mod tests {
     fn create_test_response() -> OpenMeteoResponse {
         OpenMeteoResponse {
             current: OpenMeteoCurrent {
-                temperature_2m: 20.5,
-                relative_humidity_2m: 65,
-                apparent_temperature: 19.0,
-                precipitation: 0.5,
-                weather_code: 3,
-                wind_speed_10m: 15.0,
-                wind_direction_10m: 180,
-                wind_gusts_10m: 25.0,
-                cloud_cover: 75,
-                pressure_msl: 1013.25,
-                uv_index: 5.0,
-                is_day: 1,
+                time: "2024-01-01T00:00".to_string(),
+                temperature_2m: Some(20.5),
+                relative_humidity_2m: Some(65),
+                apparent_temperature: Some(19.0),
+                precipitation: Some(0.5),
+                weather_code: Some(3),
+                wind_speed_10m: Some(15.0),
+                wind_direction_10m: Some(180),
+                wind_gusts_10m: Some(25.0),
+                cloud_cover: Some(75),
+                pressure_msl: Some(1013.25),
+                uv_index: Some(5.0),
+                is_day: Some(1),
+                dew_point_2m: Some(13.8),
+                visibility: Some(24140.0),
             },
             hourly: OpenMeteoHourly {
                 time: vec![
@@ -190,20 +422,29 @@ mod tests {
                     "2024-01-01T01:00".to_string(),
                     "2024-01-01T02:00".to_string(),
                 ],
-                temperature_2m: vec![18.0, 17.5, 17.0],
-                apparent_temperature: vec![16.0, 15.5, 15.0],
-                precipitation_probability: vec![10, 20, 30],
-                precipitation: vec![0.0, 0.5, 1.2],
-                weather_code: vec![0, 1, 2],
-                wind_speed_10m: vec![10.0, 12.0, 14.0],
+                temperature_2m: vec![Some(18.0), Some(17.5), Some(17.0)],
+                apparent_temperature: vec![Some(16.0), Some(15.5), Some(15.0)],
+                precipitation_probability: vec![Some(10), Some(20), Some(30)],
+                precipitation: vec![Some(0.0), Some(0.5), Some(1.2)],
+                weather_code: vec![Some(0), Some(1), Some(2)],
+                wind_speed_10m: vec![Some(10.0), Some(12.0), Some(14.0)],
+                relative_humidity_2m: vec![Some(70), Some(75), Some(80)],
+                dew_point_2m: vec![Some(12.4), Some(13.0), None],
+                wind_direction_10m: vec![Some(200), Some(210), Some(220)],
+                wind_gusts_10m: vec![Some(22.0), Some(25.0), Some(30.0)],
+                cloud_cover: vec![Some(10), Some(50), Some(100)],
+                visibility: vec![Some(24140.0), Some(16000.0), Some(800.0)],
+                uv_index: vec![Some(0.0), Some(0.0), Some(0.0)],
+                // A model without snowfall leaves it out
+                snowfall: vec![],
             },
             daily: OpenMeteoDaily {
                 time: vec!["2024-01-01".to_string(), "2024-01-02".to_string()],
-                weather_code: vec![3, 61],
-                temperature_2m_max: vec![22.0, 20.0],
-                temperature_2m_min: vec![15.0, 12.0],
-                apparent_temperature_max: vec![21.0, 19.0],
-                apparent_temperature_min: vec![14.0, 11.0],
+                weather_code: vec![Some(3), Some(61)],
+                temperature_2m_max: vec![Some(22.0), Some(20.0)],
+                temperature_2m_min: vec![Some(15.0), Some(12.0)],
+                apparent_temperature_max: vec![Some(21.0), Some(19.0)],
+                apparent_temperature_min: vec![Some(14.0), Some(11.0)],
                 sunrise: vec![
                     "2024-01-01T07:00".to_string(),
                     "2024-01-02T07:01".to_string(),
@@ -212,11 +453,20 @@ mod tests {
                     "2024-01-01T17:00".to_string(),
                     "2024-01-02T17:01".to_string(),
                 ],
-                precipitation_sum: vec![0.0, 5.5],
-                precipitation_probability_max: vec![10, 80],
-                wind_speed_10m_max: vec![20.0, 35.0],
-                uv_index_max: vec![4.0, 2.0],
+                precipitation_sum: vec![Some(0.0), Some(5.5)],
+                precipitation_probability_max: vec![Some(10), Some(80)],
+                wind_speed_10m_max: vec![Some(20.0), Some(35.0)],
+                uv_index_max: vec![Some(4.0), Some(2.0)],
             },
+            minutely_15: Some(OpenMeteoMinutely15 {
+                time: vec![
+                    "2024-01-01T00:15".to_string(),
+                    "2024-01-01T00:30".to_string(),
+                    "2024-01-01T00:45".to_string(),
+                ],
+                precipitation: vec![Some(0.0), Some(0.3), None],
+                snowfall: vec![Some(0.0), Some(0.0), None],
+            }),
         }
     }
 
@@ -226,16 +476,16 @@ mod tests {
         let weather_data: WeatherData = response.into();
 
         assert_eq!(weather_data.current.temperature, 20.5);
-        assert_eq!(weather_data.current.humidity, 65);
+        assert_eq!(weather_data.current.humidity, Some(65));
         assert_eq!(weather_data.current.apparent_temperature, 19.0);
         assert_eq!(weather_data.current.weather_code, 3);
         assert_eq!(weather_data.current.wind_speed, 15.0);
         assert_eq!(weather_data.current.wind_direction, 180);
-        assert_eq!(weather_data.current.wind_gusts, 25.0);
-        assert_eq!(weather_data.current.cloud_cover, 75);
-        assert_eq!(weather_data.current.pressure, 1013.25);
+        assert_eq!(weather_data.current.wind_gusts, Some(25.0));
+        assert_eq!(weather_data.current.cloud_cover, Some(75));
+        assert_eq!(weather_data.current.pressure, Some(1013.25));
         assert_eq!(weather_data.current.precipitation, 0.5);
-        assert_eq!(weather_data.current.uv_index, 5.0);
+        assert_eq!(weather_data.current.uv_index, Some(5.0));
         assert!(weather_data.current.is_day);
     }
 
@@ -244,12 +494,12 @@ mod tests {
         let mut response = create_test_response();
 
         // Test is_day = 1 (true)
-        response.current.is_day = 1;
+        response.current.is_day = Some(1);
         let weather_data: WeatherData = response.clone().into();
         assert!(weather_data.current.is_day);
 
         // Test is_day = 0 (false)
-        response.current.is_day = 0;
+        response.current.is_day = Some(0);
         let weather_data: WeatherData = response.into();
         assert!(!weather_data.current.is_day);
     }
@@ -265,7 +515,7 @@ mod tests {
         assert_eq!(first_hour.time, "2024-01-01T00:00");
         assert_eq!(first_hour.temperature, 18.0);
         assert_eq!(first_hour.apparent_temperature, 16.0);
-        assert_eq!(first_hour.precipitation_probability, 10);
+        assert_eq!(first_hour.precipitation_probability, Some(10));
         assert_eq!(first_hour.precipitation, 0.0);
         assert_eq!(first_hour.weather_code, 0);
         assert_eq!(first_hour.wind_speed, 10.0);
@@ -273,10 +523,30 @@ mod tests {
         let last_hour = &weather_data.hourly[2];
         assert_eq!(last_hour.time, "2024-01-01T02:00");
         assert_eq!(last_hour.temperature, 17.0);
-        assert_eq!(last_hour.precipitation_probability, 30);
+        assert_eq!(last_hour.precipitation_probability, Some(30));
         assert_eq!(last_hour.precipitation, 1.2);
     }
 
+    #[test]
+    fn test_hourly_extra_variables() {
+        let weather_data: WeatherData = create_test_response().into();
+        assert_eq!(weather_data.current.dew_point, Some(13.8));
+        assert_eq!(weather_data.current.visibility, Some(24140.0));
+
+        let first_hour = &weather_data.hourly[0];
+        assert_eq!(first_hour.humidity, Some(70));
+        assert_eq!(first_hour.dew_point, Some(12.4));
+        assert_eq!(first_hour.wind_direction, Some(200));
+        assert_eq!(first_hour.wind_gusts, Some(22.0));
+        assert_eq!(first_hour.cloud_cover, Some(10));
+        assert_eq!(first_hour.visibility, Some(24140.0));
+        assert_eq!(first_hour.uv_index, Some(0.0));
+
+        // Nulls and missing variables come through as None
+        assert_eq!(weather_data.hourly[2].dew_point, None);
+        assert!(weather_data.hourly.iter().all(|h| h.snowfall.is_none()));
+    }
+
     #[test]
     fn test_daily_forecast_conversion() {
         let response = create_test_response();
@@ -294,14 +564,40 @@ mod tests {
         assert_eq!(first_day.sunrise, "2024-01-01T07:00");
         assert_eq!(first_day.sunset, "2024-01-01T17:00");
         assert_eq!(first_day.precipitation_sum, 0.0);
-        assert_eq!(first_day.precipitation_probability, 10);
+        assert_eq!(first_day.precipitation_probability, Some(10));
         assert_eq!(first_day.wind_speed_max, 20.0);
         assert_eq!(first_day.uv_index_max, 4.0);
 
         let second_day = &weather_data.daily[1];
         assert_eq!(second_day.date, "2024-01-02");
         assert_eq!(second_day.weather_code, 61);
-        assert_eq!(second_day.precipitation_probability, 80);
+        assert_eq!(second_day.precipitation_probability, Some(80));
+    }
+
+    #[test]
+    fn test_minutely_conversion() {
+        let weather_data: WeatherData = create_test_response().into();
+
+        // The null step is dropped rather than read as dry
+        assert_eq!(weather_data.minutely.len(), 2);
+        assert_eq!(weather_data.minutely[1].time, "2024-01-01T00:30");
+        assert_eq!(weather_data.minutely[1].precipitation, 0.3);
+
+        let mut response = create_test_response();
+        response.minutely_15 = None;
+        let weather_data: WeatherData = response.into();
+        assert!(weather_data.minutely.is_empty());
+    }
+
+    #[test]
+    fn test_precipitation_over() {
+        let weather_data: WeatherData = create_test_response().into();
+        let now = NaiveDateTime::parse_from_str("2024-01-01T02:10", "%Y-%m-%dT%H:%M").unwrap();
+
+        assert_eq!(weather_data.precipitation_over(2, now), Some(1.7));
+        assert_eq!(weather_data.precipitation_over(3, now), Some(1.7));
+        // The data starts at midnight, so it can't cover four hours
+        assert_eq!(weather_data.precipitation_over(4, now), None);
     }
 
     #[test]
@@ -316,8 +612,17 @@ mod tests {
                 precipitation: vec![],
                 weather_code: vec![],
                 wind_speed_10m: vec![],
+                relative_humidity_2m: vec![],
+                dew_point_2m: vec![],
+                wind_direction_10m: vec![],
+                wind_gusts_10m: vec![],
+                cloud_cover: vec![],
+                visibility: vec![],
+                uv_index: vec![],
+                snowfall: vec![],
             },
             daily: create_test_response().daily,
+            minutely_15: None,
         };
         let weather_data: WeatherData = response.into();
         assert!(weather_data.hourly.is_empty());
@@ -342,8 +647,19 @@ mod tests {
                 wind_speed_10m_max: vec![],
                 uv_index_max: vec![],
             },
+            minutely_15: None,
         };
         let weather_data: WeatherData = response.into();
         assert!(weather_data.daily.is_empty());
     }
+
+    #[test]
+    fn test_apparent_temperature() {
+        // Calm and mild: no adjustment
+        assert_eq!(apparent_temperature(18.0, Some(50.0), 10.0), 18.0);
+        // Cold and windy: wind chill
+        assert!(apparent_temperature(-5.0, None, 30.0) < -10.0);
+        // Hot and humid: heat index
+        assert!(apparent_temperature(32.0, Some(70.0), 5.0) > 38.0);
+    }
 }
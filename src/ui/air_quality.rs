@@ -0,0 +1,146 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::models::air_quality::AirQualityData;
+use crate::ui::icons::{european_aqi_info, us_aqi_info};
+
+/// Width of the full panel shown beside current conditions
+pub const AIR_QUALITY_PANEL_WIDTH: u16 = 30;
+
+/// Air quality panel. Given only a few rows it collapses to a single
+/// summary line so it still fits under current conditions on narrow
+/// terminals.
+pub fn render_air_quality(frame: &mut Frame, area: Rect, air_quality: &AirQualityData) {
+    let block = Block::default()
+        .title(" Air Quality ")
+        .title_style(
+            Style::default()
+                .fg(Color::LightGreen)
+                .add_modifier(Modifier::BOLD),
+        )
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::LightGreen));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let lines = if inner.height <= 1 {
+        vec![summary_line(air_quality)]
+    } else {
+        panel_lines(air_quality)
+    };
+
+    let paragraph = Paragraph::new(lines).wrap(Wrap { trim: false });
+    frame.render_widget(paragraph, inner);
+}
+
+fn summary_line(air_quality: &AirQualityData) -> Line<'static> {
+    let current = &air_quality.current;
+    let mut spans = vec![Span::styled(
+        " US AQI ",
+        Style::default().fg(Color::DarkGray),
+    )];
+
+    match current.us_aqi {
+        Some(aqi) => {
+            let (category, color) = us_aqi_info(aqi);
+            spans.push(Span::styled(
+                format!("{:.0} {}", aqi, category),
+                Style::default().fg(color).add_modifier(Modifier::BOLD),
+            ));
+        }
+        None => spans.push(Span::styled("--", Style::default().fg(Color::Gray))),
+    }
+
+    if let Some(pm2_5) = current.pm2_5 {
+        spans.push(Span::styled(
+            "  PM2.5 ",
+            Style::default().fg(Color::DarkGray),
+        ));
+        spans.push(Span::styled(
+            format!("{:.0} µg/m³", pm2_5),
+            Style::default().fg(Color::Gray),
+        ));
+    }
+
+    Line::from(spans)
+}
+
+fn panel_lines(air_quality: &AirQualityData) -> Vec<Line<'static>> {
+    let current = &air_quality.current;
+    let label = |text: &'static str| Span::styled(text, Style::default().fg(Color::DarkGray));
+
+    let mut lines = vec![Line::from("")];
+
+    match current.us_aqi {
+        Some(aqi) => {
+            let (category, color) = us_aqi_info(aqi);
+            lines.push(Line::from(vec![
+                label(" US AQI  "),
+                Span::styled(
+                    format!("{:.0}", aqi),
+                    Style::default().fg(color).add_modifier(Modifier::BOLD),
+                ),
+            ]));
+            lines.push(Line::from(Span::styled(
+                format!(" {}", category),
+                Style::default().fg(color),
+            )));
+        }
+        None => lines.push(Line::from(vec![label(" US AQI  "), Span::raw("--")])),
+    }
+
+    lines.push(Line::from(""));
+    match current.european_aqi {
+        Some(aqi) => {
+            let (category, color) = european_aqi_info(aqi);
+            lines.push(Line::from(vec![
+                label(" EU AQI  "),
+                Span::styled(
+                    format!("{:.0} ", aqi),
+                    Style::default().fg(color).add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(category, Style::default().fg(color)),
+            ]));
+        }
+        None => lines.push(Line::from(vec![label(" EU AQI  "), Span::raw("--")])),
+    }
+
+    lines.push(Line::from(""));
+    let pollutants = [
+        (" PM2.5   ", current.pm2_5),
+        (" PM10    ", current.pm10),
+        (" O₃      ", current.ozone),
+        (" NO₂     ", current.nitrogen_dioxide),
+    ];
+    for (name, value) in pollutants {
+        let value = value
+            .map(|v| format!("{:>5.1} µg/m³", v))
+            .unwrap_or_else(|| format!("{:>5}", "--"));
+        lines.push(Line::from(vec![
+            label(name),
+            Span::styled(value, Style::default().fg(Color::Gray)),
+        ]));
+    }
+
+    lines
+}
+
+/// Hourly AQI column cell, `width` wide and colored by US AQI category
+pub fn hourly_aqi_span(aqi: Option<f64>, width: usize) -> Span<'static> {
+    match aqi {
+        Some(aqi) => Span::styled(
+            format!("{:>width$.0}", aqi, width = width),
+            Style::default().fg(us_aqi_info(aqi).1),
+        ),
+        None => Span::styled(
+            format!("{:>width$}", "--", width = width),
+            Style::default().fg(Color::DarkGray),
+        ),
+    }
+}
@@ -0,0 +1,276 @@
+use chrono::{DateTime, FixedOffset, Local, Utc};
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::app::App;
+use crate::models::alerts::{Alert, Alerts};
+use crate::ui::icons::alert_severity_color;
+
+/// The alert overlay's box, inset from the main content like the other
+/// full-screen overlays
+fn popup_area(area: Rect) -> Rect {
+    Rect::new(
+        area.x + 2,
+        area.y + 1,
+        area.width.saturating_sub(4),
+        area.height.saturating_sub(2),
+    )
+}
+
+/// Full-screen overlay with every active alert in full: severity, urgency
+/// and certainty, when it takes effect and expires, the areas covered, and
+/// the description and instructions
+pub fn render_alerts(frame: &mut Frame, area: Rect, app: &App) {
+    let popup_area = popup_area(area);
+    frame.render_widget(Clear, popup_area);
+
+    let active = app.alerts.active(Utc::now());
+    let color = active
+        .first()
+        .map(|a| alert_severity_color(a.severity))
+        .unwrap_or(Color::Green);
+    let block = Block::default()
+        .title(format!(" Weather Alerts ({}) ", active.len()))
+        .title_bottom(" ↑↓ Scroll  a Close ")
+        .title_style(Style::default().fg(color).add_modifier(Modifier::BOLD))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(color));
+    let inner = block.inner(popup_area);
+    frame.render_widget(block, popup_area);
+    app.alerts_area.set(inner);
+
+    let lines = alert_lines(&app.alerts, inner.width as usize);
+    let visible: Vec<Line> = lines
+        .into_iter()
+        .skip(app.alerts_scroll)
+        .take(inner.height as usize)
+        .collect();
+    frame.render_widget(Paragraph::new(visible), inner);
+}
+
+/// Furthest the overlay can scroll while keeping its last line in view
+pub fn max_alerts_scroll(alerts: &Alerts, inner: Rect) -> usize {
+    alert_lines(alerts, inner.width as usize)
+        .len()
+        .saturating_sub(inner.height as usize)
+}
+
+/// The overlay's text, wrapped to `width` here rather than by the widget so
+/// scrolling moves exactly one screen line at a time
+fn alert_lines(alerts: &Alerts, width: usize) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    let label_style = Style::default().fg(Color::DarkGray);
+    let text_width = width.saturating_sub(2).max(10);
+
+    let active = alerts.active(Utc::now());
+    if active.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "  No alerts in effect for this location",
+            Style::default().fg(Color::Green),
+        )));
+    }
+
+    for (i, alert) in active.into_iter().enumerate() {
+        if i > 0 {
+            lines.push(Line::from(Span::styled(
+                "─".repeat(width),
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+        let color = alert_severity_color(alert.severity);
+
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!(" ⚠ {}", alert.event.to_uppercase()),
+                Style::default().fg(color).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                alert
+                    .sender
+                    .as_ref()
+                    .map(|s| format!("  from {}", s))
+                    .unwrap_or_default(),
+                label_style,
+            ),
+        ]));
+        lines.push(Line::from(vec![
+            Span::styled("  Severity ", label_style),
+            Span::styled(alert.severity.label(), Style::default().fg(color)),
+            Span::styled("   Urgency ", label_style),
+            Span::raw(alert.urgency.label()),
+            Span::styled("   Certainty ", label_style),
+            Span::raw(if alert.certainty.is_empty() {
+                "Unknown".to_string()
+            } else {
+                alert.certainty.clone()
+            }),
+        ]));
+        lines.push(times_line(alert, label_style));
+        if !alert.area.is_empty() {
+            for (j, row) in wrap(&alert.area, text_width.saturating_sub(6))
+                .into_iter()
+                .enumerate()
+            {
+                let label = if j == 0 { "  Area " } else { "       " };
+                lines.push(Line::from(vec![
+                    Span::styled(label, label_style),
+                    Span::raw(row),
+                ]));
+            }
+        }
+
+        if let Some(headline) = &alert.headline {
+            lines.push(Line::from(""));
+            for row in wrap(headline, text_width) {
+                lines.push(Line::from(Span::styled(
+                    format!("  {}", row),
+                    Style::default()
+                        .fg(Color::White)
+                        .add_modifier(Modifier::BOLD),
+                )));
+            }
+        }
+        if let Some(description) = &alert.description {
+            lines.push(Line::from(""));
+            lines.extend(paragraphs(description, text_width, Style::default()));
+        }
+        if let Some(instruction) = &alert.instruction {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                "  What to do",
+                Style::default().fg(color).add_modifier(Modifier::BOLD),
+            )));
+            lines.extend(paragraphs(
+                instruction,
+                text_width,
+                Style::default().fg(Color::White),
+            ));
+        }
+    }
+
+    if !alerts.failed_sources.is_empty() {
+        lines.push(Line::from(""));
+        for row in wrap(
+            &format!(
+                "Couldn't check {}; there may be alerts not shown here.",
+                alerts.failed_sources.join(", ")
+            ),
+            text_width,
+        ) {
+            lines.push(Line::from(Span::styled(
+                format!("  {}", row),
+                Style::default().fg(Color::Yellow),
+            )));
+        }
+    }
+
+    lines
+}
+
+fn times_line(alert: &Alert, label_style: Style) -> Line<'static> {
+    let mut spans = Vec::new();
+    if let Some(effective) = alert.effective {
+        spans.push(Span::styled("  Effective ", label_style));
+        spans.push(Span::raw(format_time(effective)));
+    }
+    // Onset only adds something when the hazard starts later
+    if let Some(onset) = alert.onset.filter(|&o| Some(o) > alert.effective) {
+        spans.push(Span::styled("   Begins ", label_style));
+        spans.push(Span::raw(format_time(onset)));
+    }
+    spans.push(Span::styled("   Expires ", label_style));
+    spans.push(Span::raw(
+        alert
+            .expires
+            .map(format_time)
+            .unwrap_or_else(|| "until further notice".to_string()),
+    ));
+    Line::from(spans)
+}
+
+/// An alert time in local time, e.g. "Sat Jun 1 5:45 PM"
+pub fn format_time(time: DateTime<FixedOffset>) -> String {
+    let local = time.with_timezone(&Local);
+    let format = if local.date_naive() == Local::now().date_naive() {
+        "%l:%M %p"
+    } else {
+        "%a %b %e %l:%M %p"
+    };
+    local
+        .format(format)
+        .to_string()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Alert text is often hard-wrapped at 70 columns, so lines within a
+/// paragraph are rejoined before wrapping to the overlay's width
+fn paragraphs(text: &str, width: usize, style: Style) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    for (i, paragraph) in text
+        .split("\n\n")
+        .map(|p| p.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|p| !p.is_empty())
+        .enumerate()
+    {
+        if i > 0 {
+            lines.push(Line::from(""));
+        }
+        lines.extend(
+            wrap(&paragraph, width)
+                .into_iter()
+                .map(|row| Line::from(Span::styled(format!("  {}", row), style))),
+        );
+    }
+    lines
+}
+
+/// Greedy word wrap; words longer than the width are split
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    let mut rows = Vec::new();
+    let mut row = String::new();
+    for word in text.split_whitespace() {
+        let mut word: Vec<char> = word.chars().collect();
+        while word.len() > width {
+            if !row.is_empty() {
+                rows.push(std::mem::take(&mut row));
+            }
+            rows.push(word.drain(..width).collect());
+        }
+        let word: String = word.into_iter().collect();
+        let row_len = row.chars().count();
+        if row_len > 0 && row_len + 1 + word.chars().count() > width {
+            rows.push(std::mem::take(&mut row));
+        }
+        if !row.is_empty() {
+            row.push(' ');
+        }
+        row.push_str(&word);
+    }
+    if !row.is_empty() {
+        rows.push(row);
+    }
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wrap() {
+        assert_eq!(
+            wrap("Move to an interior room on the lowest floor", 16),
+            vec!["Move to an", "interior room on", "the lowest floor"]
+        );
+        assert_eq!(wrap("abcdefghij kl", 4), vec!["abcd", "efgh", "ij", "kl"]);
+        assert!(wrap("   ", 10).is_empty());
+    }
+}
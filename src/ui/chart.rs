@@ -7,9 +7,10 @@ use ratatui::{
 };
 
 use crate::config::UnitsConfig;
+use crate::models::normals::DayNormal;
 use crate::models::HourlyForecast;
 use crate::ui::icons::temperature_color_celsius;
-use chrono::{Local, NaiveDateTime, Timelike};
+use chrono::{NaiveDateTime, Timelike};
 
 const CHART_HEIGHT: usize = 8;
 
@@ -18,6 +19,8 @@ pub fn render_today_chart(
     area: Rect,
     hourly: &[HourlyForecast],
     units: &UnitsConfig,
+    normal: Option<&DayNormal>,
+    now: NaiveDateTime,
 ) {
     let block = Block::default()
         .title(" Today's Forecast ")
@@ -32,8 +35,8 @@ pub fn render_today_chart(
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
-    let now = Local::now();
-    let today = now.date_naive();
+    // `now` is the location's own time, like the forecast times
+    let today = now.date();
     let current_hour = now.hour();
 
     // Get all of today's hourly data (full 24 hours)
@@ -56,9 +59,19 @@ pub fn render_today_chart(
 
     // Find temperature range (in Celsius - raw API data)
     let temps: Vec<f64> = today_hours.iter().map(|h| h.temperature).collect();
-    let temp_min_c = temps.iter().cloned().fold(f64::INFINITY, f64::min);
-    let temp_max_c = temps.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    let mut temp_min_c = temps.iter().cloned().fold(f64::INFINITY, f64::min);
+    let mut temp_max_c = temps.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    // Stretch the scale so the normal high and low lines always fit
+    if let Some(normal) = normal {
+        temp_min_c = temp_min_c.min(normal.temp_min);
+        temp_max_c = temp_max_c.max(normal.temp_max);
+    }
     let temp_range = (temp_max_c - temp_min_c).max(1.0);
+    let temp_to_row = |temp_c: f64| {
+        let normalized = (temp_c - temp_min_c) / temp_range;
+        ((CHART_HEIGHT - 1) as f64 * (1.0 - normalized)).round() as usize
+    };
+    let normal_rows = normal.map(|n| (temp_to_row(n.temp_max), temp_to_row(n.temp_min)));
 
     // Convert min/max to user's preferred unit for display labels
     let temp_min_display = units.temperature.convert(temp_min_c);
@@ -101,6 +114,12 @@ pub fn render_today_chart(
         // Separator
         spans.push(Span::styled("│", Style::default().fg(Color::DarkGray)));
 
+        // Empty cells on the normal high and low rows are dashed
+        let (blank, fill) = match normal_rows {
+            Some((high, low)) if row == high || row == low => ('┄', "┄"),
+            _ => (' ', " "),
+        };
+
         // Chart content - draw each hour with proper spacing
         for (i, hour) in today_hours.iter().enumerate() {
             let hour_num =
@@ -114,15 +133,16 @@ pub fn render_today_chart(
 
             // Calculate temperature position (0 = bottom, CHART_HEIGHT-1 = top)
             // Use raw Celsius values for consistent positioning
-            let temp_normalized = (hour.temperature - temp_min_c) / temp_range;
-            let temp_row = ((CHART_HEIGHT - 1) as f64 * (1.0 - temp_normalized)).round() as usize;
+            let temp_row = temp_to_row(hour.temperature);
 
-            // Calculate rain position
-            let rain_normalized = hour.precipitation_probability as f64 / 100.0;
-            let rain_row = ((CHART_HEIGHT - 1) as f64 * (1.0 - rain_normalized)).round() as usize;
+            // Calculate rain position, if there's a chance of rain to plot
+            let rain_row = hour.precipitation_probability.map(|chance| {
+                let rain_normalized = chance as f64 / 100.0;
+                ((CHART_HEIGHT - 1) as f64 * (1.0 - rain_normalized)).round() as usize
+            });
 
             // Determine what to draw at this position
-            let (ch, color) = if row == temp_row && row == rain_row {
+            let (ch, color) = if row == temp_row && rain_row == Some(row) {
                 // Both temp and rain at same position
                 (
                     '◆',
@@ -136,12 +156,15 @@ pub fn render_today_chart(
                 // Temperature point - use raw Celsius value
                 let temp_color = temperature_color_celsius(hour.temperature);
                 ('●', if is_current { Color::White } else { temp_color })
-            } else if row == rain_row {
+            } else if let Some(chance) = hour
+                .precipitation_probability
+                .filter(|_| rain_row == Some(row))
+            {
                 // Rain point
-                let rain_color = rain_to_color(hour.precipitation_probability);
+                let rain_color = rain_to_color(chance);
                 ('○', if is_current { Color::White } else { rain_color })
             } else {
-                (' ', Color::DarkGray)
+                (blank, Color::DarkGray)
             };
 
             let style = if is_current {
@@ -154,12 +177,13 @@ pub fn render_today_chart(
             let padding_before = (chars_per_hour - 1) / 2;
             let padding_after = chars_per_hour - 1 - padding_before;
 
+            let padding_style = Style::default().fg(Color::DarkGray);
             if padding_before > 0 {
-                spans.push(Span::raw(" ".repeat(padding_before)));
+                spans.push(Span::styled(fill.repeat(padding_before), padding_style));
             }
             spans.push(Span::styled(ch.to_string(), style));
             if padding_after > 0 && i < total_hours - 1 {
-                spans.push(Span::raw(" ".repeat(padding_after)));
+                spans.push(Span::styled(fill.repeat(padding_after), padding_style));
             }
         }
 
@@ -230,7 +254,7 @@ pub fn render_today_chart(
     lines.push(Line::from(hour_spans));
 
     // Add legend
-    lines.push(Line::from(vec![
+    let mut legend = vec![
         Span::raw("      "),
         Span::styled("●", Style::default().fg(Color::Yellow)),
         Span::styled(" Temp  ", Style::default().fg(Color::DarkGray)),
@@ -242,7 +266,14 @@ pub fn render_today_chart(
         Span::styled("=Noon ", Style::default().fg(Color::DarkGray)),
         Span::styled("6", Style::default().fg(Color::Gray)),
         Span::styled("=6am/pm", Style::default().fg(Color::DarkGray)),
-    ]));
+    ];
+    if normal.is_some() {
+        legend.push(Span::styled(
+            "  ┄ Normal",
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    lines.push(Line::from(legend));
 
     let chart = Paragraph::new(lines);
     frame.render_widget(chart, inner);
@@ -0,0 +1,208 @@
+use chrono::NaiveDateTime;
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::app::App;
+use crate::models::comparison::{model_label, ComparisonHour, TEMPERATURE_DISAGREEMENT_C};
+
+/// Width of each model's temperature and precipitation cell
+const CELL_WIDTH: usize = 14;
+
+/// Full-screen overlay listing each configured model's hourly temperature
+/// and precipitation side by side. Hours where the models disagree are
+/// highlighted: the warmest and coldest values when temperatures spread
+/// widely, and the precipitation when only some models have rain.
+pub fn render_comparison(frame: &mut Frame, area: Rect, app: &App) {
+    let popup_area = Rect::new(
+        area.x + 2,
+        area.y + 1,
+        area.width.saturating_sub(4),
+        area.height.saturating_sub(2),
+    );
+    frame.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .title(" Model Comparison ")
+        .title_bottom(" ↑↓ Scroll  r Refresh  c Close ")
+        .title_style(
+            Style::default()
+                .fg(Color::LightMagenta)
+                .add_modifier(Modifier::BOLD),
+        )
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::LightMagenta));
+    let inner = block.inner(popup_area);
+    frame.render_widget(block, popup_area);
+
+    let units = &app.config.units;
+    let Some(comparison) = &app.comparison else {
+        let message = match &app.comparison_error {
+            Some(error) => Span::styled(format!("  {}", error), Style::default().fg(Color::Red)),
+            None => Span::styled(
+                format!("  {} Fetching models…", app.spinner()),
+                Style::default().fg(Color::Yellow),
+            ),
+        };
+        frame.render_widget(Paragraph::new(message), inner);
+        return;
+    };
+
+    let header_style = Style::default()
+        .fg(Color::Cyan)
+        .add_modifier(Modifier::BOLD);
+    let mut header = vec![Span::styled(
+        format!("{:<10}{:>6}", "Date", "Time"),
+        header_style,
+    )];
+    for model in &comparison.models {
+        header.push(Span::styled(
+            format!("{:>width$}", model_label(model), width = CELL_WIDTH),
+            header_style,
+        ));
+    }
+    header.push(Span::styled(format!("{:>8}", "Δ"), header_style));
+
+    let mut lines = vec![
+        Line::from(header),
+        Line::from(Span::styled(
+            "─".repeat(inner.width as usize),
+            Style::default().fg(Color::DarkGray),
+        )),
+    ];
+    if let Some(error) = &app.comparison_error {
+        lines.push(Line::from(Span::styled(
+            format!("Refresh failed: {}", error),
+            Style::default().fg(Color::Red),
+        )));
+    }
+
+    // Times are the location's own, like `local_now`
+    let now = app.local_now();
+    let upcoming = comparison
+        .hourly
+        .iter()
+        .filter(|h| parse_time(&h.time).is_some_and(|t| t >= now - chrono::Duration::hours(1)));
+    let rows = (inner.height as usize).saturating_sub(lines.len());
+
+    let mut last_date = None;
+    for hour in upcoming.skip(app.comparison_scroll).take(rows) {
+        let Some(time) = parse_time(&hour.time) else {
+            continue;
+        };
+        let date = time.date();
+        let date_col = if last_date != Some(date) {
+            time.format("%a %m/%d").to_string()
+        } else {
+            String::new()
+        };
+        last_date = Some(date);
+
+        let mut spans = vec![
+            Span::styled(
+                format!("{:<10}", date_col),
+                Style::default()
+                    .fg(Color::Magenta)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!("{:>6}", time.format("%l%p").to_string().trim()),
+                Style::default().fg(Color::Gray),
+            ),
+        ];
+        spans.extend(model_cells(
+            hour,
+            |c| units.temperature.convert(c),
+            |mm| {
+                (
+                    units.precipitation.convert(mm),
+                    units.precipitation.symbol(),
+                )
+            },
+        ));
+
+        let spread = hour.temperature_spread();
+        let spread_color = match spread {
+            Some(s) if s >= 2.0 * TEMPERATURE_DISAGREEMENT_C => Color::Red,
+            Some(s) if s >= TEMPERATURE_DISAGREEMENT_C => Color::Yellow,
+            _ => Color::DarkGray,
+        };
+        // Differences scale with the unit but don't shift with its zero
+        let spread_str = spread
+            .map(|s| {
+                let delta = units.temperature.convert(s) - units.temperature.convert(0.0);
+                format!("{:>7.1}°", delta)
+            })
+            .unwrap_or_else(|| format!("{:>8}", "--"));
+        spans.push(Span::styled(spread_str, Style::default().fg(spread_color)));
+
+        lines.push(Line::from(spans));
+    }
+
+    frame.render_widget(Paragraph::new(lines), inner);
+}
+
+fn model_cells(
+    hour: &ComparisonHour,
+    convert_temperature: impl Fn(f64) -> f64,
+    convert_precipitation: impl Fn(f64) -> (f64, &'static str),
+) -> Vec<Span<'static>> {
+    let disagree = hour
+        .temperature_spread()
+        .is_some_and(|s| s >= TEMPERATURE_DISAGREEMENT_C);
+    let values: Vec<f64> = hour.temperature.iter().flatten().copied().collect();
+    let warmest = values.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    let coldest = values.iter().cloned().fold(f64::INFINITY, f64::min);
+    let rain_split = hour.precipitation_disagrees();
+
+    let mut spans = Vec::new();
+    for (temperature, precipitation) in hour.temperature.iter().zip(&hour.precipitation) {
+        let temperature_style = match temperature {
+            Some(t) if disagree && *t == warmest => Style::default()
+                .fg(Color::LightRed)
+                .add_modifier(Modifier::BOLD),
+            Some(t) if disagree && *t == coldest => Style::default()
+                .fg(Color::LightBlue)
+                .add_modifier(Modifier::BOLD),
+            _ => Style::default().fg(Color::White),
+        };
+        let temperature_str = temperature
+            .map(|t| format!("{:.0}°", convert_temperature(t)))
+            .unwrap_or_else(|| "--".to_string());
+
+        let precipitation_style = match precipitation {
+            Some(mm) if rain_split && *mm > 0.0 => Style::default()
+                .fg(Color::Magenta)
+                .add_modifier(Modifier::BOLD),
+            Some(_) if rain_split => Style::default().fg(Color::Magenta),
+            Some(mm) if *mm > 0.0 => Style::default().fg(Color::Cyan),
+            _ => Style::default().fg(Color::DarkGray),
+        };
+        let precipitation_str = match precipitation {
+            Some(mm) if *mm > 0.0 => {
+                let (amount, symbol) = convert_precipitation(*mm);
+                format!("{:.2}{}", amount, symbol)
+            }
+            Some(_) => "·".to_string(),
+            None => "--".to_string(),
+        };
+
+        spans.push(Span::styled(
+            format!("{:>6}", temperature_str),
+            temperature_style,
+        ));
+        spans.push(Span::styled(
+            format!("{:>width$}", precipitation_str, width = CELL_WIDTH - 6),
+            precipitation_style,
+        ));
+    }
+    spans
+}
+
+fn parse_time(time: &str) -> Option<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(time, "%Y-%m-%dT%H:%M").ok()
+}
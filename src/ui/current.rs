@@ -1,3 +1,4 @@
+use chrono::NaiveDateTime;
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
@@ -7,14 +8,18 @@ use ratatui::{
 };
 
 use crate::config::UnitsConfig;
-use crate::models::CurrentWeather;
+use crate::models::nowcast::{Intensity, Nowcast};
+use crate::models::weather::PRECIPITATION_WINDOWS;
+use crate::models::{CurrentWeather, WeatherData};
+use crate::ui::hourly::format_visibility;
 use crate::ui::icons::{temperature_color_celsius, uv_info, wind_direction_str, WeatherCondition};
 
 pub fn render_current_weather(
     frame: &mut Frame,
     area: Rect,
-    weather: &CurrentWeather,
+    weather: &WeatherData,
     units: &UnitsConfig,
+    now: NaiveDateTime,
 ) {
     let block = Block::default()
         .title(" Current Conditions ")
@@ -26,11 +31,30 @@ pub fn render_current_weather(
         .borders(Borders::ALL)
         .border_style(Style::default().fg(Color::Cyan));
 
-    let inner = block.inner(area);
+    let mut inner = block.inner(area);
     frame.render_widget(block, area);
 
-    // Calculate content height to center vertically
-    let content_height = 11; // icon (5) + blank + temp + feels + blank + condition = 10 lines for left side
+    // The nowcast strip takes the bottom two rows when there's 15-minute data.
+    // `now` is the location's own time, like the forecast times.
+    if let Some(nowcast) = Nowcast::new(&weather.minutely, now) {
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(2)])
+            .split(inner);
+        render_nowcast(frame, rows[1], &nowcast);
+        inner = rows[0];
+    }
+
+    // Rain already fallen, where the provider has past hours
+    let totals: Option<Vec<f64>> = PRECIPITATION_WINDOWS
+        .iter()
+        .map(|&hours| weather.precipitation_over(hours, now))
+        .collect();
+
+    // Calculate content height to center vertically. The details column is
+    // the taller one: 13 lines, plus one for the rolling totals. The left is
+    // icon (5) + blank + temp + feels + blank + condition = 10 lines.
+    let content_height = 13 + usize::from(totals.is_some());
     let vertical_padding = (inner.height as usize).saturating_sub(content_height) / 2;
 
     // Create a centered area
@@ -53,8 +77,40 @@ pub fn render_current_weather(
         .constraints([Constraint::Length(20), Constraint::Min(30)])
         .split(centered_area);
 
-    render_icon_and_temp(frame, chunks[0], weather, units);
-    render_details(frame, chunks[1], weather, units);
+    render_icon_and_temp(frame, chunks[0], &weather.current, units);
+    render_details(frame, chunks[1], &weather.current, totals.as_deref(), units);
+}
+
+fn render_nowcast(frame: &mut Frame, area: Rect, nowcast: &Nowcast) {
+    let mut strip = vec![Span::styled(
+        " Next 2h  ",
+        Style::default().fg(Color::DarkGray),
+    )];
+    for step in &nowcast.steps {
+        let (bar, color) = match step.intensity() {
+            None => ("▁", Color::DarkGray),
+            Some(_) if step.snowfall > 0.0 => ("▄", Color::White),
+            Some(Intensity::Light) => ("▃", Color::LightBlue),
+            Some(Intensity::Moderate) => ("▅", Color::Blue),
+            Some(Intensity::Heavy) => ("▇", Color::Magenta),
+        };
+        strip.push(Span::styled(bar.repeat(4), Style::default().fg(color)));
+    }
+    strip.push(Span::styled(
+        format!(" {}", nowcast.end().format("%-I:%M%P")),
+        Style::default().fg(Color::DarkGray),
+    ));
+
+    let wet = nowcast.steps.iter().any(|s| s.intensity().is_some());
+    let summary = Span::styled(
+        format!(" {}", nowcast.summary()),
+        Style::default().fg(if wet { Color::LightBlue } else { Color::Gray }),
+    );
+
+    frame.render_widget(
+        Paragraph::new(vec![Line::from(strip), Line::from(summary)]),
+        area,
+    );
 }
 
 fn render_icon_and_temp(
@@ -108,24 +164,78 @@ fn render_icon_and_temp(
     frame.render_widget(paragraph, area);
 }
 
-fn render_details(frame: &mut Frame, area: Rect, weather: &CurrentWeather, units: &UnitsConfig) {
-    let (uv_desc, uv_color) = uv_info(weather.uv_index);
+fn render_details(
+    frame: &mut Frame,
+    area: Rect,
+    weather: &CurrentWeather,
+    totals: Option<&[f64]>,
+    units: &UnitsConfig,
+) {
     let wind_dir = wind_direction_str(weather.wind_direction);
 
     // Convert from metric (km/h, mm) to user's preferred units
     let wind_speed = units.wind_speed.convert(weather.wind_speed);
-    let wind_gusts = units.wind_speed.convert(weather.wind_gusts);
     let precipitation = units.precipitation.convert(weather.precipitation);
 
-    let lines = vec![
+    // Readings the provider doesn't report show as "--"
+    let percent = |value: Option<i32>| value.map_or("--".to_string(), |v| format!("{}%", v));
+    let gusts = weather.wind_gusts.map_or("--".to_string(), |g| {
+        format!(
+            "{:.0} {}",
+            units.wind_speed.convert(g),
+            units.wind_speed.symbol()
+        )
+    });
+    let pressure = weather.pressure.map_or("--".to_string(), |p| {
+        format!("{} {}", units.pressure.format(p), units.pressure.symbol())
+    });
+    let (uv, uv_color) = match weather.uv_index {
+        Some(uv_index) => {
+            let (desc, color) = uv_info(uv_index);
+            (format!("{:.0} ({})", uv_index, desc), color)
+        }
+        None => ("--".to_string(), Color::Gray),
+    };
+
+    // Dew point and visibility share lines, where the provider has them
+    let mut humidity = vec![
+        Span::styled("Humidity:    ", Style::default().fg(Color::DarkGray)),
+        Span::styled(
+            percent(weather.humidity),
+            Style::default().fg(Color::LightCyan),
+        ),
+    ];
+    if let Some(dew_point) = weather.dew_point {
+        humidity.push(Span::styled(
+            "  Dew point ",
+            Style::default().fg(Color::DarkGray),
+        ));
+        humidity.push(Span::styled(
+            format!("{:.0}°", units.temperature.convert(dew_point)),
+            Style::default().fg(temperature_color_celsius(dew_point)),
+        ));
+    }
+    let mut cloud_cover = vec![
+        Span::styled("Cloud Cover: ", Style::default().fg(Color::DarkGray)),
+        Span::styled(
+            percent(weather.cloud_cover),
+            Style::default().fg(Color::Gray),
+        ),
+    ];
+    if let Some(visibility) = weather.visibility {
+        cloud_cover.push(Span::styled(
+            "  Visibility ",
+            Style::default().fg(Color::DarkGray),
+        ));
+        cloud_cover.push(Span::styled(
+            format_visibility(visibility, units),
+            Style::default().fg(Color::Gray),
+        ));
+    }
+
+    let mut lines = vec![
         Line::from(""),
-        Line::from(vec![
-            Span::styled("Humidity:    ", Style::default().fg(Color::DarkGray)),
-            Span::styled(
-                format!("{}%", weather.humidity),
-                Style::default().fg(Color::LightCyan),
-            ),
-        ]),
+        Line::from(humidity),
         Line::from(""),
         Line::from(vec![
             Span::styled("Wind:        ", Style::default().fg(Color::DarkGray)),
@@ -141,38 +251,19 @@ fn render_details(frame: &mut Frame, area: Rect, weather: &CurrentWeather, units
         ]),
         Line::from(vec![
             Span::styled("Gusts:       ", Style::default().fg(Color::DarkGray)),
-            Span::styled(
-                format!("{:.0} {}", wind_gusts, units.wind_speed.symbol()),
-                Style::default().fg(Color::LightGreen),
-            ),
+            Span::styled(gusts, Style::default().fg(Color::LightGreen)),
         ]),
         Line::from(""),
         Line::from(vec![
             Span::styled("Pressure:    ", Style::default().fg(Color::DarkGray)),
-            Span::styled(
-                format!(
-                    "{} {}",
-                    units.pressure.format(weather.pressure),
-                    units.pressure.symbol()
-                ),
-                Style::default().fg(Color::LightMagenta),
-            ),
+            Span::styled(pressure, Style::default().fg(Color::LightMagenta)),
         ]),
         Line::from(""),
-        Line::from(vec![
-            Span::styled("Cloud Cover: ", Style::default().fg(Color::DarkGray)),
-            Span::styled(
-                format!("{}%", weather.cloud_cover),
-                Style::default().fg(Color::Gray),
-            ),
-        ]),
+        Line::from(cloud_cover),
         Line::from(""),
         Line::from(vec![
             Span::styled("UV Index:    ", Style::default().fg(Color::DarkGray)),
-            Span::styled(
-                format!("{:.0} ({})", weather.uv_index, uv_desc),
-                Style::default().fg(uv_color),
-            ),
+            Span::styled(uv, Style::default().fg(uv_color)),
         ]),
         Line::from(""),
         Line::from(vec![
@@ -184,6 +275,28 @@ fn render_details(frame: &mut Frame, area: Rect, weather: &CurrentWeather, units
         ]),
     ];
 
+    // e.g. "Past 24h:    0.12 in  48h 0.40  72h 1.20"
+    if let Some(totals) = totals {
+        let mut spans = vec![Span::styled(
+            "Past 24h:    ",
+            Style::default().fg(Color::DarkGray),
+        )];
+        for (i, (&hours, &total)) in PRECIPITATION_WINDOWS.iter().zip(totals).enumerate() {
+            let amount = units.precipitation.convert(total);
+            let text = if i == 0 {
+                format!("{:.2} {}", amount, units.precipitation.symbol())
+            } else {
+                spans.push(Span::styled(
+                    format!("  {}h ", hours),
+                    Style::default().fg(Color::DarkGray),
+                ));
+                format!("{:.2}", amount)
+            };
+            spans.push(Span::styled(text, Style::default().fg(Color::LightBlue)));
+        }
+        lines.push(Line::from(spans));
+    }
+
     let paragraph = Paragraph::new(lines);
     frame.render_widget(paragraph, area);
 }
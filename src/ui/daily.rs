@@ -7,18 +7,40 @@ use ratatui::{
 };
 
 use crate::config::UnitsConfig;
+use crate::models::ensemble::{Confidence, EnsembleData};
+use crate::models::history::DayHistory;
+use crate::models::normals::{ClimateNormals, DayNormal};
 use crate::models::DailyForecast;
-use crate::ui::icons::{temperature_color_celsius, uv_info, WeatherCondition};
+use crate::ui::icons::{confidence_info, temperature_color_celsius, uv_info, WeatherCondition};
 use chrono::{NaiveDate, NaiveDateTime};
 
+/// Narrowest a day's column gets before fewer days are shown
+const DAY_COLUMN_WIDTH: u16 = 22;
+
+/// How many days fit side by side in a daily panel this wide
+pub fn visible_days(panel_width: u16, days: usize) -> usize {
+    let columns = panel_width.saturating_sub(2) / DAY_COLUMN_WIDTH;
+    (columns as usize).clamp(1, days.max(1))
+}
+
+/// Days as a strip of columns, as many as fit the width, starting `scroll`
+/// days after today
+#[allow(clippy::too_many_arguments)]
 pub fn render_daily_forecast(
     frame: &mut Frame,
     area: Rect,
     daily: &[DailyForecast],
+    scroll: usize,
     units: &UnitsConfig,
+    history: Option<&DayHistory>,
+    normals: Option<&ClimateNormals>,
+    ensemble: Option<&EnsembleData>,
 ) {
-    let block = Block::default()
-        .title(" 5-Day Forecast ")
+    let visible = visible_days(area.width, daily.len());
+    let first = scroll.min(daily.len().saturating_sub(visible));
+
+    let mut block = Block::default()
+        .title(format!(" {}-Day Forecast ", daily.len()))
         .title_style(
             Style::default()
                 .fg(Color::Magenta)
@@ -27,11 +49,38 @@ pub fn render_daily_forecast(
         .borders(Borders::ALL)
         .border_style(Style::default().fg(Color::Magenta));
 
+    // Compare today with past years along the bottom border
+    if let (Some(today), Some(history)) = (daily.first(), history) {
+        if today.date == history.date {
+            block = block.title_bottom(history_line(today, history, units));
+        }
+    }
+
+    // Where the strip is when not every day fits
+    if visible < daily.len() {
+        let arrow = |shown: bool, arrow: &'static str| {
+            Span::styled(
+                arrow,
+                Style::default().fg(if shown { Color::White } else { Color::DarkGray }),
+            )
+        };
+        block = block.title(
+            Line::from(vec![
+                arrow(first > 0, " ◀ "),
+                Span::styled(
+                    format!("days {}-{}", first + 1, first + visible),
+                    Style::default().fg(Color::Gray),
+                ),
+                arrow(first + visible < daily.len(), " ▶ "),
+            ])
+            .right_aligned(),
+        );
+    }
+
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
-    // Take only first 5 days
-    let days: Vec<&DailyForecast> = daily.iter().take(5).collect();
+    let days: Vec<&DailyForecast> = daily.iter().skip(first).take(visible).collect();
 
     if days.is_empty() {
         return;
@@ -49,16 +98,74 @@ pub fn render_daily_forecast(
         .split(inner);
 
     for (i, day) in days.iter().enumerate() {
-        render_day_column(frame, chunks[i], day, units, i == 0);
+        let normal = normals.and_then(|n| {
+            let date = NaiveDate::parse_from_str(&day.date, "%Y-%m-%d").ok()?;
+            n.for_date(date)
+        });
+        let confidence = ensemble
+            .and_then(|e| e.day(&day.date))
+            .map(|d| d.confidence);
+        let is_today = first + i == 0;
+        render_day_column(frame, chunks[i], day, units, is_today, normal, confidence);
     }
 }
 
+/// Difference from normal (°C) worth pointing out
+const ANOMALY_C: f64 = 3.0;
+
+/// An arrow for a temperature well above or below its normal
+fn anomaly_marker(temp_c: f64, normal_c: Option<f64>) -> (&'static str, Color) {
+    match normal_c.map(|normal| temp_c - normal) {
+        Some(delta) if delta >= ANOMALY_C => ("▲", Color::LightRed),
+        Some(delta) if delta <= -ANOMALY_C => ("▼", Color::LightBlue),
+        _ => ("", Color::DarkGray),
+    }
+}
+
+/// e.g. "Today vs 10-year average: high +6°, low +2°, rain +0.1 in"
+fn history_line(today: &DailyForecast, history: &DayHistory, units: &UnitsConfig) -> Line<'static> {
+    let temperature_delta = |today: f64, average: f64| {
+        let delta = units.temperature.convert(today) - units.temperature.convert(average);
+        let color = if delta.round() > 0.0 {
+            Color::LightRed
+        } else if delta.round() < 0.0 {
+            Color::LightBlue
+        } else {
+            Color::Gray
+        };
+        Span::styled(format!("{:+.0}°", delta), Style::default().fg(color))
+    };
+    let label = |text: String| Span::styled(text, Style::default().fg(Color::DarkGray));
+
+    let precipitation_delta = units.precipitation.convert(today.precipitation_sum)
+        - units.precipitation.convert(history.precipitation);
+
+    Line::from(vec![
+        label(format!(" Today vs {}-year average: high ", history.years)),
+        temperature_delta(today.temp_max, history.temp_max),
+        label(", low ".to_string()),
+        temperature_delta(today.temp_min, history.temp_min),
+        label(", rain ".to_string()),
+        Span::styled(
+            format!(
+                "{:+.1} {}",
+                precipitation_delta,
+                units.precipitation.symbol()
+            ),
+            Style::default().fg(Color::Gray),
+        ),
+        Span::raw(" "),
+    ])
+}
+
 fn render_day_column(
     frame: &mut Frame,
     area: Rect,
     day: &DailyForecast,
     units: &UnitsConfig,
     is_today: bool,
+    normal: Option<&DayNormal>,
+    confidence: Option<Confidence>,
 ) {
     let condition = WeatherCondition::from_wmo_code(day.weather_code, true);
     let icon = condition.icon();
@@ -97,10 +204,11 @@ fn render_day_column(
 
     // Precipitation color
     let precip_color = match day.precipitation_probability {
-        0..=20 => Color::Green,
-        21..=50 => Color::Yellow,
-        51..=70 => Color::Rgb(255, 165, 0),
-        _ => Color::Red,
+        None => Color::Gray,
+        Some(0..=20) => Color::Green,
+        Some(21..=50) => Color::Yellow,
+        Some(51..=70) => Color::Rgb(255, 165, 0),
+        Some(_) => Color::Red,
     };
 
     let (uv_desc, uv_color) = uv_info(day.uv_index_max);
@@ -115,10 +223,22 @@ fn render_day_column(
     } else {
         Style::default().fg(Color::Gray)
     };
-    lines.push(Line::from(Span::styled(
-        format!("{:^width$}", date_str, width = area.width as usize),
-        header_style,
-    )));
+    match confidence {
+        // Ensemble agreement as a three-dot badge after the date
+        Some(confidence) => {
+            let (dots, _, color) = confidence_info(confidence);
+            let label = format!("{} {}", date_str, dots);
+            let padding = (area.width as usize).saturating_sub(label.chars().count()) / 2;
+            lines.push(Line::from(vec![
+                Span::styled(format!("{:>padding$}{} ", "", date_str), header_style),
+                Span::styled(dots, Style::default().fg(color)),
+            ]));
+        }
+        None => lines.push(Line::from(Span::styled(
+            format!("{:^width$}", date_str, width = area.width as usize),
+            header_style,
+        ))),
+    }
 
     lines.push(Line::from(""));
 
@@ -133,20 +253,27 @@ fn render_day_column(
 
     lines.push(Line::from(""));
 
-    // Low/High temperature
-    let temp_line = format!("{}° / {}°", temp_min as i32, temp_max as i32);
-    let padding = (area.width as usize).saturating_sub(temp_line.len()) / 2;
+    // Low/High temperature, each flagged when well away from normal
+    let low_marker = anomaly_marker(day.temp_min, normal.map(|n| n.temp_min));
+    let high_marker = anomaly_marker(day.temp_max, normal.map(|n| n.temp_max));
+    let temp_line = format!(
+        "{}°{} / {}°{}",
+        temp_min as i32, low_marker.0, temp_max as i32, high_marker.0
+    );
+    let padding = (area.width as usize).saturating_sub(temp_line.chars().count()) / 2;
     lines.push(Line::from(vec![
         Span::raw(format!("{:>padding$}", "", padding = padding)),
         Span::styled(
             format!("{}°", temp_min as i32),
             Style::default().fg(low_color),
         ),
+        Span::styled(low_marker.0, Style::default().fg(low_marker.1)),
         Span::styled(" / ", Style::default().fg(Color::DarkGray)),
         Span::styled(
             format!("{}°", temp_max as i32),
             Style::default().fg(high_color).add_modifier(Modifier::BOLD),
         ),
+        Span::styled(high_marker.0, Style::default().fg(high_marker.1)),
     ]));
 
     // Feels like temperature range
@@ -157,19 +284,37 @@ fn render_day_column(
         Style::default().fg(Color::DarkGray),
     )));
 
+    // 1991-2020 normal high/low and chance of a wet day
+    if let Some(normal) = normal {
+        let normal_str = format!(
+            "Normal {}°/{}° {:.0}%",
+            units.temperature.convert(normal.temp_min).round() as i32,
+            units.temperature.convert(normal.temp_max).round() as i32,
+            normal.precipitation_probability
+        );
+        let padding = (area.width as usize).saturating_sub(normal_str.chars().count()) / 2;
+        lines.push(Line::from(Span::styled(
+            format!("{:>padding$}{}", "", normal_str, padding = padding),
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+
     lines.push(Line::from(""));
 
     // Precipitation probability and amount
     let precip_amount = units.precipitation.convert(day.precipitation_sum);
+    let chance = day
+        .precipitation_probability
+        .map_or("--".to_string(), |p| format!("{}%", p));
     let precip_str = if day.precipitation_sum > 0.0 {
         format!(
-            "{}% ({:.1}{})",
-            day.precipitation_probability,
+            "{} ({:.1}{})",
+            chance,
             precip_amount,
             units.precipitation.symbol()
         )
     } else {
-        format!("{}% rain", day.precipitation_probability)
+        format!("{} rain", chance)
     };
     let padding = (area.width as usize).saturating_sub(precip_str.len()) / 2;
     lines.push(Line::from(Span::styled(
@@ -6,19 +6,30 @@ use ratatui::{
     Frame,
 };
 
-use crate::config::UnitsConfig;
+use crate::config::{HourlyColumn, UnitsConfig, WindSpeedUnit};
+use crate::models::air_quality::AirQualityData;
+use crate::models::marine::MarineData;
 use crate::models::HourlyForecast;
-use crate::ui::icons::{temperature_color_celsius, WeatherCondition};
-use chrono::{DateTime, Datelike, Local, NaiveDateTime, TimeZone, Timelike};
+use crate::ui::air_quality::hourly_aqi_span;
+use crate::ui::icons::{
+    sea_state_info, temperature_color_celsius, uv_info, wind_direction_str, WeatherCondition,
+};
+use crate::ui::marine::format_wave_height;
+use chrono::{NaiveDate, NaiveDateTime, Timelike};
 
+#[allow(clippy::too_many_arguments)]
 pub fn render_hourly_forecast(
     frame: &mut Frame,
     area: Rect,
     hourly: &[HourlyForecast],
     units: &UnitsConfig,
-    scroll_offset: usize,
+    columns: &[HourlyColumn],
+    scroll_offset: isize,
+    air_quality: Option<&AirQualityData>,
+    marine: Option<&MarineData>,
+    now: NaiveDateTime,
 ) {
-    let block = Block::default()
+    let mut block = Block::default()
         .title(" Hourly Forecast ")
         .title_style(
             Style::default()
@@ -28,26 +39,27 @@ pub fn render_hourly_forecast(
         .borders(Borders::ALL)
         .border_style(Style::default().fg(Color::Yellow));
 
+    if scroll_offset < 0 {
+        block = block.title_bottom(Line::from(Span::styled(
+            " Earlier hours are dimmed ",
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
-    // Get current hour to filter past hours
-    let now = Local::now();
-
-    // Filter to show only future hours (including current hour)
-    let future_hours: Vec<&HourlyForecast> = hourly
+    // Waves only make sense in marine mode, and near the sea
+    let columns: Vec<HourlyColumn> = columns
         .iter()
-        .filter(|h| {
-            if let Ok(dt) = NaiveDateTime::parse_from_str(&h.time, "%Y-%m-%dT%H:%M") {
-                let local_dt = Local.from_local_datetime(&dt).single();
-                if let Some(local_dt) = local_dt {
-                    return local_dt >= now - chrono::Duration::hours(1);
-                }
-            }
-            false
-        })
+        .copied()
+        .filter(|&c| c != HourlyColumn::Waves || marine.is_some())
         .collect();
 
+    // Rows before the current hour are past hours, shown when scrolled back
+    let current = current_hour_index(hourly, now);
+    let start = (current as isize + scroll_offset).clamp(0, hourly.len() as isize) as usize;
+
     // Calculate how many hours we can display (subtract 2 for header and separator)
     let available_height = inner.height as usize;
     let hours_to_show = available_height.saturating_sub(2);
@@ -58,16 +70,18 @@ pub fn render_hourly_forecast(
     let header_style = Style::default()
         .fg(Color::Cyan)
         .add_modifier(Modifier::BOLD);
-    lines.push(Line::from(vec![
+    let mut header = vec![
         Span::styled(format!("{:<10}", "Date"), header_style),
         Span::styled(format!("{:>6}", "Time"), header_style),
-        Span::styled(format!("{:>6}", "Temp"), header_style),
-        Span::styled(format!("{:>8}", "Feels"), header_style),
-        Span::styled(format!("{:>4}", ""), header_style),
-        Span::styled(format!("{:>8}", "Wind"), header_style),
-        Span::styled(format!("{:>12}", "Precip"), header_style),
-        // Span::styled(format!("{:>6}", ""), header_style),
-    ]));
+    ];
+    for &column in &columns {
+        let (title, width) = column_header(column);
+        header.push(Span::styled(
+            format!("{:>width$}", title, width = width),
+            header_style,
+        ));
+    }
+    lines.push(Line::from(header));
 
     // Add separator line
     lines.push(Line::from(Span::styled(
@@ -75,39 +89,26 @@ pub fn render_hourly_forecast(
         Style::default().fg(Color::DarkGray),
     )));
 
-    // Track the current date being displayed
-    let mut last_date: Option<(i32, u32, u32)> = None;
-
-    // Determine if first visible row should show date
-    if let Some(first_hour) = future_hours.get(scroll_offset) {
-        if let Ok(dt) = NaiveDateTime::parse_from_str(&first_hour.time, "%Y-%m-%dT%H:%M") {
-            let local_dt: DateTime<Local> = Local
-                .from_local_datetime(&dt)
-                .single()
-                .unwrap_or_else(Local::now);
-            last_date = Some((local_dt.year(), local_dt.month(), local_dt.day()));
-        }
-    }
+    // Track the current date being displayed. Times are the location's own
+    // and shown as given.
+    let mut last_date: Option<NaiveDate> = hourly
+        .get(start)
+        .and_then(|h| NaiveDateTime::parse_from_str(&h.time, "%Y-%m-%dT%H:%M").ok())
+        .map(|dt| dt.date());
 
     // Track if we've shown the date for the first visible row
     let mut first_row_date_shown = false;
 
-    for (i, hour) in future_hours
-        .iter()
-        .skip(scroll_offset)
-        .take(hours_to_show)
-        .enumerate()
-    {
+    for (i, hour) in hourly.iter().skip(start).take(hours_to_show).enumerate() {
+        let is_past = start + i < current;
+
         // Parse time
-        let parsed_dt = NaiveDateTime::parse_from_str(&hour.time, "%Y-%m-%dT%H:%M").ok();
-        let local_dt: Option<DateTime<Local>> =
-            parsed_dt.and_then(|dt| Local.from_local_datetime(&dt).single());
+        let local_dt = NaiveDateTime::parse_from_str(&hour.time, "%Y-%m-%dT%H:%M").ok();
 
         let (time_str, is_midnight, current_date) = if let Some(ldt) = local_dt {
             let is_midnight = ldt.hour() == 0;
-            let date_tuple = (ldt.year(), ldt.month(), ldt.day());
             let time_formatted = ldt.format("%l%p").to_string().trim().to_string();
-            (time_formatted, is_midnight, Some(date_tuple))
+            (time_formatted, is_midnight, Some(ldt.date()))
         } else {
             (hour.time.clone(), false, None)
         };
@@ -117,35 +118,6 @@ pub fn render_hourly_forecast(
             .map(|ldt| (6..=20).contains(&ldt.hour()))
             .unwrap_or(true);
 
-        let condition = WeatherCondition::from_wmo_code(hour.weather_code, is_day);
-
-        // Convert temperature from Celsius to user's preferred unit
-        let temp = units.temperature.convert(hour.temperature);
-        let feels_like = units.temperature.convert(hour.apparent_temperature);
-        // Get color based on raw Celsius value
-        let temp_color = temperature_color_celsius(hour.temperature);
-
-        // Convert wind speed from km/h to user's preferred unit
-        let wind_speed = units.wind_speed.convert(hour.wind_speed);
-
-        // Color precipitation probability
-        let precip_color = match hour.precipitation_probability {
-            0..=20 => Color::Green,
-            21..=50 => Color::Yellow,
-            51..=70 => Color::Rgb(255, 165, 0),
-            _ => Color::Red,
-        };
-
-        // Convert precipitation amount from mm to user's preferred unit
-        let precip_amount = units.precipitation.convert(hour.precipitation);
-        let precip_str = format!("{:>3}%", hour.precipitation_probability);
-
-        let precip_amount_str = if hour.precipitation > 0.0 {
-            format!("{:>6.2} {}", precip_amount, units.precipitation.symbol())
-        } else {
-            "".to_string()
-        };
-
         // Determine if we should show date in the date column
         // Show on first row, or at midnight when date changes
         let show_date = if i == 0 && !first_row_date_shown {
@@ -182,8 +154,8 @@ pub fn render_hourly_forecast(
             .fg(Color::Magenta)
             .add_modifier(Modifier::BOLD);
 
-        // Highlight current hour (first row when not scrolled)
-        let time_style = if i == 0 && scroll_offset == 0 {
+        // Highlight current hour
+        let time_style = if start + i == current {
             Style::default()
                 .fg(Color::White)
                 .add_modifier(Modifier::BOLD)
@@ -191,39 +163,29 @@ pub fn render_hourly_forecast(
             Style::default().fg(Color::Gray)
         };
 
-        lines.push(Line::from(vec![
+        let mut row = vec![
             Span::styled(format!("{:<10}", date_col), date_style),
             Span::styled(format!("{:>6}", time_str), time_style),
-            Span::styled(
-                format!("{:>5}°", temp as i32),
-                Style::default().fg(temp_color),
-            ),
-            Span::styled(
-                format!("{:>5}°", feels_like as i32),
-                Style::default().fg(Color::Gray),
-            ),
-            Span::styled(
-                format!("  {} ", condition.small_icon()),
-                Style::default().fg(condition.color()),
-            ),
-            Span::styled(
-                format!("{:>7.0} {}", wind_speed, units.wind_speed.symbol()),
-                Style::default().fg(Color::LightGreen),
-            ),
-            Span::styled(
-                format!("{:>6}", precip_str),
-                Style::default().fg(precip_color),
-            ),
-            Span::styled(
-                format!("{:>6}", precip_amount_str),
-                Style::default().fg(precip_color),
-            ),
-        ]));
+        ];
+        row.extend(
+            columns.iter().map(|&column| {
+                column_cell(column, hour, units, is_day, is_past, air_quality, marine)
+            }),
+        );
+        // Past hours are dimmed so they don't read as forecast
+        if is_past {
+            for span in row.iter_mut().skip(1) {
+                span.style = span
+                    .style
+                    .fg(Color::DarkGray)
+                    .add_modifier(Modifier::ITALIC);
+            }
+        }
+        lines.push(Line::from(row));
     }
 
     // Add scroll indicator if there are more items
-    let total_future = future_hours.len();
-    if scroll_offset + hours_to_show < total_future && lines.len() < available_height {
+    if start + hours_to_show < hourly.len() && lines.len() < available_height {
         lines.push(Line::from(Span::styled(
             "          ↓ more",
             Style::default().fg(Color::DarkGray),
@@ -234,23 +196,208 @@ pub fn render_hourly_forecast(
     frame.render_widget(paragraph, inner);
 }
 
-/// Get the maximum scroll offset for hourly forecast
-pub fn get_max_hourly_scroll(hourly: &[HourlyForecast], visible_height: usize) -> usize {
-    let now = Local::now();
+/// Title and width of a configurable column
+fn column_header(column: HourlyColumn) -> (&'static str, usize) {
+    match column {
+        HourlyColumn::Temperature => ("Temp", 6),
+        HourlyColumn::FeelsLike => ("Feels", 7),
+        HourlyColumn::Conditions => ("", 4),
+        HourlyColumn::Wind => ("Wind", 10),
+        HourlyColumn::WindDirection => ("Dir", 5),
+        HourlyColumn::Gusts => ("Gusts", 10),
+        HourlyColumn::Precipitation => ("Precip", 15),
+        HourlyColumn::Snowfall => ("Snow", 9),
+        HourlyColumn::Humidity => ("Hum", 5),
+        HourlyColumn::DewPoint => ("Dew", 6),
+        HourlyColumn::CloudCover => ("Cloud", 6),
+        HourlyColumn::Visibility => ("Vis", 8),
+        HourlyColumn::UvIndex => ("UV", 4),
+        HourlyColumn::Aqi => ("AQI", 6),
+        HourlyColumn::Waves => ("Waves", 14),
+    }
+}
 
-    let future_count = hourly
+/// One hour's value for a column, padded to the column's width. Values the
+/// provider doesn't forecast show as "--".
+fn column_cell(
+    column: HourlyColumn,
+    hour: &HourlyForecast,
+    units: &UnitsConfig,
+    is_day: bool,
+    is_past: bool,
+    air_quality: Option<&AirQualityData>,
+    marine: Option<&MarineData>,
+) -> Span<'static> {
+    let width = column_header(column).1;
+    let cell = |text: String, color: Color| {
+        Span::styled(
+            format!("{:>width$}", text, width = width),
+            Style::default().fg(color),
+        )
+    };
+    let missing = || cell("--".to_string(), Color::DarkGray);
+    let degrees = |celsius: f64| format!("{}°", units.temperature.convert(celsius) as i32);
+    let speed = |kmh: f64| {
+        format!(
+            "{:.0} {}",
+            units.wind_speed.convert(kmh),
+            units.wind_speed.symbol()
+        )
+    };
+
+    match column {
+        HourlyColumn::Temperature => cell(
+            degrees(hour.temperature),
+            temperature_color_celsius(hour.temperature),
+        ),
+        HourlyColumn::FeelsLike => cell(degrees(hour.apparent_temperature), Color::Gray),
+        HourlyColumn::Conditions => {
+            let condition = WeatherCondition::from_wmo_code(hour.weather_code, is_day);
+            Span::styled(
+                format!("  {} ", condition.small_icon()),
+                Style::default().fg(condition.color()),
+            )
+        }
+        HourlyColumn::Wind => cell(speed(hour.wind_speed), Color::LightGreen),
+        HourlyColumn::WindDirection => match hour.wind_direction {
+            Some(direction) => cell(wind_direction_str(direction).to_string(), Color::LightGreen),
+            None => missing(),
+        },
+        HourlyColumn::Gusts => match hour.wind_gusts {
+            Some(gusts) => cell(speed(gusts), Color::Green),
+            None => missing(),
+        },
+        HourlyColumn::Precipitation => {
+            let color = match hour.precipitation_probability {
+                None => Color::Gray,
+                Some(0..=20) => Color::Green,
+                Some(21..=50) => Color::Yellow,
+                Some(51..=70) => Color::Rgb(255, 165, 0),
+                Some(_) => Color::Red,
+            };
+            // A past hour's chance of rain is moot next to what fell
+            let chance = match hour.precipitation_probability {
+                _ if is_past => String::new(),
+                Some(chance) => format!("{}%", chance),
+                None => "--".to_string(),
+            };
+            let amount = if hour.precipitation > 0.0 {
+                format!(
+                    "{:.2} {}",
+                    units.precipitation.convert(hour.precipitation),
+                    units.precipitation.symbol()
+                )
+            } else {
+                String::new()
+            };
+            Span::styled(
+                format!("{:>5}{:>10}", chance, amount),
+                Style::default().fg(color),
+            )
+        }
+        HourlyColumn::Snowfall => match hour.snowfall {
+            Some(cm) if cm > 0.0 => cell(
+                format!(
+                    "{:.1} {}",
+                    units.precipitation.convert(cm * 10.0),
+                    units.precipitation.symbol()
+                ),
+                Color::White,
+            ),
+            Some(_) => cell(String::new(), Color::White),
+            None => missing(),
+        },
+        HourlyColumn::Humidity => match hour.humidity {
+            Some(humidity) => cell(format!("{}%", humidity), Color::Cyan),
+            None => missing(),
+        },
+        HourlyColumn::DewPoint => match hour.dew_point {
+            Some(dew_point) => cell(degrees(dew_point), temperature_color_celsius(dew_point)),
+            None => missing(),
+        },
+        HourlyColumn::CloudCover => match hour.cloud_cover {
+            Some(cloud_cover) => cell(format!("{}%", cloud_cover), Color::Gray),
+            None => missing(),
+        },
+        HourlyColumn::Visibility => match hour.visibility {
+            Some(metres) => cell(format_visibility(metres, units), Color::Gray),
+            None => missing(),
+        },
+        HourlyColumn::UvIndex => match hour.uv_index {
+            Some(uv) => cell(format!("{:.0}", uv), uv_info(uv).1),
+            None => missing(),
+        },
+        HourlyColumn::Aqi => {
+            hourly_aqi_span(air_quality.and_then(|aq| aq.us_aqi_at(&hour.time)), width)
+        }
+        HourlyColumn::Waves => wave_span(
+            marine
+                .and_then(|m| m.at(&hour.time))
+                .and_then(|h| h.wave_height.zip(h.wave_period)),
+            units,
+            width,
+        ),
+    }
+}
+
+/// Visibility in miles alongside mph and knots, otherwise kilometres
+pub fn format_visibility(metres: f64, units: &UnitsConfig) -> String {
+    let (distance, symbol) = match units.wind_speed {
+        WindSpeedUnit::Mph | WindSpeedUnit::Knots => (metres / 1609.344, "mi"),
+        WindSpeedUnit::Kmh | WindSpeedUnit::Ms => (metres / 1000.0, "km"),
+    };
+    if distance >= 10.0 {
+        format!("{:.0} {}", distance, symbol)
+    } else {
+        format!("{:.1} {}", distance, symbol)
+    }
+}
+
+/// Wave height and period for one hour, colored by sea state
+fn wave_span(wave: Option<(f64, f64)>, units: &UnitsConfig, width: usize) -> Span<'static> {
+    match wave {
+        Some((height, period)) => Span::styled(
+            format!(
+                "{:>width$}",
+                format!("{} {:.0}s", format_wave_height(height, units), period),
+                width = width
+            ),
+            Style::default().fg(sea_state_info(height).1),
+        ),
+        None => Span::styled(
+            format!("{:>width$}", "--", width = width),
+            Style::default().fg(Color::DarkGray),
+        ),
+    }
+}
+
+/// Index of the first hour still worth forecasting: the current hour, or
+/// the end when every hour has passed. `now` is the location's own time,
+/// which the hours are given in.
+fn current_hour_index(hourly: &[HourlyForecast], now: NaiveDateTime) -> usize {
+    hourly
         .iter()
-        .filter(|h| {
-            if let Ok(dt) = NaiveDateTime::parse_from_str(&h.time, "%Y-%m-%dT%H:%M") {
-                let local_dt = Local.from_local_datetime(&dt).single();
-                if let Some(local_dt) = local_dt {
-                    return local_dt >= now - chrono::Duration::hours(1);
-                }
-            }
-            false
+        .position(|h| {
+            NaiveDateTime::parse_from_str(&h.time, "%Y-%m-%dT%H:%M")
+                .is_ok_and(|dt| dt >= now - chrono::Duration::hours(1))
         })
-        .count();
+        .unwrap_or(hourly.len())
+}
+
+/// Get the maximum scroll offset for hourly forecast
+pub fn get_max_hourly_scroll(
+    hourly: &[HourlyForecast],
+    visible_height: usize,
+    now: NaiveDateTime,
+) -> isize {
+    let future_count = hourly.len() - current_hour_index(hourly, now);
 
     // Account for header (2 lines)
-    future_count.saturating_sub(visible_height.saturating_sub(2))
+    future_count.saturating_sub(visible_height.saturating_sub(2)) as isize
+}
+
+/// Get the minimum scroll offset for hourly forecast, reaching back to the
+/// earliest past hour
+pub fn get_min_hourly_scroll(hourly: &[HourlyForecast], now: NaiveDateTime) -> isize {
+    -(current_hour_index(hourly, now) as isize)
 }
@@ -1,5 +1,9 @@
 use ratatui::style::Color;
 
+use crate::models::air_quality::PollenLevel;
+use crate::models::alerts::Severity;
+use crate::models::ensemble::Confidence;
+
 /// Weather condition based on WMO code
 #[derive(Debug, Clone, Copy)]
 pub enum WeatherCondition {
@@ -12,6 +16,8 @@ pub enum WeatherCondition {
     Drizzle,
     Rain,
     HeavyRain,
+    /// Rain and snow mixed
+    Sleet,
     Snow,
     HeavySnow,
     Thunderstorm,
@@ -40,6 +46,7 @@ impl WeatherCondition {
             51 | 53 | 55 | 56 | 57 => Self::Drizzle,
             61 | 63 | 66 => Self::Rain,
             65 | 67 => Self::HeavyRain,
+            68 | 69 | 83 | 84 => Self::Sleet,
             71 | 73 | 77 => Self::Snow,
             75 | 85 | 86 => Self::HeavySnow,
             80..=82 => Self::Rain,
@@ -59,6 +66,7 @@ impl WeatherCondition {
             Self::Drizzle => "Drizzle",
             Self::Rain => "Rain",
             Self::HeavyRain => "Heavy Rain",
+            Self::Sleet => "Sleet",
             Self::Snow => "Snow",
             Self::HeavySnow => "Heavy Snow",
             Self::Thunderstorm => "Thunderstorm",
@@ -77,6 +85,7 @@ impl WeatherCondition {
             Self::Drizzle => Color::LightCyan,
             Self::Rain => Color::Cyan,
             Self::HeavyRain => Color::Blue,
+            Self::Sleet => Color::LightCyan,
             Self::Snow => Color::White,
             Self::HeavySnow => Color::White,
             Self::Thunderstorm => Color::Magenta,
@@ -150,6 +159,13 @@ impl WeatherCondition {
                 " ,',',',',' ",
                 " ,',',',',' ",
             ],
+            Self::Sleet => [
+                "    .-.     ",
+                "   (   ).   ",
+                "  (___(__)  ",
+                "  ,'*',*',  ",
+                "  *',*',*'  ",
+            ],
             Self::Snow => [
                 "    .-.     ",
                 "   (   ).   ",
@@ -193,6 +209,7 @@ impl WeatherCondition {
             Self::Drizzle => "🌧️",
             Self::Rain => "🌧️",
             Self::HeavyRain => "🌧️",
+            Self::Sleet => "🌨️",
             Self::Snow => "❄️",
             Self::HeavySnow => "❄️",
             Self::Thunderstorm => "🌩️",
@@ -232,6 +249,95 @@ pub fn uv_info(uv_index: f64) -> (&'static str, Color) {
     }
 }
 
+/// Get US AQI category and color (EPA breakpoints)
+pub fn us_aqi_info(aqi: f64) -> (&'static str, Color) {
+    match aqi.round() as i32 {
+        i32::MIN..=50 => ("Good", Color::Green),
+        51..=100 => ("Moderate", Color::Yellow),
+        101..=150 => ("Unhealthy for Sensitive Groups", Color::Rgb(255, 165, 0)),
+        151..=200 => ("Unhealthy", Color::Red),
+        201..=300 => ("Very Unhealthy", Color::Magenta),
+        _ => ("Hazardous", Color::Rgb(128, 0, 35)),
+    }
+}
+
+/// Get European AQI category and color (EEA bands)
+pub fn european_aqi_info(aqi: f64) -> (&'static str, Color) {
+    match aqi.round() as i32 {
+        i32::MIN..=20 => ("Good", Color::Green),
+        21..=40 => ("Fair", Color::LightGreen),
+        41..=60 => ("Moderate", Color::Yellow),
+        61..=80 => ("Poor", Color::Red),
+        81..=100 => ("Very Poor", Color::Rgb(150, 0, 50)),
+        _ => ("Extremely Poor", Color::Magenta),
+    }
+}
+
+/// Get pollen severity label and color
+pub fn pollen_level_info(level: PollenLevel) -> (&'static str, Color) {
+    match level {
+        PollenLevel::None => ("None", Color::DarkGray),
+        PollenLevel::Low => ("Low", Color::Green),
+        PollenLevel::Moderate => ("Moderate", Color::Yellow),
+        PollenLevel::High => ("High", Color::Rgb(255, 165, 0)),
+        PollenLevel::VeryHigh => ("Very High", Color::Red),
+    }
+}
+
+/// Get forecast confidence dots, label and color
+pub fn confidence_info(confidence: Confidence) -> (&'static str, &'static str, Color) {
+    match confidence {
+        Confidence::High => ("●●●", "High", Color::Green),
+        Confidence::Medium => ("●●○", "Medium", Color::Yellow),
+        Confidence::Low => ("●○○", "Low", Color::Rgb(255, 165, 0)),
+    }
+}
+
+/// Get the color for a weather alert's severity
+pub fn alert_severity_color(severity: Severity) -> Color {
+    match severity {
+        Severity::Extreme => Color::Magenta,
+        Severity::Severe => Color::Red,
+        Severity::Moderate => Color::Rgb(255, 165, 0),
+        Severity::Minor => Color::Yellow,
+        Severity::Unknown => Color::Gray,
+    }
+}
+
+/// Get Beaufort force, description and color for a wind speed in km/h
+pub fn beaufort(kmh: f64) -> (u8, &'static str, Color) {
+    match kmh.round() as i32 {
+        i32::MIN..=0 => (0, "Calm", Color::Gray),
+        1..=5 => (1, "Light air", Color::Cyan),
+        6..=11 => (2, "Light breeze", Color::Cyan),
+        12..=19 => (3, "Gentle breeze", Color::Green),
+        20..=28 => (4, "Moderate breeze", Color::Green),
+        29..=38 => (5, "Fresh breeze", Color::LightGreen),
+        39..=49 => (6, "Strong breeze", Color::Yellow),
+        50..=61 => (7, "Near gale", Color::Yellow),
+        62..=74 => (8, "Gale", Color::Rgb(255, 165, 0)),
+        75..=88 => (9, "Strong gale", Color::Rgb(255, 165, 0)),
+        89..=102 => (10, "Storm", Color::Red),
+        103..=117 => (11, "Violent storm", Color::Red),
+        _ => (12, "Hurricane force", Color::Magenta),
+    }
+}
+
+/// Get Douglas sea state description and color for a wave height in metres
+pub fn sea_state_info(wave_height: f64) -> (&'static str, Color) {
+    match wave_height {
+        h if h < 0.1 => ("Calm", Color::Gray),
+        h if h < 0.5 => ("Smooth", Color::Cyan),
+        h if h < 1.25 => ("Slight", Color::Green),
+        h if h < 2.5 => ("Moderate", Color::Yellow),
+        h if h < 4.0 => ("Rough", Color::Rgb(255, 165, 0)),
+        h if h < 6.0 => ("Very rough", Color::Red),
+        h if h < 9.0 => ("High", Color::Red),
+        h if h < 14.0 => ("Very high", Color::Magenta),
+        _ => ("Phenomenal", Color::Magenta),
+    }
+}
+
 /// Convert wind direction degrees to cardinal direction
 pub fn wind_direction_str(degrees: i32) -> &'static str {
     // Normalize to 0-359 range
@@ -326,6 +432,16 @@ mod tests {
             }
         }
 
+        #[test]
+        fn test_sleet_codes() {
+            for code in [68, 69, 83, 84] {
+                assert!(matches!(
+                    WeatherCondition::from_wmo_code(code, true),
+                    WeatherCondition::Sleet
+                ));
+            }
+        }
+
         #[test]
         fn test_snow_codes() {
             for code in [71, 73, 77] {
@@ -482,6 +598,57 @@ mod tests {
         }
     }
 
+    mod aqi_info_tests {
+        use super::*;
+
+        #[test]
+        fn test_us_aqi_categories() {
+            assert_eq!(us_aqi_info(0.0).0, "Good");
+            assert_eq!(us_aqi_info(50.0).0, "Good");
+            assert_eq!(us_aqi_info(51.0).0, "Moderate");
+            assert_eq!(us_aqi_info(120.0).0, "Unhealthy for Sensitive Groups");
+            assert_eq!(us_aqi_info(156.0), ("Unhealthy", Color::Red));
+            assert_eq!(us_aqi_info(250.0).0, "Very Unhealthy");
+            assert_eq!(us_aqi_info(420.0).0, "Hazardous");
+        }
+
+        #[test]
+        fn test_european_aqi_categories() {
+            assert_eq!(european_aqi_info(15.0).0, "Good");
+            assert_eq!(european_aqi_info(35.0).0, "Fair");
+            assert_eq!(european_aqi_info(55.0).0, "Moderate");
+            assert_eq!(european_aqi_info(72.0).0, "Poor");
+            assert_eq!(european_aqi_info(95.0).0, "Very Poor");
+            assert_eq!(european_aqi_info(130.0).0, "Extremely Poor");
+        }
+    }
+
+    mod marine_tests {
+        use super::*;
+
+        #[test]
+        fn test_beaufort_scale() {
+            assert_eq!(beaufort(0.0).0, 0);
+            assert_eq!(beaufort(0.4).1, "Calm");
+            assert_eq!(beaufort(5.0).0, 1);
+            assert_eq!(beaufort(25.0), (4, "Moderate breeze", Color::Green));
+            assert_eq!(beaufort(61.0).1, "Near gale");
+            assert_eq!(beaufort(62.0).1, "Gale");
+            assert_eq!(beaufort(118.0).0, 12);
+            assert_eq!(beaufort(250.0).0, 12);
+        }
+
+        #[test]
+        fn test_sea_state() {
+            assert_eq!(sea_state_info(0.0).0, "Calm");
+            assert_eq!(sea_state_info(0.3).0, "Smooth");
+            assert_eq!(sea_state_info(1.24).0, "Slight");
+            assert_eq!(sea_state_info(1.25).0, "Moderate");
+            assert_eq!(sea_state_info(5.0).0, "Very rough");
+            assert_eq!(sea_state_info(15.0).0, "Phenomenal");
+        }
+    }
+
     mod wind_direction {
         use super::*;
 
@@ -0,0 +1,150 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+use crate::config::{PrecipitationUnit, UnitsConfig};
+use crate::models::marine::MarineData;
+use crate::models::CurrentWeather;
+use crate::ui::icons::{beaufort, sea_state_info, temperature_color_celsius, wind_direction_str};
+
+/// Marine panel, shown in place of today's chart in marine mode. The
+/// Beaufort force comes from the weather provider's wind, so it's shown
+/// even where there's no sea data.
+pub fn render_marine(
+    frame: &mut Frame,
+    area: Rect,
+    marine: Option<&MarineData>,
+    current: &CurrentWeather,
+    units: &UnitsConfig,
+) {
+    let block = Block::default()
+        .title(" Marine ")
+        .title_style(
+            Style::default()
+                .fg(Color::LightBlue)
+                .add_modifier(Modifier::BOLD),
+        )
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::LightBlue));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let label = |text: &'static str| Span::styled(text, Style::default().fg(Color::DarkGray));
+
+    let (force, description, color) = beaufort(current.wind_speed);
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(vec![
+            label("  Wind    "),
+            Span::styled(
+                format!("Force {} ", force),
+                Style::default().fg(color).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(description, Style::default().fg(color)),
+            Span::styled(
+                format!("  from {}", wind_direction_str(current.wind_direction)),
+                Style::default().fg(Color::Gray),
+            ),
+        ]),
+    ];
+
+    match marine.filter(|m| m.has_data()) {
+        Some(marine) => {
+            let current = &marine.current;
+            if let Some(height) = current.wave_height {
+                let (state, color) = sea_state_info(height);
+                lines.push(Line::from(vec![
+                    label("  Sea     "),
+                    Span::styled(
+                        state,
+                        Style::default().fg(color).add_modifier(Modifier::BOLD),
+                    ),
+                ]));
+            }
+            lines.push(Line::from(""));
+            lines.push(Line::from(vec![
+                label("  Waves   "),
+                Span::styled(
+                    swell_str(
+                        current.wave_height,
+                        current.wave_period,
+                        current.wave_direction,
+                        units,
+                    ),
+                    Style::default().fg(Color::White),
+                ),
+            ]));
+            lines.push(Line::from(vec![
+                label("  Swell   "),
+                Span::styled(
+                    swell_str(
+                        current.swell_wave_height,
+                        current.swell_wave_period,
+                        current.swell_wave_direction,
+                        units,
+                    ),
+                    Style::default().fg(Color::White),
+                ),
+            ]));
+            if let Some(sst) = current.sea_surface_temperature {
+                lines.push(Line::from(vec![
+                    label("  Water   "),
+                    Span::styled(
+                        format!(
+                            "{:.0}{}",
+                            units.temperature.convert(sst),
+                            units.temperature.symbol()
+                        ),
+                        Style::default().fg(temperature_color_celsius(sst)),
+                    ),
+                ]));
+            }
+        }
+        None => {
+            let message = if marine.is_some() {
+                "  No marine data for this location"
+            } else {
+                "  No sea conditions yet (r to refresh)"
+            };
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                message,
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+    }
+
+    frame.render_widget(Paragraph::new(lines), inner);
+}
+
+/// Wave height in feet alongside inches, otherwise metres
+pub fn format_wave_height(metres: f64, units: &UnitsConfig) -> String {
+    match units.precipitation {
+        PrecipitationUnit::Inch => format!("{:.1} ft", metres * 3.28084),
+        _ => format!("{:.1} m", metres),
+    }
+}
+
+fn swell_str(
+    height: Option<f64>,
+    period: Option<f64>,
+    direction: Option<f64>,
+    units: &UnitsConfig,
+) -> String {
+    let Some(height) = height else {
+        return "--".to_string();
+    };
+    let mut text = format_wave_height(height, units);
+    if let Some(period) = period {
+        text.push_str(&format!(" @ {:.0}s", period));
+    }
+    if let Some(direction) = direction {
+        text.push_str(&format!(" from {}", wind_direction_str(direction as i32)));
+    }
+    text
+}
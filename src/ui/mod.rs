@@ -1,8 +1,14 @@
+pub mod air_quality;
+pub mod alerts;
 pub mod chart;
+pub mod comparison;
 pub mod current;
 pub mod daily;
 pub mod hourly;
 pub mod icons;
+pub mod marine;
+pub mod plume;
+pub mod pollen;
 pub mod render;
 
 pub use render::render;
@@ -0,0 +1,217 @@
+use chrono::NaiveDateTime;
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    symbols::Marker,
+    text::{Line, Span},
+    widgets::{Axis, Block, Borders, Chart, Clear, Dataset, GraphType, Paragraph},
+    Frame,
+};
+
+use crate::config::UnitsConfig;
+use crate::models::ensemble::{Confidence, EnsembleData, EnsembleHour, Spread};
+use crate::ui::icons::confidence_info;
+
+/// Full-screen overlay plotting the ensemble median with the 25-75% and
+/// 10-90% member ranges for temperature and precipitation
+pub fn render_plume(
+    frame: &mut Frame,
+    area: Rect,
+    ensemble: Option<&EnsembleData>,
+    units: &UnitsConfig,
+) {
+    let popup_area = Rect::new(
+        area.x + 2,
+        area.y + 1,
+        area.width.saturating_sub(4),
+        area.height.saturating_sub(2),
+    );
+    frame.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .title(" Ensemble Forecast ")
+        .title_style(
+            Style::default()
+                .fg(Color::LightCyan)
+                .add_modifier(Modifier::BOLD),
+        )
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::LightCyan));
+    let inner = block.inner(popup_area);
+    frame.render_widget(block, popup_area);
+
+    let Some(ensemble) = ensemble.filter(|e| !e.hourly.is_empty()) else {
+        let message = Paragraph::new(Span::styled(
+            "  Ensemble forecast unavailable (r to refresh, e to close)",
+            Style::default().fg(Color::DarkGray),
+        ));
+        frame.render_widget(message, inner);
+        return;
+    };
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage(60),
+            Constraint::Min(6),
+            Constraint::Length(1),
+        ])
+        .split(inner);
+
+    let hours = &ensemble.hourly;
+    let x_max = (hours.len().div_ceil(24) * 24) as f64;
+    let day_labels: Vec<Span> = hours
+        .iter()
+        .step_by(24)
+        .filter_map(|h| NaiveDateTime::parse_from_str(&h.time, "%Y-%m-%dT%H:%M").ok())
+        .map(|dt| Span::raw(dt.format("%a").to_string()))
+        .chain(std::iter::once(Span::raw("")))
+        .collect();
+
+    let temperature = Bands::new(hours, |h| h.temperature, |c| units.temperature.convert(c));
+    render_bands(
+        frame,
+        chunks[0],
+        &temperature,
+        &format!(" Temperature ({}) ", units.temperature.symbol()),
+        (x_max, day_labels.clone()),
+        (None, 0),
+        Color::Yellow,
+    );
+
+    let precipitation = Bands::new(
+        hours,
+        |h| h.precipitation,
+        |mm| units.precipitation.convert(mm),
+    );
+    render_bands(
+        frame,
+        chunks[1],
+        &precipitation,
+        &format!(" Precipitation ({}/h) ", units.precipitation.symbol()),
+        (x_max, day_labels),
+        (Some(0.0), 2),
+        Color::Cyan,
+    );
+
+    let mut legend = vec![
+        Span::styled(" ━", Style::default().fg(Color::Yellow)),
+        Span::styled(" median  ", Style::default().fg(Color::DarkGray)),
+        Span::styled("━", Style::default().fg(Color::Gray)),
+        Span::styled(" 25-75%  ", Style::default().fg(Color::DarkGray)),
+        Span::styled("━", Style::default().fg(Color::DarkGray)),
+        Span::styled(
+            format!(" 10-90% of {} members    ", ensemble.members),
+            Style::default().fg(Color::DarkGray),
+        ),
+    ];
+    for confidence in [Confidence::High, Confidence::Medium, Confidence::Low] {
+        let (dots, label, color) = confidence_info(confidence);
+        legend.push(Span::styled(dots, Style::default().fg(color)));
+        legend.push(Span::styled(
+            format!(" {}  ", label),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    frame.render_widget(Paragraph::new(Line::from(legend)), chunks[2]);
+}
+
+/// Percentile series in display units, one point per hour
+struct Bands {
+    p10: Vec<(f64, f64)>,
+    p25: Vec<(f64, f64)>,
+    median: Vec<(f64, f64)>,
+    p75: Vec<(f64, f64)>,
+    p90: Vec<(f64, f64)>,
+    min: f64,
+    max: f64,
+}
+
+impl Bands {
+    fn new(
+        hours: &[EnsembleHour],
+        spread: impl Fn(&EnsembleHour) -> Spread,
+        convert: impl Fn(f64) -> f64,
+    ) -> Self {
+        let series = |pick: fn(&Spread) -> f64| -> Vec<(f64, f64)> {
+            hours
+                .iter()
+                .enumerate()
+                .map(|(i, h)| (i as f64, convert(pick(&spread(h)))))
+                .collect()
+        };
+        let p10 = series(|s| s.p10);
+        let p90 = series(|s| s.p90);
+        let min = p10.iter().map(|p| p.1).fold(f64::INFINITY, f64::min);
+        let max = p90.iter().map(|p| p.1).fold(f64::NEG_INFINITY, f64::max);
+        Self {
+            p25: series(|s| s.p25),
+            median: series(|s| s.median),
+            p75: series(|s| s.p75),
+            p10,
+            p90,
+            min,
+            max,
+        }
+    }
+}
+
+/// `x_axis` is the end of the range with labels at each midnight; `y_axis`
+/// is an optional floor for the range and the decimals in its labels
+fn render_bands(
+    frame: &mut Frame,
+    area: Rect,
+    bands: &Bands,
+    title: &str,
+    x_axis: (f64, Vec<Span<'static>>),
+    y_axis: (Option<f64>, usize),
+    color: Color,
+) {
+    let (x_max, x_labels) = x_axis;
+    let (y_floor, precision) = y_axis;
+    let line = |data, color| {
+        Dataset::default()
+            .marker(Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(color))
+            .data(data)
+    };
+    let datasets = vec![
+        line(&bands.p10, Color::DarkGray),
+        line(&bands.p90, Color::DarkGray),
+        line(&bands.p25, Color::Gray),
+        line(&bands.p75, Color::Gray),
+        line(&bands.median, color),
+    ];
+
+    // Pad the range a little and never let it collapse below what the
+    // labels can show, e.g. on a dry week
+    let resolution = 0.5 / 10f64.powi(precision as i32);
+    let pad = ((bands.max - bands.min) * 0.1).max(resolution);
+    let y_min = (bands.min - pad).max(y_floor.unwrap_or(f64::NEG_INFINITY));
+    let y_max = bands.max + pad;
+    let y_labels = [y_min, (y_min + y_max) / 2.0, y_max]
+        .iter()
+        .map(|v| Span::raw(format!("{:.*}", precision, v)))
+        .collect::<Vec<_>>();
+
+    let chart = Chart::new(datasets)
+        .block(
+            Block::default()
+                .title(title.to_string())
+                .title_style(Style::default().fg(color)),
+        )
+        .x_axis(
+            Axis::default()
+                .bounds([0.0, x_max])
+                .labels(x_labels)
+                .style(Style::default().fg(Color::DarkGray)),
+        )
+        .y_axis(
+            Axis::default()
+                .bounds([y_min, y_max])
+                .labels(y_labels)
+                .style(Style::default().fg(Color::DarkGray)),
+        );
+    frame.render_widget(chart, area);
+}
@@ -0,0 +1,116 @@
+use chrono::NaiveDate;
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+use crate::models::air_quality::{PollenDay, PollenType};
+use crate::ui::icons::pollen_level_info;
+
+const SPECIES_WIDTH: usize = 10;
+const TODAY_WIDTH: usize = 16;
+const OUTLOOK_WIDTH: usize = 10;
+
+/// Rows needed to show every species reported in `days`, including borders
+pub fn pollen_panel_height(days: &[PollenDay]) -> u16 {
+    species(days).len() as u16 + 3
+}
+
+/// Species reported on any day, in a fixed order
+fn species(days: &[PollenDay]) -> Vec<PollenType> {
+    PollenType::ALL
+        .into_iter()
+        .filter(|s| days.iter().any(|d| d.peak(*s).is_some()))
+        .collect()
+}
+
+/// Today's pollen counts with severity, followed by as many days of outlook
+/// as fit the width
+pub fn render_pollen(frame: &mut Frame, area: Rect, days: &[PollenDay]) {
+    let block = Block::default()
+        .title(" Pollen (grains/m³) ")
+        .title_style(
+            Style::default()
+                .fg(Color::LightYellow)
+                .add_modifier(Modifier::BOLD),
+        )
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::LightYellow));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let Some((today, outlook)) = days.split_first() else {
+        return;
+    };
+    let outlook_days =
+        (inner.width as usize).saturating_sub(SPECIES_WIDTH + TODAY_WIDTH) / OUTLOOK_WIDTH;
+    let outlook = &outlook[..outlook.len().min(outlook_days)];
+
+    let header_style = Style::default()
+        .fg(Color::Cyan)
+        .add_modifier(Modifier::BOLD);
+    let mut header = vec![
+        Span::styled(format!(" {:<w$}", "", w = SPECIES_WIDTH - 1), header_style),
+        Span::styled(format!("{:<w$}", "Today", w = TODAY_WIDTH), header_style),
+    ];
+    for day in outlook {
+        let label = NaiveDate::parse_from_str(&day.date, "%Y-%m-%d")
+            .map(|d| d.format("%a").to_string())
+            .unwrap_or_else(|_| day.date.clone());
+        header.push(Span::styled(
+            format!("{:<w$}", label, w = OUTLOOK_WIDTH),
+            header_style,
+        ));
+    }
+
+    let mut lines = vec![Line::from(header)];
+    for species in species(days) {
+        let mut row = vec![Span::styled(
+            format!(" {:<w$}", species.name(), w = SPECIES_WIDTH - 1),
+            Style::default().fg(Color::Gray),
+        )];
+
+        row.push(match today.peak(species) {
+            Some(grains) => {
+                let (label, color) = pollen_level_info(species.level(grains));
+                Span::styled(
+                    format!(
+                        "{:<w$}",
+                        format!("{:>4.0} {}", grains, label),
+                        w = TODAY_WIDTH
+                    ),
+                    Style::default().fg(color).add_modifier(Modifier::BOLD),
+                )
+            }
+            None => no_data(TODAY_WIDTH),
+        });
+
+        for day in outlook {
+            row.push(match day.peak(species) {
+                Some(grains) => {
+                    let (label, color) = pollen_level_info(species.level(grains));
+                    Span::styled(
+                        format!("{:<w$}", label, w = OUTLOOK_WIDTH),
+                        Style::default().fg(color),
+                    )
+                }
+                None => no_data(OUTLOOK_WIDTH),
+            });
+        }
+
+        lines.push(Line::from(row));
+    }
+
+    frame.render_widget(Paragraph::new(lines), inner);
+}
+
+fn no_data(width: usize) -> Span<'static> {
+    Span::styled(
+        format!("{:<w$}", "  --", w = width),
+        Style::default().fg(Color::DarkGray),
+    )
+}
@@ -1,3 +1,4 @@
+use chrono::{Local, Utc};
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
@@ -7,29 +8,58 @@ use ratatui::{
 };
 
 use crate::app::{App, AppState, UnitMenuField};
+use crate::config::ProviderKind;
+use crate::models::comparison::model_label;
+use crate::ui::air_quality::{render_air_quality, AIR_QUALITY_PANEL_WIDTH};
+use crate::ui::alerts::{format_time, render_alerts};
 use crate::ui::chart::render_today_chart;
+use crate::ui::comparison::render_comparison;
 use crate::ui::current::render_current_weather;
 use crate::ui::daily::render_daily_forecast;
 use crate::ui::hourly::render_hourly_forecast;
+use crate::ui::icons::alert_severity_color;
+use crate::ui::marine::render_marine;
+use crate::ui::plume::render_plume;
+use crate::ui::pollen::{pollen_panel_height, render_pollen};
 
 pub fn render(frame: &mut Frame, app: &App) {
     let size = frame.area();
+    // Set again below if the daily strip is drawn this frame
+    app.daily_area.set(Rect::default());
+
+    let warning_height = if app.refresh_warning.is_some() { 1 } else { 0 };
 
     // Main layout
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(3), // Header
-            Constraint::Min(10),   // Main content
-            Constraint::Length(1), // Footer
+            Constraint::Length(3),              // Header
+            Constraint::Length(warning_height), // Refresh warning
+            Constraint::Min(10),                // Main content
+            Constraint::Length(1),              // Footer
         ])
         .split(size);
 
     render_header(frame, chunks[0], app);
-    render_main_content(frame, chunks[1], app);
-    render_footer(frame, chunks[2], app);
+    if let Some(warning) = &app.refresh_warning {
+        render_refresh_warning(frame, chunks[1], app, warning);
+    }
+    render_main_content(frame, chunks[2], app);
+    render_footer(frame, chunks[3], app);
 
     // Render overlays
+    if app.show_plume {
+        render_plume(frame, chunks[2], app.ensemble.as_ref(), &app.config.units);
+    }
+
+    if app.show_comparison {
+        render_comparison(frame, chunks[2], app);
+    }
+
+    if app.show_alerts {
+        render_alerts(frame, chunks[2], app);
+    }
+
     if app.show_help {
         render_help_overlay(frame, size);
     }
@@ -50,14 +80,29 @@ fn render_header(frame: &mut Frame, area: Rect, app: &App) {
         .map(|l| l.display_name())
         .unwrap_or_else(|| "Loading...".to_string());
 
-    let last_updated = app
-        .last_updated
-        .map(|t| t.format("%l:%M %p").to_string())
-        .unwrap_or_default();
+    let (last_updated, updated_style) = match app.last_updated {
+        Some(t) if app.is_stale => {
+            // Cached data may be from an earlier day
+            let format = if t.date_naive() == Local::now().date_naive() {
+                "%l:%M %p"
+            } else {
+                "%b %e %l:%M %p"
+            };
+            (
+                format!("stale since {}", t.format(format).to_string().trim()),
+                Style::default().fg(Color::Yellow),
+            )
+        }
+        Some(t) => (
+            t.format("%l:%M %p").to_string(),
+            Style::default().fg(Color::DarkGray),
+        ),
+        None => (String::new(), Style::default()),
+    };
 
     let title = format!(" WxMan - {} ", location_str);
 
-    let header = Paragraph::new(Line::from(vec![
+    let mut spans = vec![
         Span::styled(
             title,
             Style::default()
@@ -65,17 +110,106 @@ fn render_header(frame: &mut Frame, area: Rect, app: &App) {
                 .add_modifier(Modifier::BOLD),
         ),
         Span::raw(" "),
-        Span::styled(last_updated, Style::default().fg(Color::DarkGray)),
-    ]))
-    .block(
-        Block::default()
-            .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Cyan)),
-    );
+        Span::styled(last_updated, updated_style),
+    ];
+
+    // Which Open-Meteo model the forecast comes from
+    if app.config.provider.name == ProviderKind::OpenMeteo {
+        let model = app.config.provider.model.as_deref().unwrap_or("best_match");
+        spans.push(Span::styled(
+            format!("  [{}]", model_label(model)),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+
+    if app.is_refreshing() {
+        spans.push(Span::styled(
+            format!("  {} Updating… (Esc to cancel)", app.spinner()),
+            Style::default().fg(Color::Yellow),
+        ));
+    }
+
+    // The most severe alert in force takes over the header
+    let active = app.alerts.active(Utc::now());
+    let mut border_color = Color::Cyan;
+    let mut block = Block::default().borders(Borders::ALL);
+    if let Some(alert) = active.first() {
+        border_color = alert_severity_color(alert.severity);
+        let until = alert
+            .expires
+            .map(|t| format!(" until {}", format_time(t)))
+            .unwrap_or_default();
+        let more = match active.len() {
+            1 => String::new(),
+            n => format!("+{} more · ", n - 1),
+        };
+        block = block.title(
+            Line::from(vec![
+                Span::styled(
+                    format!(" ⚠ {}{} ", alert.event.to_uppercase(), until),
+                    Style::default()
+                        .fg(Color::Black)
+                        .bg(border_color)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(
+                    format!(" {}a for details ", more),
+                    Style::default().fg(border_color),
+                ),
+            ])
+            .right_aligned(),
+        );
+    } else if !app.alerts.failed_sources.is_empty() {
+        block = block.title(
+            Line::from(Span::styled(
+                " Alerts unavailable ",
+                Style::default().fg(Color::DarkGray),
+            ))
+            .right_aligned(),
+        );
+    }
+
+    let header = Paragraph::new(Line::from(spans))
+        .block(block.border_style(Style::default().fg(border_color)));
 
     frame.render_widget(header, area);
 }
 
+/// One-line banner shown above the dashboard when a refresh failed but
+/// older data is still being displayed
+fn render_refresh_warning(frame: &mut Frame, area: Rect, app: &App, message: &str) {
+    let age = app
+        .last_updated
+        .map(|t| format!(" Showing data from {}.", format_age(Local::now() - t)))
+        .unwrap_or_default();
+
+    let banner = Paragraph::new(Line::from(vec![
+        Span::styled(
+            " ⚠ Refresh failed: ",
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(message, Style::default().fg(Color::Yellow)),
+        Span::styled(age, Style::default().fg(Color::DarkGray)),
+    ]));
+
+    frame.render_widget(banner, area);
+}
+
+fn format_age(age: chrono::Duration) -> String {
+    let minutes = age.num_minutes();
+    if minutes < 1 {
+        "just now".to_string()
+    } else if minutes < 60 {
+        format!("{} min ago", minutes)
+    } else if minutes < 48 * 60 {
+        format!("{} h ago", minutes / 60)
+    } else {
+        format!("{} days ago", minutes / (24 * 60))
+    }
+}
+
 fn render_main_content(frame: &mut Frame, area: Rect, app: &App) {
     match &app.state {
         AppState::Loading => {
@@ -86,12 +220,13 @@ fn render_main_content(frame: &mut Frame, area: Rect, app: &App) {
         }
         AppState::Ready => {
             if let Some(weather) = &app.weather {
-                // Split into top section and bottom (5-day forecast full width)
+                // Split into top section and bottom (daily forecast full width)
                 let main_rows = Layout::default()
                     .direction(Direction::Vertical)
                     .constraints([
-                        Constraint::Min(20),    // Top: Current + Chart + Hourly
-                        Constraint::Length(17), // Bottom: 5-Day forecast (full width)
+                        Constraint::Min(20), // Top: Current + Chart + Hourly
+                        // Bottom: daily forecast (full width), a row taller for normals
+                        Constraint::Length(if app.normals.is_some() { 18 } else { 17 }),
                     ])
                     .split(area);
 
@@ -113,20 +248,111 @@ fn render_main_content(frame: &mut Frame, area: Rect, app: &App) {
                     ])
                     .split(top_columns[0]);
 
-                render_current_weather(frame, left_chunks[0], &weather.current, &app.config.units);
-                render_today_chart(frame, left_chunks[1], &weather.hourly, &app.config.units);
+                match &app.air_quality {
+                    Some(air_quality) => {
+                        // Beside current conditions when there's room, else a
+                        // one-line summary underneath
+                        let (direction, constraints) = if left_chunks[0].width >= 80 {
+                            (
+                                Direction::Horizontal,
+                                [
+                                    Constraint::Min(50),
+                                    Constraint::Length(AIR_QUALITY_PANEL_WIDTH),
+                                ],
+                            )
+                        } else {
+                            (
+                                Direction::Vertical,
+                                [Constraint::Min(12), Constraint::Length(3)],
+                            )
+                        };
+                        let current_chunks = Layout::default()
+                            .direction(direction)
+                            .constraints(constraints)
+                            .split(left_chunks[0]);
+
+                        render_current_weather(
+                            frame,
+                            current_chunks[0],
+                            weather,
+                            &app.config.units,
+                            app.local_now(),
+                        );
+                        render_air_quality(frame, current_chunks[1], air_quality);
+                    }
+                    None => render_current_weather(
+                        frame,
+                        left_chunks[0],
+                        weather,
+                        &app.config.units,
+                        app.local_now(),
+                    ),
+                }
+                if app.config.display.marine {
+                    render_marine(
+                        frame,
+                        left_chunks[1],
+                        app.marine.as_ref(),
+                        &weather.current,
+                        &app.config.units,
+                    );
+                } else {
+                    render_today_chart(
+                        frame,
+                        left_chunks[1],
+                        &weather.hourly,
+                        &app.config.units,
+                        app.normals
+                            .as_ref()
+                            .and_then(|n| n.for_date(app.local_now().date())),
+                        app.local_now(),
+                    );
+                }
+
+                // Hourly takes the right column of the top section, with the
+                // pollen outlook below it where pollen data is available
+                let pollen = app
+                    .air_quality
+                    .as_ref()
+                    .map(|aq| aq.pollen.as_slice())
+                    .filter(|days| !days.is_empty());
+                let right_chunks = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([
+                        Constraint::Min(10),
+                        Constraint::Length(pollen.map(pollen_panel_height).unwrap_or(0)),
+                    ])
+                    .split(top_columns[1]);
 
-                // Hourly takes the full right column of top section
                 render_hourly_forecast(
                     frame,
-                    top_columns[1],
+                    right_chunks[0],
                     &weather.hourly,
                     &app.config.units,
+                    &app.config.display.hourly_columns,
                     app.hourly_scroll,
+                    app.air_quality.as_ref(),
+                    app.marine
+                        .as_ref()
+                        .filter(|m| app.config.display.marine && m.has_data()),
+                    app.local_now(),
                 );
+                if let Some(days) = pollen {
+                    render_pollen(frame, right_chunks[1], days);
+                }
 
-                // 5-Day forecast at bottom, full width
-                render_daily_forecast(frame, main_rows[1], &weather.daily, &app.config.units);
+                // Daily forecast at bottom, full width
+                app.daily_area.set(main_rows[1]);
+                render_daily_forecast(
+                    frame,
+                    main_rows[1],
+                    &weather.daily,
+                    app.daily_scroll,
+                    &app.config.units,
+                    app.history.as_ref(),
+                    app.normals.as_ref(),
+                    app.ensemble.as_ref(),
+                );
             }
         }
     }
@@ -199,7 +425,7 @@ fn render_footer(frame: &mut Frame, area: Rect, app: &App) {
 fn render_help_overlay(frame: &mut Frame, area: Rect) {
     // Center the help box
     let popup_width = 50;
-    let popup_height = 16;
+    let popup_height = 23;
     let popup_x = (area.width.saturating_sub(popup_width)) / 2;
     let popup_y = (area.height.saturating_sub(popup_height)) / 2;
 
@@ -211,8 +437,12 @@ fn render_help_overlay(frame: &mut Frame, area: Rect) {
     let help_text = vec![
         Line::from(""),
         Line::from(vec![
-            Span::styled("  q / Esc", Style::default().fg(Color::Yellow)),
-            Span::raw("     Quit application"),
+            Span::styled("  q", Style::default().fg(Color::Yellow)),
+            Span::raw("           Quit application"),
+        ]),
+        Line::from(vec![
+            Span::styled("  Esc", Style::default().fg(Color::Yellow)),
+            Span::raw("         Cancel refresh, otherwise quit"),
         ]),
         Line::from(vec![
             Span::styled("  r", Style::default().fg(Color::Yellow)),
@@ -226,14 +456,38 @@ fn render_help_overlay(frame: &mut Frame, area: Rect) {
             Span::styled("  u", Style::default().fg(Color::Yellow)),
             Span::raw("           Configure units"),
         ]),
+        Line::from(vec![
+            Span::styled("  m", Style::default().fg(Color::Yellow)),
+            Span::raw("           Toggle marine mode"),
+        ]),
+        Line::from(vec![
+            Span::styled("  e", Style::default().fg(Color::Yellow)),
+            Span::raw("           Ensemble forecast plume"),
+        ]),
+        Line::from(vec![
+            Span::styled("  c", Style::default().fg(Color::Yellow)),
+            Span::raw("           Compare weather models"),
+        ]),
+        Line::from(vec![
+            Span::styled("  a", Style::default().fg(Color::Yellow)),
+            Span::raw("           Weather alert details"),
+        ]),
         Line::from(vec![
             Span::styled("  ↑ / k", Style::default().fg(Color::Yellow)),
-            Span::raw("       Scroll hourly forecast up"),
+            Span::raw("       Scroll hourly forecast up (past)"),
         ]),
         Line::from(vec![
             Span::styled("  ↓ / j", Style::default().fg(Color::Yellow)),
             Span::raw("       Scroll hourly forecast down"),
         ]),
+        Line::from(vec![
+            Span::styled("  ← / →", Style::default().fg(Color::Yellow)),
+            Span::raw("       Scroll daily forecast (or wheel)"),
+        ]),
+        Line::from(vec![
+            Span::styled("  Home", Style::default().fg(Color::Yellow)),
+            Span::raw("        Back to today"),
+        ]),
         Line::from(vec![
             Span::styled("  ?", Style::default().fg(Color::Yellow)),
             Span::raw("           Toggle this help"),
@@ -357,9 +611,10 @@ fn render_units_menu(frame: &mut Frame, area: Rect, app: &App) {
 }
 
 fn render_location_input(frame: &mut Frame, area: Rect, app: &App) {
-    // Center the input box
-    let popup_width = 50;
-    let popup_height = 10;
+    // Center the input box, growing it to fit the list of matches
+    let results = app.location_results.len() as u16;
+    let popup_width = if results > 0 { 72 } else { 50 }.min(area.width);
+    let popup_height = if results > 0 { 11 + results } else { 10 }.min(area.height);
     let popup_x = (area.width.saturating_sub(popup_width)) / 2;
     let popup_y = (area.height.saturating_sub(popup_height)) / 2;
 
@@ -398,24 +653,74 @@ fn render_location_input(frame: &mut Frame, area: Rect, app: &App) {
         ]),
     ];
 
-    // Show error if any
-    if let Some(error) = &app.location_error {
+    // Show matches to pick from, lookup progress, or error if any
+    if app.has_location_results() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            "  Select a match:",
+            Style::default().fg(Color::Gray),
+        )));
+        for (i, location) in app.location_results.iter().enumerate() {
+            let selected = i == app.location_selection;
+            let mut name = location.city.clone();
+            if let Some(region) = &location.region {
+                name.push_str(&format!(", {}", region));
+            }
+            if !location.country.is_empty() {
+                name.push_str(&format!(", {}", location.country));
+            }
+            let (marker, style) = if selected {
+                (
+                    "  ▶ ",
+                    Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD),
+                )
+            } else {
+                ("    ", Style::default().fg(Color::White))
+            };
+            lines.push(Line::from(vec![
+                Span::styled(marker, style),
+                Span::styled(name, style),
+                Span::styled(
+                    format!("  ({:.2}, {:.2})", location.latitude, location.longitude),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]));
+        }
+    } else if app.is_looking_up_location() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            format!("  {} Looking up location...", app.spinner()),
+            Style::default().fg(Color::Yellow),
+        )));
+    } else if let Some(error) = &app.location_error {
         lines.push(Line::from(""));
         lines.push(Line::from(Span::styled(
             format!("  {}", error),
             Style::default().fg(Color::Red),
         )));
     } else {
+        let hint = if app.config.geolocation.enabled {
+            "  Leave empty for auto-detect (IP)"
+        } else {
+            "  IP auto-detect is off; enter a place"
+        };
         lines.push(Line::from(""));
         lines.push(Line::from(Span::styled(
-            "  Leave empty for auto-detect (IP)",
+            hint,
             Style::default().fg(Color::DarkGray),
         )));
     }
 
+    let hint = if app.has_location_results() {
+        "  ↑↓ to choose, Enter to confirm, Esc to edit"
+    } else {
+        "  Enter to confirm, Esc to cancel"
+    };
     lines.push(Line::from(""));
     lines.push(Line::from(Span::styled(
-        "  Enter to confirm, Esc to cancel",
+        hint,
         Style::default().fg(Color::DarkGray),
     )));
 